@@ -53,6 +53,23 @@ pub fn enum_parameter(input: proc_macro::TokenStream) -> proc_macro::TokenStream
                 Parameter(Self::iter().position(|s| s == self).unwrap() as i16)
             }
         }
+
+        impl #impl_generics DisplayParameter for #name #ty_generics #where_clause {
+            fn unit() -> &'static str {
+                ""
+            }
+
+            fn range() -> String {
+                Self::iter().map(|v| v.display()).collect::<Vec<_>>().join(", ")
+            }
+
+            fn display(&self) -> String {
+                match serde_json::to_value(self) {
+                    Ok(serde_json::Value::String(s)) => s,
+                    _ => format!("{:?}", self)
+                }
+            }
+        }
     }.into()
 }
 
@@ -111,6 +128,21 @@ pub fn discrete_values_serialization(input: proc_macro::TokenStream) -> proc_mac
             }
         }
 
+        impl #impl_generics DisplayParameter for #name #ty_generics #where_clause {
+            fn unit() -> &'static str {
+                Self::UNIT
+            }
+
+            fn range() -> String {
+                let values = Self::values();
+                format!("{} to {}", Self::readable(values[0]), Self::readable(values[values.len() - 1]))
+            }
+
+            fn display(&self) -> String {
+                Self::readable(self.0)
+            }
+        }
+
         impl #impl_generics Into<Parameter> for #name #ty_generics #where_clause {
             fn into(self) -> Parameter {
                 if let Some(position) = Self::values().iter().position(|v| Self::equal(v, &self.0)) {
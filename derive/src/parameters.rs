@@ -39,8 +39,11 @@ pub fn impl_parameters(name: &Ident, fields: &Vec<&Field>, generics: &Generics)
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let mut size: usize = 0;
     let mut inner = TokenStream::new();
+    let mut describe = TokenStream::new();
+    let mut describe_fields = TokenStream::new();
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
+        let field_str = field_name.to_string();
         if let Some(len) = get_array_len(&field.ty) {
             // expects an Array of Parameter
             inner.append_all(quote! {
@@ -48,12 +51,22 @@ pub fn impl_parameters(name: &Ident, fields: &Vec<&Field>, generics: &Generics)
                     p.push(*element);
                 }
             });
+            describe_fields.append_all(quote! {
+                ParameterField::unused(#field_str),
+            });
             size += len;
         } else {
-            // expects a type which implements Into<Parameter>
+            // expects a type which implements Into<Parameter> and DisplayParameter
             inner.append_all(quote! {
                 p.push(self.#field_name.into());
             });
+            let field_type = &field.ty;
+            describe.append_all(quote! {
+                ParameterInfo::of(#field_str, &self.#field_name),
+            });
+            describe_fields.append_all(quote! {
+                ParameterField::of::<#field_type>(#field_str),
+            });
             size += 1;
         }
     }
@@ -64,6 +77,18 @@ pub fn impl_parameters(name: &Ident, fields: &Vec<&Field>, generics: &Generics)
                 #inner
                 p.try_into().unwrap()
             }
+
+            fn describe(&self) -> Vec<ParameterInfo> {
+                vec![
+                    #describe
+                ]
+            }
+
+            fn fields() -> Vec<ParameterField> {
+                vec![
+                    #describe_fields
+                ]
+            }
         }
     }
 }
@@ -93,6 +93,10 @@ impl BitStream {
         self.bits.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
     pub fn reset(&mut self) {
         self.index = 0;
     }
@@ -4,7 +4,7 @@ use std::error::Error;
 mod bits;
 mod bit_stream;
 
-pub use bits::{Bit, Bits};
+pub use bits::{Bit, Bits, BitsError};
 pub use bit_stream::BitStream;
 
 #[derive(Debug)]
@@ -0,0 +1,907 @@
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use crate::json::lint::LintConfig;
+use crate::json::migration::{json_version, migrate_json, migrate_structure, FORMAT_VERSION};
+use crate::json::patch::LiveSetPatch;
+use crate::json::presets::{EffectPreset, PresetLibrary};
+use crate::json::selection::{set_label, SetSelection};
+use crate::json::set_list::SetList;
+use crate::json::transform::{transform, Edit, LiveSetFilter, TransformError};
+use crate::json::tuning::{KeyboardMapping, ScalaScale, TuningSource};
+use crate::json::keyboard::keyboard_map;
+use crate::json::effects::effects_summary;
+use crate::json::format::FileFormat;
+use crate::json::sheet::{CueSheet, SheetFormat, SheetNotes};
+use crate::json::templates::{Templated, Templates};
+use crate::json::warnings::Warnings;
+use schemars::schema_for;
+use validator::Validate;
+
+use crate::{Bytes, Json, RD300NX};
+use crate::json::{self, StructuredJson, SplitManifest, UpdateSummary, SplitDepth};
+use crate::roland::tones::{Tone, ToneQuery};
+use crate::roland::types::effects::Effect;
+use crate::roland::types::enums::Layer;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn run() -> Result<(), Box<dyn Error>> {
+    env::set_var("RUST_BACKTRACE", "1");
+    let mut args = env::args();
+    let cmd = args.next().unwrap();
+    if let Some(verb) = args.next() {
+        match verb.as_str() {
+            "decode" => decode(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output JSON file (or '-' for STDOUT)")?),
+                args.next()
+            )?,
+            "encode" => encode(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS file (or '-' for STDOUT)")?)
+            )?,
+            "validate" => validate(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?),
+            )?,
+            "tone-remain" => tone_remain(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?),
+            )?,
+            "split" => {
+                let input_json = optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?);
+                let output_folder = args.next().ok_or("The 3rd argument should be the FOLDER for the JSON file to be split into (and must not exist, unless using --update)")?;
+                let mut depth = None;
+                let mut update = false;
+                for arg in args.by_ref() {
+                    if arg == "--update" {
+                        update = true;
+                    } else {
+                        depth = Some(arg.parse()?);
+                    }
+                }
+                split(input_json, output_folder, depth, update)?
+            },
+            "merge" => merge(
+                args.next().ok_or("The 2nd argument should be the FOLDER containing the JSON data to combine")?,
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output JSON file (or '-' for STDOUT)")?),
+            )?,
+            "flatten" => flatten(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output JSON file (or '-' for STDOUT)")?)
+            )?,
+            "extract-template" => extract_template(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input JSON file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output JSON file (or '-' for STDOUT)")?),
+                args.next().ok_or("The 4th argument should be the NAME of the template to create")?,
+                args.next().map(|s| s.parse()).transpose()?
+            )?,
+            "apply" => apply(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                args.next().ok_or("The 3rd argument should be the FILENAME for the patch JSON file")?,
+                optional(args.next().ok_or("The 4th argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?),
+                args.next().ok_or("The 5th argument should be the live SETS to apply the patch to (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?
+            )?,
+            "build" => build(
+                args.next().ok_or("The 2nd argument should be the FILENAME for the set list YAML or JSON file")?,
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?)
+            )?,
+            "show" => show(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                args.next().ok_or("The 3rd argument should be the live SETS to show (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?
+            )?,
+            "show-effects" => {
+                let input = optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?);
+                let sets = args.next().ok_or("The 3rd argument should be the live SETS to show the effects of (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?;
+                let ranges = args.next().map(|arg| arg == "--ranges").unwrap_or(false);
+                show_effects(input, sets, ranges)?
+            },
+            "reset-effect" => reset_effect(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?),
+                args.next().ok_or("The 4th argument should be the live SETS to reset the effect of (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?,
+                args.next().ok_or("The 5th argument should be the EFFECT to reset (mfx, reverb or chorus)")?.parse()?,
+                args.next()
+            )?,
+            "save-preset" => save_preset(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                args.next().ok_or("The 3rd argument should be the live SET to save the effect of (eg. '3' or 'piano/1')")?.parse()?,
+                args.next().ok_or("The 4th argument should be the EFFECT to save (mfx, reverb or chorus)")?.parse()?,
+                args.next().ok_or("The 5th argument should be the FOLDER of the preset library")?,
+                args.next().ok_or("The 6th argument should be the NAME of the preset to save")?
+            )?,
+            "list-presets" => list_presets(
+                args.next().ok_or("The 2nd argument should be the FOLDER of the preset library")?
+            )?,
+            "apply-preset" => apply_preset(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?),
+                args.next().ok_or("The 4th argument should be the live SETS to apply the preset to (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?,
+                args.next().ok_or("The 5th argument should be the FOLDER of the preset library")?,
+                args.next().ok_or("The 6th argument should be the NAME of the preset to apply")?
+            )?,
+            "export-sheet" => {
+                let input = optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?);
+                let output = optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output Markdown or HTML file (or '-' for STDOUT)")?);
+                let mut sets = None;
+                let mut notes = None;
+                while let Some(arg) = args.next() {
+                    if arg == "--notes" {
+                        notes = Some(args.next().ok_or("The argument after --notes should be the FILENAME for the notes YAML or JSON file")?);
+                    } else {
+                        sets = Some(arg.parse()?);
+                    }
+                }
+                export_sheet(input, output, sets, notes)?
+            },
+            "migrate" => migrate(
+                args.next().ok_or("The 2nd argument should be the FILENAME (or FOLDER) of the JSON data to upgrade in place")?
+            )?,
+            "import-tuning" => {
+                let input = optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?);
+                let output = optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?);
+                let sets = args.next().ok_or("The 4th argument should be the live SETS to tune (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?;
+                let layer = args.next().ok_or("The 5th argument should be the LAYER to tune (upper1, upper2 or lower1)")?.parse()?;
+                let scale_file = args.next().ok_or("The 6th argument should be the FILENAME for the Scala scale (.scl) file, or --temperament")?;
+                let source = if scale_file == "--temperament" {
+                    let temperament = args.next().ok_or("The argument after --temperament should be the TEMPERAMENT (eg. 'kirnberger')")?.parse()?;
+                    let key = args.next().map(|k| k.parse()).transpose()?.unwrap_or_default();
+                    TuningSource::Temperament(temperament, key)
+                } else {
+                    let mapping = match args.next() {
+                        Some(file) => KeyboardMapping::load(&PathBuf::from(file))?,
+                        None => KeyboardMapping::default()
+                    };
+                    TuningSource::Scala(ScalaScale::load(&PathBuf::from(scale_file))?, mapping)
+                };
+                import_tuning(input, output, sets, layer, source)?
+            },
+            "export-tuning" => export_tuning(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?),
+                args.next().ok_or("The 3rd argument should be the live SET to export the tuning of (eg. '3' or 'piano/1')")?.parse()?,
+                args.next().ok_or("The 4th argument should be the LAYER to export the tuning of (upper1, upper2 or lower1)")?.parse()?,
+                optional(args.next().ok_or("The 5th argument should be the FILENAME for the output Scala scale (.scl) file (or '-' for STDOUT)")?),
+                args.next()
+            )?,
+            "map" => {
+                let input = optional(args.next().ok_or("The 2nd argument should be the FILENAME for the input RDS or JSON file (or '-' for STDIN)")?);
+                let output = optional(args.next().ok_or("The 3rd argument should be the FILENAME for the output RDS or JSON file (or '-' for STDOUT)")?);
+                let mut filter = LiveSetFilter::default();
+                let mut edits = Vec::new();
+                while let Some(arg) = args.next() {
+                    if arg == "--bank" {
+                        filter.set_bank(&args.next().ok_or("The argument after --bank should be the BANK of live sets to edit (user_sets, piano or e_piano)")?)?;
+                    } else if arg == "--sets" {
+                        filter.sets = Some(args.next().ok_or("The argument after --sets should be the live SETS to edit (eg. '1,3,10-20' or 'piano/1-5')")?.parse()?);
+                    } else if arg == "--name" {
+                        filter.set_name(&args.next().ok_or("The argument after --name should be a REGEX which the names of live sets to edit must match")?)?;
+                    } else if arg == "--tone" {
+                        filter.tone = Some(args.next().ok_or("The argument after --tone should be the TONE which live sets to edit must use")?.parse()?);
+                    } else {
+                        edits.push(arg.parse()?);
+                    }
+                }
+                if edits.is_empty() {
+                    return Err("At least one EDIT should be given (eg. 'volume-=6' or 'key_touch_velocity=real')".into());
+                }
+                map(input, output, filter, edits)?
+            },
+            "tones" => {
+                let mut query = ToneQuery::default();
+                let mut input = None;
+                while let Some(arg) = args.next() {
+                    if arg == "--category" {
+                        query.set_category(&args.next().ok_or("The argument after --category should be the CATEGORY of tones to list")?)?;
+                    } else if arg == "--in" {
+                        input = Some(optional(args.next().ok_or("The argument after --in should be the FILENAME for the RDS or JSON file to search (or '-' for STDIN)")?));
+                    } else {
+                        query.name = Some(arg);
+                    }
+                }
+                tones(query, input)?
+            },
+            "renumber" => renumber(
+                args.next().ok_or("The 2nd argument should be the FOLDER containing the JSON data to renumber")?
+            )?,
+            "schema" => schema(
+                optional(args.next().ok_or("The 2nd argument should be the FILENAME for the output JSON schema file (or '-' for STDOUT)")?)
+            )?,
+            "help" => help(&cmd),
+            _ => {
+                println!("The 1st argument did not contain a valid command: {}", verb);
+                help(&cmd)
+            }
+        }
+    } else {
+        help(&cmd)
+    }
+    Ok(())
+}
+
+fn optional(arg: String) -> Option<String> {
+    if arg == "-" {
+        None
+    } else {
+        Some(arg)
+    }
+}
+
+fn help(cmd: &str) {
+    println!("roland-rds (v{})", VERSION);
+    println!("Usage:");
+    println!("  {} decode INPUT.RDS OUTPUT.JSON [PREVIOUS.JSON]", cmd);
+    println!("                                     -- read RDS file and write to JSON file (keeping metadata from PREVIOUS.JSON)");
+    println!("  {} encode INPUT.JSON OUTPUT.RDS     -- read JSON file (or folder) and write to RDS file", cmd);
+    println!("  {} validate INPUT.JSON              -- read JSON file (or folder) and validate its contents", cmd);
+    println!("  {} tone-remain INPUT.JSON           -- read JSON file and report on each tone remain transition", cmd);
+    println!("  {} split INPUT.JSON OUTPUT_FOLDER [DEPTH] [--update]", cmd);
+    println!("                                     -- split JSON file into a folder structure of nested JSON files,");
+    println!("                                        where DEPTH is 'set', 'section' (default) or 'full', and");
+    println!("                                        --update rewrites only changed files in an existing folder");
+    println!("  {} merge INPUT_FOLDER OUTPUT.JSON   -- merge folder structure of nested JSON files into a JSON file", cmd);
+    println!("  {} flatten INPUT.JSON OUTPUT.JSON  -- read JSON file (or folder) and write to JSON file with all templates resolved", cmd);
+    println!("  {} extract-template INPUT.JSON OUTPUT.JSON NAME [SETS]", cmd);
+    println!("                                     -- factor the common fields of live SETS (default all user sets) out into a template");
+    println!("  {} apply INPUT.RDS PATCH.JSON OUTPUT.RDS SETS", cmd);
+    println!("                                     -- apply a JSON merge patch (RFC 7386) or JSON patch (RFC 6902) to live SETS,");
+    println!("                                        where INPUT/OUTPUT can each be an RDS or JSON file");
+    println!("  {} build SETLIST.YAML OUTPUT.RDS   -- build RDS (or JSON) file from a set list of live set JSON files", cmd);
+    println!("  {} show INPUT.RDS SETS              -- draw a keyboard map of the layer ranges in live SETS", cmd);
+    println!("  {} show-effects INPUT.RDS SETS [--ranges]", cmd);
+    println!("                                     -- list the MFX, reverb and chorus parameters of live SETS in their units (and ranges)");
+    println!("  {} reset-effect INPUT.RDS OUTPUT.RDS SETS EFFECT [TYPE]", cmd);
+    println!("                                     -- reset the parameters of EFFECT (mfx, reverb or chorus) in live SETS to the defaults");
+    println!("                                        for its current type, or change it to TYPE (eg. 'Rotary') with its defaults");
+    println!("  {} save-preset INPUT.RDS SET EFFECT PRESETS_FOLDER NAME", cmd);
+    println!("                                     -- save EFFECT (mfx, reverb or chorus) of live SET into a preset library as NAME");
+    println!("  {} list-presets PRESETS_FOLDER      -- list the effect presets in a preset library", cmd);
+    println!("  {} apply-preset INPUT.RDS OUTPUT.RDS SETS PRESETS_FOLDER NAME", cmd);
+    println!("                                     -- replace the effect of live SETS with the preset NAME from a preset library");
+    println!("  {} map INPUT.RDS OUTPUT.RDS EDIT... [--bank BANK] [--sets SETS] [--name REGEX] [--tone TONE]", cmd);
+    println!("                                     -- apply each EDIT (eg. 'volume-=6', 'tone=OLD:NEW' or 'key_touch_velocity=real')");
+    println!("                                        to every live set matching the filters, and list what changed");
+    println!("  {} export-sheet INPUT.RDS OUTPUT.MD [SETS] [--notes NOTES.YAML]", cmd);
+    println!("                                     -- write a cue sheet of live SETS (default all named user sets) as Markdown,");
+    println!("                                        or HTML if OUTPUT ends in '.html', with optional notes for each set");
+    println!("  {} migrate INPUT.JSON               -- upgrade JSON file (or folder) written by an older version to the current format", cmd);
+    println!("  {} import-tuning INPUT.RDS OUTPUT.RDS SETS LAYER SCALE.SCL [MAPPING.KBM]", cmd);
+    println!("                                     -- set the micro tuning of LAYER in live SETS from a Scala scale (and keyboard mapping)");
+    println!("  {} import-tuning INPUT.RDS OUTPUT.RDS SETS LAYER --temperament TEMPERAMENT [KEY]", cmd);
+    println!("                                     -- set the micro tuning of LAYER in live SETS to a TEMPERAMENT (eg. 'kirnberger') in KEY (default C)");
+    println!("  {} export-tuning INPUT.RDS SET LAYER OUTPUT.SCL [OUTPUT.KBM]", cmd);
+    println!("                                     -- write the micro tuning of LAYER in live SET as a Scala scale (and keyboard mapping)");
+    println!("  {} tones [NAME] [--category CATEGORY] [--in INPUT.RDS]", cmd);
+    println!("                                     -- list tones (by NAME/number and/or CATEGORY, eg. 'piano', 'epiano', 'organ',");
+    println!("                                        'strings', 'pads' or 'gm2') with their MSB/LSB/PC, or the live sets using them");
+    println!("  {} renumber INPUT_FOLDER            -- rewrite the number prefixes in a folder structure to be sequential", cmd);
+    println!("  {} schema OUTPUT.JSON               -- write JSON schema to JSON file", cmd);
+    println!("In all instances, '-' can be used as a file argument to indicate STDIN or STDOUT, however");
+    println!("  - folders cannot be STDIN/STDOUT and must be specified");
+    println!("  - STDIN/STDOUT does not support binary data on Windows");
+    println!("JSON files can instead be YAML or TOML files, chosen by their extension ('.yaml', '.yml' or '.toml'), and");
+    println!("  - split writes the folder in the format of INPUT, which is recorded in the folder for merging");
+    println!("Warnings can be configured with a '{}' file next to the JSON file (or inside the folder)", LintConfig::FILE_NAME);
+    println!("SETS is a list of live set numbers or ranges, optionally prefixed by bank, eg. '1,3,10-20' or 'user_sets/1-5,piano/2'");
+    println!("Templates are read from a '{}' folder next to the JSON file (or inside the folder)", json::templates::TEMPLATES_FOLDER);
+}
+
+fn decode(input_rds: Option<String>, output_json: Option<String>, previous_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let (size, bytes) = read_data(&input_rds)?;
+    if size != RD300NX::BYTE_SIZE {
+        Err(format!("File should be {} bytes but found {}", RD300NX::BYTE_SIZE, size).into())
+    } else {
+        let mut rds = RD300NX::from_bytes(bytes.try_into().unwrap())?;
+        if rds.validate().is_err() {
+            return Err(format!("Data validation failed.").into());
+        }
+        if let Some(previous) = &previous_json {
+            let count = rds.reattach_metadata(&*read_json(&Some(previous.clone()))?)?;
+            if output_json.is_some() {
+                println!("Re-attached metadata for {} live sets from '{}'", count, previous);
+            }
+        }
+        write_json(&output_json, rds.to_json())?;
+        if let Some(file) = &output_json {
+            println!("Decoded RDS data into '{}'", file);
+        }
+        Ok(())
+    }
+}
+
+fn encode(input_json: Option<String>, output_rds: Option<String>) -> Result<(), Box<dyn Error>> {
+    let rds = read_json(&input_json)?;
+    if rds.validate().is_err() {
+        return Err(format!("Data validation failed.").into());
+    }
+    if let Err(failed) = LintConfig::find(&input_json)?.apply(rds.warnings()).check() {
+        for error in &failed.errors {
+            eprintln!("- {}", error);
+        }
+        return Err(failed.to_string().into());
+    }
+    write_data(&output_rds, &*rds.to_bytes()?)?;
+    if let Some(file) = &output_rds {
+        println!("Encoded RDS data into '{}'", file);
+    }
+    Ok(())
+}
+
+fn validate(input_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let rds = read_json(&input_json)?;
+    if let Err(errors) = rds.validate() {
+        for (p, e) in errors.into_errors() {
+            println!("Error with {}: {:?}", p, e);
+        }
+    } else {
+        let lint = LintConfig::find(&input_json)?.apply(rds.warnings());
+        if !lint.errors.is_empty() {
+            println!("{} lint errors: ", lint.errors.len());
+            for error in &lint.errors {
+                println!("- {}", error);
+            }
+        }
+        if !lint.warnings.is_empty() {
+            println!("{} warnings: ", lint.warnings.len());
+            for warning in &lint.warnings {
+                println!("- {}", warning);
+            }
+        }
+        if lint.errors.is_empty() && lint.warnings.is_empty() {
+            println!("Validation completed with no errors or warnings.");
+        }
+    }
+    Ok(())
+}
+
+fn tone_remain(input_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let rds = read_json(&input_json)?;
+    let transitions = rds.tone_remain_report();
+    if transitions.is_empty() {
+        println!("Tone remain is OFF for all live sets.");
+        return Ok(());
+    }
+    let mut problems = 0;
+    for transition in &transitions {
+        if transition.is_seamless() {
+            println!("{} -> {} ({} -> {}): seamless", transition.set_before(), transition.set_after(), transition.from_name, transition.to_name);
+        } else {
+            problems += 1;
+            println!("{} -> {} ({} -> {}): {} issues", transition.set_before(), transition.set_after(), transition.from_name, transition.to_name, transition.issues.len());
+            for issue in &transition.issues {
+                println!("  - {}", issue.reason);
+                println!("    so {}", issue.effect);
+                println!("    fix: in {}, {}", transition.set_after(), issue.suggestion);
+            }
+        }
+    }
+    println!("{} of {} tone remain transitions are not seamless.", problems, transitions.len());
+    Ok(())
+}
+
+fn split(input_json: Option<String>, output_folder: String, depth: Option<SplitDepth>, update: bool) -> Result<(), Box<dyn Error>> {
+    let (rds, templated) = read_templated_json(&input_json)?;
+    let folder = PathBuf::from(&output_folder);
+    // the files are written in the format of the input file, otherwise the format they were already split into
+    let input_format = input_json.as_ref().and_then(|f| FileFormat::from_path(&PathBuf::from(f)));
+    if update && folder.is_dir() {
+        let manifest = SplitManifest::load(&folder)?;
+        let depth = match depth {
+            Some(d) => d,
+            None => manifest.as_ref().map(|m| m.depth).unwrap_or_default()
+        };
+        let existing_format = manifest.map(|m| m.format).unwrap_or_default();
+        let format = input_format.unwrap_or(existing_format);
+        if format != existing_format {
+            return Err(format!("'{}' was split as {:?}, so it cannot be updated from {:?} (split into a new folder instead)", output_folder, existing_format, format).into());
+        }
+        let mut summary = UpdateSummary::default();
+        let mut structure = rds.to_structured_json_at_depth(depth);
+        templated.unresolve_structure(&mut structure);
+        structure.update(folder.clone(), format, &mut summary)?;
+        SplitManifest::new(depth, format).save(&folder)?;
+        for file in &summary.added {
+            println!("+ {}", file.display());
+        }
+        for file in &summary.changed {
+            println!("~ {}", file.display());
+        }
+        for file in &summary.removed {
+            println!("- {}", file.display());
+        }
+        println!("Updated '{}': {} files added, {} changed, {} removed", output_folder, summary.added.len(), summary.changed.len(), summary.removed.len());
+        return Ok(());
+    }
+    let depth = depth.unwrap_or_default();
+    let format = input_format.unwrap_or_default();
+    let mut structure = rds.to_structured_json_at_depth(depth);
+    templated.unresolve_structure(&mut structure);
+    let count = structure.save(folder.clone(), format)?;
+    SplitManifest::new(depth, format).save(&folder)?;
+    println!("Split JSON into {} files in '{}'", count.files, output_folder);
+    let lint_config = LintConfig::path_for(&input_json);
+    if input_json.is_some() && lint_config.is_file() {
+        fs::copy(&lint_config, PathBuf::from(&output_folder).join(LintConfig::FILE_NAME))?;
+        println!("Copied lint config '{}' into '{}'", lint_config.display(), output_folder);
+    }
+    Ok(())
+}
+
+fn merge(input_folder: String, output_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let depth = SplitManifest::load(&PathBuf::from(&input_folder))?.map(|m| m.depth).unwrap_or_default();
+    let (mut structure, _) = load_structure(&input_folder)?;
+    let templated = Templated::resolve_structure(&mut structure)?;
+    let rds = RD300NX::from_structured_json(structure)?;
+    write_templated_json(&output_json, &rds, &templated)?;
+    if let Some(file) = &output_json {
+        println!("Merged JSON (split at {:?} depth) into '{}'", depth, file);
+    }
+    Ok(())
+}
+
+fn flatten(input_json: Option<String>, output_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let rds = read_json(&input_json)?;
+    write_json(&output_json, rds.to_json())?;
+    if let Some(file) = &output_json {
+        println!("Flattened JSON into '{}'", file);
+    }
+    Ok(())
+}
+
+fn extract_template(input_json: Option<String>, output_json: Option<String>, name: String, sets: Option<SetSelection>) -> Result<(), Box<dyn Error>> {
+    let (rds, mut templated) = read_templated_json(&input_json)?;
+    if templated.templates.get(&name).is_some() {
+        return Err(format!("A template named '{}' already exists", name).into());
+    }
+    let sets = match sets {
+        Some(s) => s,
+        None => format!("1-{}", RD300NX::USER_SETS).parse()?
+    };
+    templated.extract_template(&serde_json::to_value(&*rds)?, &sets, name.clone());
+    write_templated_json(&output_json, &rds, &templated)?;
+    if let Some(file) = &output_json {
+        println!("Extracted template '{}' from {} live sets into '{}'", name, sets.sets().len(), file);
+    }
+    Ok(())
+}
+
+fn apply(input: Option<String>, patch_file: String, output: Option<String>, sets: SetSelection) -> Result<(), Box<dyn Error>> {
+    let patch = LiveSetPatch::from_json(&fs::read_to_string(&patch_file)?)?;
+    let (mut rds, mut templated, input_is_rds) = read_rds_or_json(&input)?;
+    let mut errors = Vec::new();
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set_mut(bank, *index).unwrap(); // safe because selections only contain valid sets
+        match patch.apply(live_set) {
+            Ok(patched) => *live_set = patched,
+            Err(e) => errors.push(format!("{}: {}", set_label(bank, *index), e))
+        }
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("- {}", error);
+        }
+        return Err(format!("Patch could not be applied to {} live sets.", errors.len()).into());
+    }
+    if let Err(errors) = rds.validate() {
+        for (p, e) in errors.into_errors() {
+            eprintln!("Error with {}: {:?}", p, e);
+        }
+        return Err("Data validation failed.".into());
+    }
+    templated.sets.retain(|s| !sets.contains(s.bank, s.index)); // patched live sets are written in full
+    if is_rds_output(&output, input_is_rds) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_templated_json(&output, &rds, &templated)?;
+    }
+    if let Some(file) = &output {
+        println!("Applied patch '{}' to {} live sets into '{}'", patch_file, sets.sets().len(), file);
+    }
+    Ok(())
+}
+
+fn build(set_list_file: String, output: Option<String>) -> Result<(), Box<dyn Error>> {
+    let path = PathBuf::from(&set_list_file);
+    let set_list = SetList::load(&path)?;
+    let folder = path.parent().map(|f| f.to_path_buf()).unwrap_or_default();
+    let base = match &set_list.base {
+        Some(base) => read_rds_or_json(&Some(folder.join(base).to_string_lossy().to_string()))?.0,
+        None => Box::new(RD300NX::blank())
+    };
+    let rds = set_list.build(*base, &folder)?;
+    if let Err(errors) = rds.validate() {
+        for (p, e) in errors.into_errors() {
+            eprintln!("Error with {}: {:?}", p, e);
+        }
+        return Err("Data validation failed.".into());
+    }
+    let warnings = match LintConfig::find(&Some(set_list_file.clone()))?.apply(rds.warnings()).check() {
+        Ok(warnings) => warnings,
+        Err(failed) => {
+            for error in &failed.errors {
+                eprintln!("- {}", error);
+            }
+            return Err(failed.to_string().into());
+        }
+    };
+    if is_rds_output(&output, false) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_json(&output, rds.to_json())?;
+    }
+    if let Some(file) = &output {
+        println!("Built {} live sets from set list '{}' into '{}' ({} warnings)", set_list.sets.len(), set_list_file, file, warnings.len());
+    }
+    Ok(())
+}
+
+fn show(input: Option<String>, sets: SetSelection) -> Result<(), Box<dyn Error>> {
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set(bank, *index).ok_or_else(|| format!("Live set {} does not exist", set_label(bank, *index)))?;
+        println!("{}: {}", set_label(bank, *index), keyboard_map(live_set));
+    }
+    Ok(())
+}
+
+fn show_effects(input: Option<String>, sets: SetSelection, ranges: bool) -> Result<(), Box<dyn Error>> {
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set(bank, *index).unwrap(); // safe because selections only contain valid sets
+        println!("{}: {}", set_label(bank, *index), effects_summary(live_set, ranges));
+    }
+    Ok(())
+}
+
+fn reset_effect(input: Option<String>, output: Option<String>, sets: SetSelection, effect: Effect, type_name: Option<String>) -> Result<(), Box<dyn Error>> {
+    let (mut rds, mut templated, input_is_rds) = read_rds_or_json(&input)?;
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set_mut(bank, *index).unwrap(); // safe because selections only contain valid sets
+        let name = live_set.reset_effect(effect, type_name.as_deref())?;
+        if output.is_some() {
+            println!("- {}: {} reset to {} defaults", set_label(bank, *index), effect, name);
+        }
+    }
+    templated.sets.retain(|s| !sets.contains(s.bank, s.index)); // reset live sets are written in full
+    if is_rds_output(&output, input_is_rds) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_templated_json(&output, &rds, &templated)?;
+    }
+    if let Some(file) = &output {
+        println!("Reset {} of {} live sets into '{}'", effect, sets.sets().len(), file);
+    }
+    Ok(())
+}
+
+fn save_preset(input: Option<String>, set: SetSelection, effect: Effect, folder: String, name: String) -> Result<(), Box<dyn Error>> {
+    let [(bank, index)] = set.sets() else {
+        return Err("An effect preset can only be saved from a single live set".into());
+    };
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    let live_set = rds.live_set(bank, *index).unwrap(); // safe because selections only contain valid sets
+    let preset = EffectPreset::from_live_set(name, live_set, effect);
+    let replaced = PresetLibrary::new(&PathBuf::from(&folder)).save(&preset)?;
+    println!("{} preset '{}' ({} {}) from {} into '{}'", if replaced { "Replaced" } else { "Saved" }, preset.name, effect, preset.type_name(), set_label(bank, *index), folder);
+    Ok(())
+}
+
+fn list_presets(folder: String) -> Result<(), Box<dyn Error>> {
+    let presets = PresetLibrary::new(&PathBuf::from(&folder)).list()?;
+    for preset in &presets {
+        println!("{:<24}  {:<6}  {}", preset.name, preset.effect().to_string(), preset.type_name());
+    }
+    println!("{} presets found.", presets.len());
+    Ok(())
+}
+
+fn apply_preset(input: Option<String>, output: Option<String>, sets: SetSelection, folder: String, name: String) -> Result<(), Box<dyn Error>> {
+    let preset = PresetLibrary::new(&PathBuf::from(&folder)).get(&name)?;
+    preset.validate()?;
+    let (mut rds, mut templated, input_is_rds) = read_rds_or_json(&input)?;
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set_mut(bank, *index).unwrap(); // safe because selections only contain valid sets
+        preset.apply(live_set);
+    }
+    templated.sets.retain(|s| !sets.contains(s.bank, s.index)); // live sets with the preset applied are written in full
+    if is_rds_output(&output, input_is_rds) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_templated_json(&output, &rds, &templated)?;
+    }
+    if let Some(file) = &output {
+        println!("Applied preset '{}' ({} {}) to {} live sets into '{}'", preset.name, preset.effect(), preset.type_name(), sets.sets().len(), file);
+    }
+    Ok(())
+}
+
+fn map(input: Option<String>, output: Option<String>, filter: LiveSetFilter, edits: Vec<Edit>) -> Result<(), Box<dyn Error>> {
+    let (mut rds, mut templated, input_is_rds) = read_rds_or_json(&input)?;
+    let changed = match transform(&mut rds, &filter, &edits) {
+        Ok(changed) => changed,
+        Err(TransformError::FailedValidation(sets)) => {
+            for (label, errors) in &sets {
+                for error in errors {
+                    eprintln!("- {}: {}", label, error);
+                }
+            }
+            return Err(format!("Edits would make {} live sets invalid.", sets.len()).into());
+        }
+    };
+    templated.sets.retain(|s| !changed.iter().any(|c| c.bank == s.bank && c.index == s.index)); // edited live sets are written in full
+    if is_rds_output(&output, input_is_rds) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_templated_json(&output, &rds, &templated)?;
+    }
+    // the changes go to STDERR, so they are still reported when the output is written to STDOUT
+    for set in &changed {
+        eprintln!("{} ({})", set_label(set.bank, set.index), set.name);
+        for change in &set.changes {
+            eprintln!("  {}", change);
+        }
+    }
+    if let Some(file) = &output {
+        println!("Edited {} live sets into '{}'", changed.len(), file);
+    }
+    Ok(())
+}
+
+fn import_tuning(input: Option<String>, output: Option<String>, sets: SetSelection, layer: Layer, source: TuningSource) -> Result<(), Box<dyn Error>> {
+    let (mut rds, mut templated, input_is_rds) = read_rds_or_json(&input)?;
+    let layer_index: u8 = layer.into();
+    for (bank, index) in sets.sets() {
+        let live_set = rds.live_set_mut(bank, *index).unwrap(); // safe because selections only contain valid sets
+        let logical = &mut live_set.layers[layer_index as usize];
+        // warnings go to STDERR, so they are still shown (without corrupting the output) when writing to STDOUT
+        for warning in json::tuning::import_tuning(&mut logical.piano, &source)? {
+            eprintln!("- {} {}: {}", set_label(bank, *index), layer, warning);
+        }
+        if logical.tone.tone_number.as_piano_tone().is_none() {
+            eprintln!("- {} {}: micro tuning only applies to the SuperNATURAL piano tones, but the tone is {}", set_label(bank, *index), layer, logical.tone.tone_name());
+        }
+    }
+    templated.sets.retain(|s| !sets.contains(s.bank, s.index)); // tuned live sets are written in full
+    if is_rds_output(&output, input_is_rds) {
+        write_data(&output, &*rds.to_bytes()?)?;
+    } else {
+        write_templated_json(&output, &rds, &templated)?;
+    }
+    if let Some(file) = &output {
+        println!("Imported tuning '{}' into {} of {} live sets into '{}'", source.description(), layer, sets.sets().len(), file);
+    }
+    Ok(())
+}
+
+fn export_tuning(input: Option<String>, set: SetSelection, layer: Layer, scale_file: Option<String>, mapping_file: Option<String>) -> Result<(), Box<dyn Error>> {
+    let [(bank, index)] = set.sets() else {
+        return Err("Tuning can only be exported from a single live set".into());
+    };
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    let live_set = rds.live_set(bank, *index).unwrap(); // safe because selections only contain valid sets
+    let layer_index: u8 = layer.into();
+    let description = format!("{} {}", live_set.name_string().trim(), layer);
+    let (scale, mapping) = json::tuning::export_tuning(&live_set.layers[layer_index as usize].piano, description);
+    write_data(&scale_file, scale.to_scl().as_bytes())?;
+    if mapping_file.is_some() {
+        write_data(&mapping_file, mapping.to_kbm().as_bytes())?;
+    }
+    if let Some(file) = &scale_file {
+        println!("Exported tuning of {} {} into '{}' ({} notes)", set_label(bank, *index), layer, file, scale.pitches.len());
+    }
+    Ok(())
+}
+
+fn tones(query: ToneQuery, input: Option<Option<String>>) -> Result<(), Box<dyn Error>> {
+    let tones = query.search();
+    let Some(input) = input else {
+        for (_, tone) in &tones {
+            println!("{}", tone_line(tone));
+        }
+        println!("{} tones found.", tones.len());
+        return Ok(());
+    };
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    let mut used = 0;
+    for (number, tone) in &tones {
+        let usage = rds.tone_usage(*number);
+        if usage.is_empty() {
+            continue;
+        }
+        used += 1;
+        println!("{}", tone_line(tone));
+        for u in usage {
+            println!("  {} ({}) {}{}", set_label(u.bank, u.index), u.name, u.layer, if u.enabled { "" } else { " (layer off)" });
+        }
+    }
+    println!("{} of {} tones found are used.", used, tones.len());
+    Ok(())
+}
+
+// program change is shown 1-128, as per the Roland tone list
+fn tone_line(tone: &Tone) -> String {
+    let category = tone.category().map(|c| c.to_string()).unwrap_or_default();
+    let gm2 = if tone.is_gm2() { " (GM2)" } else { "" };
+    format!("{:>3} {:<12}  MSB {:>3}  LSB {:>3}  PC {:>3}  {}{}", tone.number(), tone.name, tone.msb, tone.lsb, tone.pc + 1, category, gm2)
+}
+
+fn export_sheet(input: Option<String>, output: Option<String>, sets: Option<SetSelection>, notes_file: Option<String>) -> Result<(), Box<dyn Error>> {
+    let (rds, _, _) = read_rds_or_json(&input)?;
+    let notes = notes_file.map(|f| SheetNotes::load(&PathBuf::from(f))).transpose()?;
+    let sets = match &sets {
+        Some(selection) => selection.sets().to_vec(),
+        None => CueSheet::named_user_sets(&rds)
+    };
+    let sheet = CueSheet::new(&rds, &sets, notes.as_ref());
+    let format = SheetFormat::for_path(&output);
+    write_data(&output, sheet.render(format).as_bytes())?;
+    if let Some(file) = &output {
+        println!("Exported cue sheet of {} live sets into '{}' ({:?})", sheet.sets.len(), file, format);
+    }
+    Ok(())
+}
+
+fn migrate(input: String) -> Result<(), Box<dyn Error>> {
+    let path = Some(input.clone());
+    let version = if PathBuf::from(&input).is_dir() {
+        let (_, version) = load_structure(&input)?;
+        if version != FORMAT_VERSION {
+            split(path.clone(), input.clone(), None, true)?;
+        }
+        version
+    } else {
+        let (_, bytes) = read_data(&path)?;
+        let text = utf8_text(bytes.clone())?;
+        let version = json_version(&FileFormat::for_file(&path).to_value(&text)?)?;
+        if version != FORMAT_VERSION {
+            let (rds, templated) = parse_templated_json(&path, bytes)?;
+            write_templated_json(&path, &rds, &templated)?;
+        }
+        version
+    };
+    if version == FORMAT_VERSION {
+        println!("'{}' is already at format version {}", input, FORMAT_VERSION);
+    } else {
+        println!("Upgraded '{}' from format version {} to {}", input, version, FORMAT_VERSION);
+    }
+    Ok(())
+}
+
+fn renumber(input_folder: String) -> Result<(), Box<dyn Error>> {
+    let format = SplitManifest::load(&PathBuf::from(&input_folder))?.map(|m| m.format).unwrap_or_default();
+    let mut unnumbered = Vec::new();
+    let count = StructuredJson::renumber(PathBuf::from(&input_folder), format, &mut unnumbered)?;
+    println!("Renumbered {} files/folders in '{}'", count, input_folder);
+    if !unnumbered.is_empty() {
+        println!("Skipped {} files/folders which have no number prefix:", unnumbered.len());
+        for path in &unnumbered {
+            println!("- {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn schema(output_json: Option<String>) -> Result<(), Box<dyn Error>> {
+    let schema = schema_for!(RD300NX);
+    write_json(&output_json, serde_json::to_string_pretty(&schema).unwrap())?;
+    if let Some(file) = &output_json {
+        println!("Generated JSON schema into '{}'", file);
+    }
+    Ok(())
+}
+
+fn read_json(path: &Option<String>) -> Result<Box<RD300NX>, Box<dyn Error>> {
+    Ok(read_templated_json(path)?.0)
+}
+
+fn read_templated_json(path: &Option<String>) -> Result<(Box<RD300NX>, Templated), Box<dyn Error>> {
+    if let Some(folder) = path.as_ref().filter(|p| PathBuf::from(p).is_dir()) {
+        let (mut structure, _) = load_structure(folder)?;
+        let templated = Templated::resolve_structure(&mut structure)?;
+        return Ok((Box::new(RD300NX::from_structured_json(structure)?), templated));
+    }
+    let (_, bytes) = read_data(path)?;
+    parse_templated_json(path, bytes)
+}
+
+// loads a split folder, upgrading it to the current format version, and returns the version it was upgraded from
+fn load_structure(folder: &str) -> Result<(StructuredJson, u16), Box<dyn Error>> {
+    let manifest = SplitManifest::load(&PathBuf::from(folder))?;
+    let version = manifest.as_ref().map(|m| m.format_version).unwrap_or_default();
+    let format = manifest.map(|m| m.format).unwrap_or_default();
+    let mut structure = StructuredJson::load(PathBuf::from(folder), format)?;
+    migrate_structure(&mut structure, version)?;
+    Ok((structure, version))
+}
+
+fn parse_templated_json(path: &Option<String>, bytes: Vec<u8>) -> Result<(Box<RD300NX>, Templated), Box<dyn Error>> {
+    let text = utf8_text(bytes)?;
+    let text = FileFormat::for_file(path).to_json(&text)?;
+    let mut value = serde_json::from_str(&text)?;
+    let version = migrate_json(&mut value)?;
+    let templated = Templated::resolve(&mut value, Templates::find(path)?)?;
+    let rds = if templated.sets.is_empty() && version == FORMAT_VERSION {
+        RD300NX::from_json(text)?
+    } else {
+        RD300NX::from_json(value.to_string())?
+    };
+    Ok((Box::new(rds), templated))
+}
+
+// an RDS file is detected by its size and that it does not start with a JSON object (or have a YAML/TOML extension)
+fn read_rds_or_json(path: &Option<String>) -> Result<(Box<RD300NX>, Templated, bool), Box<dyn Error>> {
+    if path.as_ref().is_some_and(|p| PathBuf::from(p).is_dir()) {
+        let (rds, templated) = read_templated_json(path)?;
+        return Ok((rds, templated, false));
+    }
+    let (size, bytes) = read_data(path)?;
+    if size == RD300NX::BYTE_SIZE && bytes.iter().find(|b| !b.is_ascii_whitespace()) != Some(&b'{') && FileFormat::for_file(path) == FileFormat::Json {
+        let rds = RD300NX::from_bytes(bytes.try_into().unwrap())?;
+        Ok((Box::new(rds), Templated::default(), true))
+    } else {
+        let (rds, templated) = parse_templated_json(path, bytes)?;
+        Ok((rds, templated, false))
+    }
+}
+
+// an output file ending in .RDS is written as RDS, otherwise JSON/YAML/TOML (or the default for STDOUT)
+fn is_rds_output(path: &Option<String>, default: bool) -> bool {
+    match path {
+        Some(file) => PathBuf::from(file).extension().map(|e| e.eq_ignore_ascii_case("rds")).unwrap_or(false),
+        None => default
+    }
+}
+
+fn write_templated_json(path: &Option<String>, rds: &RD300NX, templated: &Templated) -> Result<(), Box<dyn Error>> {
+    if templated.sets.is_empty() {
+        write_json(path, rds.to_json())?;
+    } else {
+        let mut value = serde_json::to_value(rds)?;
+        templated.unresolve(&mut value);
+        write_json(path, json::canonical::to_canonical_json(&value)?)?;
+    }
+    let count = templated.templates.save(path)?;
+    if count > 0 && path.is_some() {
+        println!("Saved {} templates into '{}'", count, Templates::path_for(path).display());
+    }
+    Ok(())
+}
+
+// written as YAML or TOML if the file has that extension
+fn write_json(path: &Option<String>, json: String) -> Result<(), io::Error> {
+    let text = FileFormat::for_file(path).from_json(&json)?;
+    write_data(path, text.as_bytes())
+}
+
+fn read_data(path: &Option<String>) -> Result<(usize, Vec<u8>), io::Error> {
+    let mut bytes = Vec::new();
+    let size = if let Some(filename) = path {
+        let mut f = fs::File::options().read(true).open(&filename)?;
+        f.read_to_end(&mut bytes)?
+    } else {
+        let stdin = io::stdin();
+        let mut lock = stdin.lock();
+        lock.read_to_end(&mut bytes)?
+    };
+    Ok((size, bytes))
+}
+
+// JSON, YAML and TOML files are read as UTF-8, without listing every byte of the input when it is not (eg. an RDS file)
+fn utf8_text(bytes: Vec<u8>) -> Result<String, io::Error> {
+    String::from_utf8(bytes).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Text should be UTF-8, but {}", e.utf8_error())))
+}
+
+fn write_data(path: &Option<String>, bytes: &[u8]) -> Result<(), io::Error> {
+    if let Some(filename) = path {
+        let mut f = fs::File::options().create(true).write(true).truncate(true).open(&filename)?;
+        f.write_all(&bytes)?;
+        f.flush()?;
+    } else {
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(&bytes)?;
+        stdout.flush()?;
+    }
+    Ok(())
+}
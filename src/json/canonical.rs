@@ -0,0 +1,130 @@
+// Canonical JSON is indented like pretty JSON, but keeps short arrays and objects on one line and packs long lists of
+// numbers into lines of up to MAX_WIDTH characters, so that editing one setting changes one line in a diff, eg:
+//   "unknown": [
+//     64, 97, 90, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0
+//   ],
+//   "key_touch_velocity": { "Fixed": 64 },
+// Object keys are written in the order they are serialized (struct field order, and maps which are sorted by key), and
+// numbers are written in their shortest form which round trips, with negative zero written as zero.
+
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+const MAX_WIDTH: usize = 120;
+const INDENT: &str = "  ";
+
+pub fn to_canonical_json<T: Serialize>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut output = String::new();
+    write_value(&mut output, &value, 0, 0);
+    Ok(output)
+}
+
+// `prefix` is the number of characters already written on the current line (after the indent)
+fn write_value(output: &mut String, value: &Value, depth: usize, prefix: usize) {
+    if let Some(inline) = inline(value) {
+        if depth * INDENT.len() + prefix + inline.len() <= MAX_WIDTH {
+            output.push_str(&inline);
+            return;
+        }
+    }
+    match value {
+        Value::Array(items) if items.iter().all(is_number) => {
+            output.push('[');
+            let mut line = String::new();
+            for (i, item) in items.iter().enumerate() {
+                let mut text = scalar(item);
+                if i + 1 < items.len() {
+                    text.push(',');
+                }
+                if !line.is_empty() && (depth + 1) * INDENT.len() + line.len() + 1 + text.len() > MAX_WIDTH {
+                    new_line(output, depth + 1);
+                    output.push_str(&line);
+                    line.clear();
+                }
+                if !line.is_empty() {
+                    line.push(' ');
+                }
+                line.push_str(&text);
+            }
+            new_line(output, depth + 1);
+            output.push_str(&line);
+            new_line(output, depth);
+            output.push(']');
+        },
+        Value::Array(items) => {
+            output.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                new_line(output, depth + 1);
+                write_value(output, item, depth + 1, 0);
+            }
+            new_line(output, depth);
+            output.push(']');
+        },
+        Value::Object(map) => {
+            output.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+                new_line(output, depth + 1);
+                let key = format!("{}: ", Value::String(key.clone()));
+                output.push_str(&key);
+                write_value(output, item, depth + 1, key.len());
+            }
+            new_line(output, depth);
+            output.push('}');
+        },
+        _ => output.push_str(&scalar(value))
+    }
+}
+
+// the single line form of a value, if it is a scalar or only contains scalars
+fn inline(value: &Value) -> Option<String> {
+    match value {
+        Value::Array(items) if items.is_empty() => Some("[]".into()),
+        Value::Object(map) if map.is_empty() => Some("{}".into()),
+        Value::Array(items) if items.iter().all(is_scalar) => {
+            let items: Vec<String> = items.iter().map(scalar).collect();
+            Some(format!("[{}]", items.join(", ")))
+        },
+        Value::Object(map) if map.values().all(is_scalar) => {
+            let items: Vec<String> = map.iter().map(|(k, v)| format!("{}: {}", Value::String(k.clone()), scalar(v))).collect();
+            Some(format!("{{ {} }}", items.join(", ")))
+        },
+        Value::Array(_) | Value::Object(_) => None,
+        _ => Some(scalar(value))
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::Number(n) => number(n),
+        _ => value.to_string()
+    }
+}
+
+fn number(n: &Number) -> String {
+    match n.as_f64() {
+        Some(f) if n.is_f64() && f == 0.0 => "0.0".into(), // including -0.0
+        _ => n.to_string()
+    }
+}
+
+fn is_scalar(value: &Value) -> bool {
+    !matches!(value, Value::Array(_) | Value::Object(_))
+}
+
+fn is_number(value: &Value) -> bool {
+    matches!(value, Value::Number(_))
+}
+
+fn new_line(output: &mut String, depth: usize) {
+    output.push('\n');
+    for _ in 0..depth {
+        output.push_str(INDENT);
+    }
+}
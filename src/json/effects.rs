@@ -0,0 +1,38 @@
+// An effects summary lists the MFX, reverb and chorus of a live set, with each parameter of the current type shown in
+// its units, eg:
+//   MFX: Equalizer
+//     Low Freq    200 Hz
+//     Low Gain    0
+//   Reverb: Hall
+//     Pre Delay   12.5 ms
+//   Chorus: Off
+// Where ranges are included, each parameter is followed by the values it can take, eg. "(0.0 ms to 100.0 ms)".
+
+use crate::roland::live_set::LiveSet;
+use crate::roland::types::effects::ParameterInfo;
+
+pub fn effects_summary(live_set: &LiveSet, ranges: bool) -> String {
+    let mut lines = vec![live_set.name_string().trim().to_string()];
+    let mfx = &live_set.mfx;
+    let switch = if mfx.enable || mfx.mfx_type.is_off() { "" } else { " (switched off)" };
+    lines.append(&mut effect_lines("MFX", &mfx.mfx_type.name(), switch, mfx.mfx_type.describe(), ranges));
+    let reverb = &live_set.reverb.reverb_type;
+    lines.append(&mut effect_lines("Reverb", reverb.name(), "", reverb.describe(), ranges));
+    let chorus = &live_set.chorus.chorus_type;
+    lines.append(&mut effect_lines("Chorus", chorus.name(), "", chorus.describe(), ranges));
+    lines.join("\n") + "\n"
+}
+
+fn effect_lines(effect: &str, type_name: &str, note: &str, parameters: Vec<ParameterInfo>, ranges: bool) -> Vec<String> {
+    let mut lines = vec![format!("  {}: {}{}", effect, type_name, note)];
+    let width = parameters.iter().map(|p| p.name.len()).max().unwrap_or(0);
+    for p in parameters {
+        let line = if ranges {
+            format!("    {:<width$}  {}  ({})", p.name, p.value, p.range, width = width)
+        } else {
+            format!("    {:<width$}  {}", p.name, p.value, width = width)
+        };
+        lines.push(line);
+    }
+    lines
+}
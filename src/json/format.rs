@@ -0,0 +1,98 @@
+// JSON files (and the files of a split folder) can also be written as YAML or TOML, which are easier to edit by hand and
+// allow comments, eg. `decode INPUT.RDS OUTPUT.yaml`. The format is chosen by the file extension, and each format is
+// converted to and from JSON, so that the same serde model (including the serialize_* modules) reads and writes them all.
+// A TOML file must hold a table, so other values (eg. a split file of unused bits) are written as `"$value" = ...` instead,
+// which is a key that no setting has, so that a table which happens to have a single `value` key is still read as a table.
+
+use std::io;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FileFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml
+}
+
+impl FileFormat {
+    const TOML_VALUE_KEY: &'static str = "$value";
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Yaml => "yaml",
+            Self::Toml => "toml"
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "yaml" | "yml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            _ => None
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|e| e.to_str()).and_then(Self::from_extension)
+    }
+
+    // files without a known extension (and STDIN/STDOUT) are JSON
+    pub fn for_file(file: &Option<String>) -> Self {
+        file.as_ref().and_then(|f| Self::from_path(Path::new(f))).unwrap_or_default()
+    }
+
+    // the file name without its extension, if it has an extension of this format
+    pub fn strip_extension<'a>(&self, file_name: &'a str) -> Option<&'a str> {
+        let (name, extension) = file_name.rsplit_once('.')?;
+        if Self::from_extension(extension) == Some(*self) {
+            Some(name)
+        } else {
+            None
+        }
+    }
+
+    // converts JSON text into text of this format
+    pub fn from_json(&self, json: &str) -> Result<String, io::Error> {
+        if *self == Self::Json {
+            return Ok(json.to_string());
+        }
+        let value: Value = serde_json::from_str(json)?;
+        let text = match self {
+            Self::Json => unreachable!(),
+            Self::Yaml => serde_yaml::to_string(&value).map_err(|e| e.to_string()),
+            Self::Toml if value.is_object() => toml::to_string(&value).map_err(|e| e.to_string()),
+            Self::Toml => {
+                let mut table = Map::new();
+                table.insert(Self::TOML_VALUE_KEY.to_string(), value);
+                toml::to_string(&table).map_err(|e| e.to_string())
+            }
+        };
+        text.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Cannot write {:?}: {}", self, e)))
+    }
+
+    // converts text of this format into JSON text
+    pub fn to_json(&self, text: &str) -> Result<String, io::Error> {
+        if *self == Self::Json {
+            return Ok(text.to_string());
+        }
+        Ok(self.to_value(text)?.to_string())
+    }
+
+    pub fn to_value(&self, text: &str) -> Result<Value, io::Error> {
+        let value = match self {
+            Self::Json => serde_json::from_str(text).map_err(|e| e.to_string()),
+            Self::Yaml => serde_yaml::from_str(text).map_err(|e| e.to_string()),
+            Self::Toml => toml::from_str(text).map(|value| match value {
+                Value::Object(mut table) if table.len() == 1 && table.contains_key(Self::TOML_VALUE_KEY) => table.remove(Self::TOML_VALUE_KEY).unwrap(),
+                value => value
+            }).map_err(|e| e.to_string())
+        };
+        value.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid {:?}: {}", self, e)))
+    }
+}
@@ -0,0 +1,161 @@
+// A keyboard map draws the 88 keys of the keyboard with a bar beneath for each enabled layer, showing which keys it
+// plays, along with its transpose and velocity range, eg:
+//             1           2           3           4           5           6           7           8
+//            -#--#-#--#-#-#--#-#--#-#-#--#-#--#-#-#--#-#--#-#-#--#-#--#-#-#--#-#--#-#-#--#-#--#-#-#--
+//   Upper1                                       ==========================================   C4-C8 ...
+//   Lower1   =======================================                                          A0-B3 ...
+// Where the split switch is OFF, the range of each layer is ignored and the keys outside it are drawn with '~'.
+
+use strum::IntoEnumIterator;
+
+use crate::roland::live_set::LiveSet;
+use crate::roland::types::enums::Layer;
+use crate::roland::types::notes::PianoKey;
+
+use super::validation::LayerRanges;
+
+const KEYS: usize = 88;
+const INDENT: usize = 10;
+
+struct LayerBar {
+    layer: Layer,
+    configured: (usize, usize),
+    effective: Option<(usize, usize)>,
+    velocity: (u8, u8),
+    transpose: i8,
+    label: String
+}
+
+impl LayerBar {
+    fn new<L: LayerRanges>(layer: Layer, ranges: &L, effective: Option<(PianoKey, PianoKey)>, transpose: i8, label: String) -> Self {
+        Self {
+            layer,
+            configured: (key_index(ranges.get_range_lower()), key_index(ranges.get_range_upper())),
+            effective: effective.map(|(lower, upper)| (key_index(lower), key_index(upper))),
+            velocity: (ranges.get_velocity_lower(), ranges.get_velocity_upper()),
+            transpose,
+            label
+        }
+    }
+
+    fn plays(&self, key: usize) -> bool {
+        self.effective.map(|(lower, upper)| key >= lower && key <= upper).unwrap_or(false)
+    }
+
+    fn velocity_overlaps(&self, other: &Self) -> bool {
+        self.velocity.0 <= other.velocity.1 && other.velocity.0 <= self.velocity.1
+    }
+
+    fn draw(&self) -> String {
+        let mut bar = String::new();
+        for key in 0..KEYS {
+            let in_range = key >= self.configured.0 && key <= self.configured.1;
+            bar.push(match (self.plays(key), in_range) {
+                (true, true) => '=',
+                (true, false) => '~',
+                (false, _) => ' '
+            });
+        }
+        let (lower, upper) = self.effective.unwrap();
+        format!("  {:<width$}{}   {}  transpose {:+}  velocity {}-{}  {}", self.layer, bar, key_span(lower, upper), self.transpose, self.velocity.0, self.velocity.1, self.label, width = INDENT - 2).trim_end().to_string()
+    }
+}
+
+pub fn keyboard_map(live_set: &LiveSet) -> String {
+    let mut lines = vec![
+        live_set.name_string().trim().to_string(),
+        format!("{:INDENT$}{}", "", octave_row()),
+        format!("{:INDENT$}{}", "", key_row())
+    ];
+    let internal: Vec<LayerBar> = Layer::iter().zip(live_set.layers.iter())
+        .map(|(layer, l)| LayerBar::new(layer, &l.internal, l.internal.key_range(live_set.common.split_switch_internal), l.internal.transpose_semitones(), l.tone.tone_name()))
+        .collect();
+    let external: Vec<LayerBar> = Layer::iter().zip(live_set.layers.iter())
+        .map(|(layer, l)| LayerBar::new(layer, &l.external, l.external.key_range(live_set.common.split_switch_external), l.external.transpose_semitones(), String::new()))
+        .collect();
+    lines.append(&mut layer_group("Internal", live_set.common.split_switch_internal, internal));
+    lines.append(&mut layer_group("External", live_set.common.split_switch_external, external));
+    lines.join("\n") + "\n"
+}
+
+fn layer_group(name: &str, split_switch: bool, layers: Vec<LayerBar>) -> Vec<String> {
+    let enabled: Vec<LayerBar> = layers.into_iter().filter(|l| l.effective.is_some()).collect();
+    let split = if split_switch { "ON" } else { "OFF" };
+    if enabled.is_empty() {
+        return vec![format!("{} (split switch {}): no layers enabled", name, split)];
+    }
+    let mut lines = vec![format!("{} (split switch {})", name, split)];
+    for layer in &enabled {
+        lines.push(layer.draw());
+    }
+    for (i, a) in enabled.iter().enumerate() {
+        for b in enabled.iter().skip(i + 1) {
+            let shared: Vec<usize> = (0..KEYS).filter(|k| a.plays(*k) && b.plays(*k)).collect();
+            if shared.is_empty() {
+                continue;
+            }
+            if a.velocity_overlaps(b) {
+                lines.push(format!("  - {} and {} overlap at {}", a.layer, b.layer, key_spans(&shared)));
+            } else {
+                lines.push(format!("  - {} and {} share {}, but are split by velocity", a.layer, b.layer, key_spans(&shared)));
+            }
+        }
+    }
+    let gaps: Vec<usize> = (0..KEYS).filter(|k| !enabled.iter().any(|l| l.plays(*k))).collect();
+    if !gaps.is_empty() {
+        lines.push(format!("  - no layer plays {}", key_spans(&gaps)));
+    }
+    let ranged: Vec<String> = enabled.iter().filter(|l| l.configured != (0, KEYS - 1)).map(|l| l.layer.to_string()).collect();
+    if !ranged.is_empty() {
+        if split_switch {
+            lines.push(format!("  - turning the split switch OFF would make {} play every key", ranged.join(", ")));
+        } else {
+            lines.push(format!("  - the ranges of {} are ignored ('~') until the split switch is turned ON", ranged.join(", ")));
+        }
+    }
+    lines
+}
+
+fn key_index(key: PianoKey) -> usize {
+    let value: u8 = key.into();
+    value as usize
+}
+
+fn key_name(index: usize) -> String {
+    format!("{:?}", PianoKey::from(index as u8))
+}
+
+fn is_black(index: usize) -> bool {
+    key_name(index).chars().nth(1) == Some('b')
+}
+
+fn octave_row() -> String {
+    (0..KEYS).map(|k| {
+        let name = key_name(k);
+        if name.starts_with('C') { name.chars().nth(1).unwrap() } else { ' ' }
+    }).collect::<String>().trim_end().to_string()
+}
+
+fn key_row() -> String {
+    (0..KEYS).map(|k| if is_black(k) { '#' } else { '-' }).collect()
+}
+
+fn key_span(lower: usize, upper: usize) -> String {
+    if lower == upper {
+        key_name(lower)
+    } else {
+        format!("{}-{}", key_name(lower), key_name(upper))
+    }
+}
+
+// consecutive keys are combined into spans, eg. "A0-B0, C4"
+fn key_spans(keys: &[usize]) -> String {
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for key in keys {
+        match spans.last_mut() {
+            Some((_, upper)) if *upper + 1 == *key => *upper = *key,
+            _ => spans.push((*key, *key))
+        }
+    }
+    spans.into_iter().map(|(lower, upper)| key_span(lower, upper)).collect::<Vec<String>>().join(", ")
+}
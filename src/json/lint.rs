@@ -0,0 +1,109 @@
+// A lint config allows each warning rule to be turned off, or promoted to an error which prevents encoding.
+
+// The config is read from a `roland-rds.toml` file placed next to the JSON file, or inside the split folder, eg:
+//   [rules]
+//   split_switch = "off"
+//   tone_remain = "error"
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::warnings::{Warning, WarningRule};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Off,
+    #[default]
+    Warning,
+    Error
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+    #[serde(default)]
+    rules: HashMap<WarningRule, Severity>
+}
+
+pub struct LintResult {
+    pub errors: Vec<Warning>,
+    pub warnings: Vec<Warning>
+}
+
+impl LintResult {
+    // RDS data is only written when there are no lint errors, in which case the remaining warnings are returned
+    pub fn check(self) -> Result<Vec<Warning>, LintFailed> {
+        if self.errors.is_empty() {
+            Ok(self.warnings)
+        } else {
+            Err(LintFailed {
+                errors: self.errors
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LintFailed {
+    pub errors: Vec<Warning>
+}
+
+impl Error for LintFailed {}
+
+impl Display for LintFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Lint failed with {} errors.", self.errors.len())
+    }
+}
+
+impl LintConfig {
+    pub const FILE_NAME: &'static str = "roland-rds.toml";
+
+    pub fn severity(&self, rule: WarningRule) -> Severity {
+        self.rules.get(&rule).copied().unwrap_or_default()
+    }
+
+    // the config applies to the JSON file or split folder it is found next to (or the current directory for STDIN)
+    pub fn path_for(input: &Option<String>) -> PathBuf {
+        let folder = match input {
+            Some(p) if Path::new(p).is_dir() => PathBuf::from(p),
+            Some(p) => Path::new(p).parent().map(|f| f.to_path_buf()).unwrap_or_default(),
+            None => PathBuf::new()
+        };
+        folder.join(Self::FILE_NAME)
+    }
+
+    pub fn find(input: &Option<String>) -> Result<Self, io::Error> {
+        let path = Self::path_for(input);
+        if path.is_file() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        let text = fs::read_to_string(path)?;
+        toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid lint config '{}': {}", path.display(), e)))
+    }
+
+    pub fn apply(&self, warnings: Vec<Warning>) -> LintResult {
+        let mut result = LintResult {
+            errors: Vec::new(),
+            warnings: Vec::new()
+        };
+        for warning in warnings {
+            match self.severity(warning.rule) {
+                Severity::Off => {},
+                Severity::Warning => result.warnings.push(warning),
+                Severity::Error => result.errors.push(warning)
+            }
+        }
+        result
+    }
+}
@@ -0,0 +1,287 @@
+// JSON files (and split folders) record the format version they were written with, so that when the shape of the JSON
+// changes (eg. when unknown bits are decoded into typed fields), older files can still be read by upgrading them one
+// version at a time. Files written before the format version existed are version 0.
+
+// To change the shape of the JSON, add a Migration to the end of MIGRATIONS which turns the previous shape into the new
+// one, at each path where it occurs. For example, when the compressor is decoded:
+//   Migration {
+//       description: "decode the system compressor",
+//       paths: &["system/compressor"],
+//       migrate: |compressor| bits_to_fields(compressor, &[("switch", 1), ("threshold", 7), ...])
+//   }
+// Paths use the JSON keys (and '*' or an index for arrays), and are mapped onto the node names of split folders (eg.
+// 'common' is the 'ls_common' file, and 'layers/0' is the '1-TONE' folder). Where a path ends at a folder, the folder is
+// merged into one value to be migrated, then split again the same way.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::{Map, Value};
+
+use super::{slot_number, StructuredJson};
+use super::canonical::to_canonical_json;
+
+pub struct Migration {
+    pub description: &'static str,
+    pub paths: &'static [&'static str], // '/' separated, where '*' matches every item of an array (or folder)
+    pub migrate: fn(&mut Value) -> Result<(), String>
+}
+
+// MIGRATIONS[n] upgrades version n to version n+1
+pub const MIGRATIONS: [Migration; 1] = [
+    Migration {
+        description: "record the format version",
+        paths: &[],
+        migrate: |_| Ok(())
+    }
+];
+
+pub const FORMAT_VERSION: u16 = MIGRATIONS.len() as u16;
+pub const VERSION_FIELD: &str = "format_version";
+
+#[derive(Debug)]
+pub enum MigrationError {
+    InvalidVersion(Value),
+    NewerVersion(u16),
+    FailedStep {
+        version: u16,
+        description: &'static str,
+        path: String,
+        message: String
+    },
+    InvalidJson(String, serde_json::Error),
+    UnexpectedFolder(String)
+}
+
+impl Error for MigrationError {}
+
+impl Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidVersion(v) => write!(f, "Invalid {}: {}", VERSION_FIELD, v),
+            Self::NewerVersion(v) => write!(f, "JSON has {} {}, but this version of roland-rds only supports up to {}", VERSION_FIELD, v, FORMAT_VERSION),
+            Self::FailedStep { version, description, path, message } => write!(f, "Failed to upgrade '{}' to {} {} ({}): {}", path, VERSION_FIELD, version, description, message),
+            Self::InvalidJson(path, e) => write!(f, "Failed to upgrade '{}': {}", path, e),
+            Self::UnexpectedFolder(path) => write!(f, "Failed to upgrade '{}': expected a file but found a folder", path)
+        }
+    }
+}
+
+pub fn json_version(value: &Value) -> Result<u16, MigrationError> {
+    match value.get(VERSION_FIELD) {
+        None => Ok(0),
+        Some(v) => v.as_u64().and_then(|n| u16::try_from(n).ok()).ok_or_else(|| MigrationError::InvalidVersion(v.clone()))
+    }
+}
+
+// (JSON key, node name) where a split folder names a node differently to the JSON
+const NODE_NAMES: [(&str, &str); 1] = [
+    ("common", "ls_common")
+];
+
+// upgrades a whole RD300NX JSON value to the current format version, returning the version it was upgraded from
+pub fn migrate_json(value: &mut Value) -> Result<u16, MigrationError> {
+    let version = json_version(value)?;
+    upgrade_json(value, version, &MIGRATIONS)?;
+    if let Value::Object(map) = value {
+        map.insert(VERSION_FIELD.to_string(), Value::from(FORMAT_VERSION));
+    }
+    Ok(version)
+}
+
+// upgrades a split folder (whose version is recorded in its manifest) to the current format version, at any split depth
+pub fn migrate_structure(structure: &mut StructuredJson, version: u16) -> Result<(), MigrationError> {
+    upgrade_structure(structure, version, &MIGRATIONS)
+}
+
+pub(crate) fn upgrade_json(value: &mut Value, version: u16, migrations: &[Migration]) -> Result<(), MigrationError> {
+    if version as usize > migrations.len() {
+        return Err(MigrationError::NewerVersion(version));
+    }
+    for (step, migration) in migrations.iter().enumerate().skip(version as usize) {
+        let step = Step { version: step as u16 + 1, migration };
+        for path in migration.paths {
+            let segments: Vec<&str> = path.split('/').collect();
+            apply_at(value, &segments, String::new(), &step)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn upgrade_structure(structure: &mut StructuredJson, version: u16, migrations: &[Migration]) -> Result<(), MigrationError> {
+    if version as usize > migrations.len() {
+        return Err(MigrationError::NewerVersion(version));
+    }
+    for (step, migration) in migrations.iter().enumerate().skip(version as usize) {
+        let step = Step { version: step as u16 + 1, migration };
+        for path in migration.paths {
+            let segments: Vec<&str> = path.split('/').collect();
+            apply_at_structure(structure, &segments, String::new(), &step)?;
+        }
+    }
+    Ok(())
+}
+
+struct Step<'a> {
+    version: u16,
+    migration: &'a Migration
+}
+
+impl Step<'_> {
+    fn failed(&self, path: String, message: String) -> MigrationError {
+        MigrationError::FailedStep { version: self.version, description: self.migration.description, path, message }
+    }
+}
+
+fn apply_at(value: &mut Value, path: &[&str], location: String, step: &Step) -> Result<(), MigrationError> {
+    let Some((first, rest)) = path.split_first() else {
+        return (step.migration.migrate)(value).map_err(|message| step.failed(location, message));
+    };
+    match value {
+        Value::Array(items) if *first == "*" => {
+            for (i, item) in items.iter_mut().enumerate() {
+                apply_at(item, rest, format!("{}/{}", location, i), step)?;
+            }
+        },
+        Value::Array(items) => {
+            if let Some(item) = first.parse().ok().and_then(|i: usize| items.get_mut(i)) {
+                apply_at(item, rest, format!("{}/{}", location, first), step)?;
+            }
+        },
+        Value::Object(map) if *first == "*" => {
+            for (key, item) in map.iter_mut() {
+                apply_at(item, rest, format!("{}/{}", location, key), step)?;
+            }
+        },
+        Value::Object(map) => {
+            if let Some(item) = map.get_mut(*first) {
+                apply_at(item, rest, format!("{}/{}", location, first), step)?;
+            }
+        },
+        _ => {} // paths which are not present (eg. in a live set with a base) are left alone
+    }
+    Ok(())
+}
+
+fn apply_at_structure(structure: &mut StructuredJson, path: &[&str], location: String, step: &Step) -> Result<(), MigrationError> {
+    match structure {
+        StructuredJson::SingleJson(json) => {
+            let mut value: Value = serde_json::from_str(json).map_err(|e| MigrationError::InvalidJson(location.clone(), e))?;
+            apply_at(&mut value, path, location, step)?;
+            *json = to_canonical_json(&value).unwrap();
+        },
+        StructuredJson::NestedCollection(nodes) => {
+            let Some((first, rest)) = path.split_first() else {
+                let mut value = structure_to_value(structure, &location)?;
+                (step.migration.migrate)(&mut value).map_err(|message| step.failed(location.clone(), message))?;
+                return split_value(structure, value, &location, step);
+            };
+            let slots = slot_order(nodes);
+            for (i, (name, node)) in nodes.iter_mut().enumerate() {
+                let matches = match &slots {
+                    Some(order) => *first == "*" || first.parse::<usize>().is_ok_and(|index| order[i] == index),
+                    None => *first == "*" || json_key(name) == *first
+                };
+                if matches {
+                    apply_at_structure(node, rest, format!("{}/{}", location, name), step)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn json_key(name: &str) -> &str {
+    NODE_NAMES.iter().find(|(_, node)| *node == name).map_or(name, |(key, _)| key)
+}
+
+fn node_name(key: &str) -> &str {
+    NODE_NAMES.iter().find(|(k, _)| *k == key).map_or(key, |(_, node)| node)
+}
+
+// the array index of each node, if the nodes are the numbered slots of an array (eg. '01-Concert Grand')
+fn slot_order(nodes: &[(String, StructuredJson)]) -> Option<Vec<usize>> {
+    let numbers: Vec<usize> = nodes.iter().map(|(name, _)| slot_number(name).map(|(n, _)| n)).collect::<Option<_>>()?;
+    if numbers.is_empty() {
+        return None;
+    }
+    let mut sorted = numbers.clone();
+    sorted.sort();
+    Some(numbers.iter().map(|n| sorted.iter().position(|s| s == n).unwrap()).collect())
+}
+
+// merges a split folder into the value it was split from
+pub(crate) fn structure_to_value(structure: &StructuredJson, location: &str) -> Result<Value, MigrationError> {
+    match structure {
+        StructuredJson::SingleJson(json) => serde_json::from_str(json).map_err(|e| MigrationError::InvalidJson(location.to_string(), e)),
+        StructuredJson::NestedCollection(nodes) => match slot_order(nodes) {
+            Some(order) => {
+                let mut items = vec![Value::Null; nodes.len()];
+                for ((name, node), index) in nodes.iter().zip(order) {
+                    items[index] = structure_to_value(node, &format!("{}/{}", location, name))?;
+                }
+                Ok(Value::Array(items))
+            },
+            None => {
+                let mut map = Map::new();
+                for (name, node) in nodes {
+                    map.insert(json_key(name).to_string(), structure_to_value(node, &format!("{}/{}", location, name))?);
+                }
+                Ok(Value::Object(map))
+            }
+        }
+    }
+}
+
+// splits a migrated value into the nodes of the folder it was merged from, adding (or removing) nodes for any keys
+// which the migration added (or removed)
+fn split_value(structure: &mut StructuredJson, value: Value, location: &str, step: &Step) -> Result<(), MigrationError> {
+    match structure {
+        StructuredJson::SingleJson(json) => *json = to_canonical_json(&value).unwrap(),
+        StructuredJson::NestedCollection(nodes) => match (slot_order(nodes), value) {
+            (Some(order), Value::Array(mut items)) if items.len() == nodes.len() => {
+                for ((name, node), index) in nodes.iter_mut().zip(order) {
+                    split_value(node, items[index].take(), &format!("{}/{}", location, name), step)?;
+                }
+            },
+            (None, Value::Object(mut map)) => {
+                nodes.retain(|(name, _)| map.contains_key(json_key(name)));
+                for (name, node) in nodes.iter_mut() {
+                    let item = map.remove(json_key(name)).unwrap(); // safe because nodes without a key were removed
+                    split_value(node, item, &format!("{}/{}", location, name), step)?;
+                }
+                for (key, item) in map {
+                    nodes.push((node_name(&key).to_string(), StructuredJson::SingleJson(to_canonical_json(&item).unwrap())));
+                }
+            },
+            _ => return Err(step.failed(location.to_string(), "the migrated value no longer matches the shape of the folder".to_string()))
+        }
+    }
+    Ok(())
+}
+
+// replaces a Bits string with an object of unsigned fields, taken in order (eg. when unknown bits are decoded)
+#[allow(dead_code)] // for future migrations, but used by tests
+pub fn bits_to_fields(value: &mut Value, fields: &[(&str, usize)]) -> Result<(), String> {
+    let total: usize = fields.iter().map(|(_, width)| width).sum();
+    let bits: Vec<bool> = match value {
+        Value::String(s) => s.chars().filter(|c| *c != ' ').map(|c| match c {
+            '0' => Ok(false),
+            '1' => Ok(true),
+            _ => Err(format!("invalid bit digit ({})", c))
+        }).collect::<Result<_, _>>()?,
+        _ => return Err(format!("expected a string of bits, but found {}", value))
+    };
+    if bits.len() != total {
+        return Err(format!("expected {} bits, but found {}", total, bits.len()));
+    }
+    let mut map = Map::new();
+    let mut offset = 0;
+    for (name, width) in fields {
+        let number = bits[offset..offset + width].iter().fold(0u64, |n, bit| (n << 1) | *bit as u64);
+        map.insert(name.to_string(), Value::from(number));
+        offset += width;
+    }
+    *value = Value::Object(map);
+    Ok(())
+}
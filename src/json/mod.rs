@@ -1,19 +1,37 @@
 use std::error::Error;
 use std::fmt::Debug;
 use std::ops::AddAssign;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::fmt::Display;
 use std::io;
 use std::fs;
 
+use format::FileFormat;
+use lint::LintConfig;
+
 pub mod serialize_fromstr_display;
 pub mod serialize_chars_as_string;
 pub mod serialize_array_as_vec;
 pub mod serialize_default_terminated_array;
 pub mod serialize_map_keys_in_order;
+pub mod canonical;
+pub mod format;
 pub mod validation;
 pub mod schema;
 pub mod warnings;
+pub mod lint;
+pub mod patch;
+pub mod selection;
+pub mod set_list;
+pub mod templates;
+pub mod sheet;
+pub mod keyboard;
+pub mod effects;
+pub mod presets;
+pub mod migration;
+pub mod tuning;
+pub mod transform;
 
 pub fn type_name_pretty<T>() -> &'static str {
     let full_name = std::any::type_name::<T>();
@@ -26,6 +44,67 @@ pub trait Json {
     fn from_json(json: String) -> Result<Self, serde_json::Error> where Self: Sized;
     fn to_structured_json(&self) -> StructuredJson;
     fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> where Self: Sized;
+
+    fn to_structured_json_at_depth(&self, _depth: SplitDepth) -> StructuredJson {
+        self.to_structured_json()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitDepth {
+    Set, // one file per live set
+    #[default]
+    Section, // one folder per live set, with a file per section (common, chorus, reverb, mfx, each layer, etc)
+    Full // as per Section, but with each layer exploded into a file per layer part (internal, external, tone, etc)
+}
+
+impl FromStr for SplitDepth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "set" => Ok(Self::Set),
+            "section" => Ok(Self::Section),
+            "full" => Ok(Self::Full),
+            _ => Err(format!("Split depth should be one of 'set', 'section' or 'full', but found '{}'", s))
+        }
+    }
+}
+
+// The manifest is saved into the split folder, recording how it was split so that it can be merged (and re-split) the same way
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SplitManifest {
+    pub depth: SplitDepth,
+    #[serde(default)]
+    pub format_version: u16, // manifests written before the format version existed are version 0
+    #[serde(default)]
+    pub format: FileFormat // manifests written before other formats existed are JSON
+}
+
+impl SplitManifest {
+    const FILE_NAME: &'static str = ".split.json";
+
+    pub fn new(depth: SplitDepth, format: FileFormat) -> Self {
+        Self {
+            depth,
+            format_version: migration::FORMAT_VERSION,
+            format
+        }
+    }
+
+    pub fn save(&self, folder: &Path) -> Result<(), io::Error> {
+        fs::write(folder.join(Self::FILE_NAME), canonical::to_canonical_json(self)?)
+    }
+
+    pub fn load(folder: &Path) -> Result<Option<Self>, io::Error> {
+        let path = folder.join(Self::FILE_NAME);
+        if path.is_file() {
+            Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+        } else {
+            Ok(None) // folders split before the manifest existed are always Section depth
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -34,7 +113,18 @@ pub enum StructuredJsonError {
     NodeNotFound(String),
     ExpectedFolderButFoundFile,
     ExpectedFileButFoundFolder,
-    UnusedNodes(Vec<String>)
+    UnusedNodes(Vec<String>),
+    InvalidSlotName(String),
+    DuplicateSlot(usize),
+    MissingSlots(Vec<usize>),
+    ExtraSlots(Vec<String>),
+    InNode(String, Box<StructuredJsonError>)
+}
+
+impl StructuredJsonError {
+    pub fn in_node(self, name: &str) -> Self {
+        Self::InNode(name.to_owned(), Box::new(self))
+    }
 }
 
 impl From<serde_json::Error> for StructuredJsonError {
@@ -47,19 +137,34 @@ impl Error for StructuredJsonError {}
 
 impl Display for StructuredJsonError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self)
+        match self {
+            Self::InNode(name, inner) => match inner.as_ref() {
+                Self::InNode(_, _) => write!(f, "{}/{}", name, inner),
+                _ => write!(f, "{}: {}", name, inner)
+            },
+            _ => write!(f, "{:?}", self)
+        }
     }
 }
 
+#[derive(Clone)]
 pub enum StructuredJson {
     SingleJson(String),
     NestedCollection(Vec<(String, StructuredJson)>)
 }
 
 impl StructuredJson {
-    const FILE_EXTENSION: &'static str = "json";
+    // the name of the node which a file represents, if it is part of the structure (hidden files, the lint config and
+    // files of other formats are not)
+    fn node_name(file_name: &str, format: FileFormat) -> Option<&str> {
+        if file_name.starts_with('.') || file_name == LintConfig::FILE_NAME {
+            None
+        } else {
+            format.strip_extension(file_name)
+        }
+    }
 
-    pub fn save(&self, path: PathBuf) -> Result<FileCount, io::Error> {
+    pub fn save(&self, path: PathBuf, format: FileFormat) -> Result<FileCount, io::Error> {
         if path.exists() {
             return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("Cannot save structured json, '{}' already exists", path.display())));
         }
@@ -67,7 +172,7 @@ impl StructuredJson {
         match self {
             Self::SingleJson(json) => {
                 count.files += 1;
-                fs::write(path.with_extension(Self::FILE_EXTENSION), json)?;
+                fs::write(path.with_extension(format.extension()), format.from_json(json)?)?;
             },
             Self::NestedCollection(vec) => {
                 fs::create_dir(&path)?;
@@ -75,7 +180,7 @@ impl StructuredJson {
                 for (name, structured_json) in vec {
                     let mut sub_path = path.clone();
                     sub_path.push(name);
-                    let sub_count = structured_json.save(sub_path)?;
+                    let sub_count = structured_json.save(sub_path, format)?;
                     count += sub_count;
                 }
             }
@@ -83,7 +188,78 @@ impl StructuredJson {
         Ok(count)
     }
 
+    // like save, but writes into an existing folder, only touching files which are added, changed or removed
+    // (YAML and TOML files which only differ by formatting or comments are not changed, so the comments are kept)
+    pub fn update(&self, path: PathBuf, format: FileFormat, summary: &mut UpdateSummary) -> Result<(), io::Error> {
+        match self {
+            Self::SingleJson(json) => {
+                let file = path.with_extension(format.extension());
+                let text = format.from_json(json)?;
+                if !file.exists() {
+                    fs::write(&file, text)?;
+                    summary.added.push(file);
+                } else {
+                    let existing = fs::read_to_string(&file)?;
+                    let unchanged = existing == text || (format != FileFormat::Json
+                        && format.to_value(&existing).ok() == Some(serde_json::from_str(json)?));
+                    if !unchanged {
+                        fs::write(&file, text)?;
+                        summary.changed.push(file);
+                    }
+                }
+            },
+            Self::NestedCollection(vec) => {
+                if !path.exists() {
+                    fs::create_dir(&path)?;
+                }
+                for entry in path.read_dir()? {
+                    let e = entry?;
+                    let name = e.file_name().to_string_lossy().to_string();
+                    if name.starts_with('.') {
+                        continue; // leave hidden files alone
+                    }
+                    if e.path().is_dir() {
+                        if !vec.iter().any(|(n, s)| *n == name && matches!(s, Self::NestedCollection(_))) {
+                            Self::remove_stale(e.path(), format, summary)?;
+                        }
+                    } else if let Some(stripped) = Self::node_name(&name, format) {
+                        if !vec.iter().any(|(n, s)| n == stripped && matches!(s, Self::SingleJson(_))) {
+                            fs::remove_file(e.path())?;
+                            summary.removed.push(e.path());
+                        }
+                    } // other files (eg. notes) are not part of the structure, so are left alone
+                }
+                for (name, structured_json) in vec {
+                    structured_json.update(path.join(name), format, summary)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // removes the json files within a folder which is no longer part of the structure, then the folder itself if nothing else is left
+    fn remove_stale(path: PathBuf, format: FileFormat, summary: &mut UpdateSummary) -> Result<(), io::Error> {
+        for entry in path.read_dir()? {
+            let e = entry?;
+            let name = e.file_name().to_string_lossy().to_string();
+            if e.path().is_dir() {
+                Self::remove_stale(e.path(), format, summary)?;
+            } else if Self::node_name(&name, format).is_some() {
+                fs::remove_file(e.path())?;
+                summary.removed.push(e.path());
+            }
+        }
+        if path.read_dir()?.next().is_none() {
+            fs::remove_dir(&path)?;
+        }
+        Ok(())
+    }
+
     pub fn from_collection<T: Json, F>(items: &[T], namer: F) -> Self where F: Fn(&T) -> Option<String> {
+        Self::from_collection_at_depth(items, namer, SplitDepth::default())
+    }
+
+    pub fn from_collection_at_depth<T: Json, F>(items: &[T], namer: F, depth: SplitDepth) -> Self where F: Fn(&T) -> Option<String> {
         let mut vec = Vec::new();
         let pad_length = digits(items.len());
         for (i, item) in items.iter().enumerate() {
@@ -92,33 +268,59 @@ impl StructuredJson {
                 Some(s) => format!("{}-{}", pad(number, pad_length, '0'), alphanumeric(s)),
                 None => pad(number, pad_length, '0')
             };
-            vec.push((name, item.to_structured_json()))
+            vec.push((name, item.to_structured_json_at_depth(depth)))
         }
         Self::NestedCollection(vec)
     }
 
-    pub fn load(path: PathBuf) -> Result<Self, io::Error> {
+    pub fn load(path: PathBuf, format: FileFormat) -> Result<Self, io::Error> {
         if !path.exists() {
             return Err(io::Error::new(io::ErrorKind::NotFound, format!("Cannot load structured json, '{}' does not exist", path.display())));
         }
-        let file_ext_with_dot = format!(".{}", StructuredJson::FILE_EXTENSION);
         Ok(if path.is_dir() {
             let mut vec = Vec::new();
             for entry in path.read_dir()? {
                 let e = entry?;
                 let mut name = e.file_name().to_string_lossy().to_string();
-                if name.ends_with(&file_ext_with_dot) {
-                    name.truncate(name.len() - file_ext_with_dot.len());
+                if name.starts_with('.') {
+                    continue; // ignore hidden files (eg. split manifest, git metadata)
                 }
-                vec.push((name, Self::load(e.path())?));
+                if let Some(stripped) = Self::node_name(&name, format).filter(|_| !e.path().is_dir()) {
+                    name = stripped.to_string();
+                } else if !e.path().is_dir() {
+                    continue; // ignore other files (eg. lint config) which are not part of the structure
+                } else if !Self::contains_json(&e.path(), format)? {
+                    continue; // ignore folders left behind with only other files (eg. notes)
+                }
+                vec.push((name, Self::load(e.path(), format)?));
             }
             vec.sort_by(|(a, _), (b, _)| a.cmp(b));
             Self::NestedCollection(vec)
         } else {
-            Self::SingleJson(fs::read_to_string(path)?)
+            let text = fs::read_to_string(&path)?;
+            let json = format.to_json(&text)
+                .map_err(|e| io::Error::new(e.kind(), format!("Cannot load '{}': {}", path.display(), e)))?;
+            Self::SingleJson(json)
         })
     }
 
+    fn contains_json(folder: &Path, format: FileFormat) -> Result<bool, io::Error> {
+        for entry in folder.read_dir()? {
+            let e = entry?;
+            let name = e.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue;
+            } else if e.path().is_dir() {
+                if Self::contains_json(&e.path(), format)? {
+                    return Ok(true);
+                }
+            } else if Self::node_name(&name, format).is_some() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
     pub fn extract(&mut self, name: &str) -> Result<Self, StructuredJsonError> {
         match self {
             Self::SingleJson(_) => Err(StructuredJsonError::ExpectedFolderButFoundFile),
@@ -132,6 +334,14 @@ impl StructuredJson {
         }
     }
 
+    pub fn extract_optional(&mut self, name: &str) -> Result<Option<Self>, StructuredJsonError> {
+        match self.extract(name) {
+            Ok(s) => Ok(Some(s)),
+            Err(StructuredJsonError::NodeNotFound(_)) => Ok(None),
+            Err(e) => Err(e)
+        }
+    }
+
     pub fn done(self) -> Result<(), StructuredJsonError> {
         match self {
             Self::SingleJson(_) => Err(StructuredJsonError::ExpectedFolderButFoundFile),
@@ -150,19 +360,90 @@ impl StructuredJson {
         T::from_structured_json(self)
     }
 
-    pub fn to_vec<T: Json>(self) -> Result<Vec<T>, StructuredJsonError> {
+    // items are ordered by their numeric prefix only, so that names which no longer match the content don't matter
+    fn numbered_items<T: Json>(self) -> Result<Vec<(usize, String, T)>, StructuredJsonError> {
         match self {
             Self::SingleJson(_) => Err(StructuredJsonError::ExpectedFolderButFoundFile),
-            Self::NestedCollection(vec) => vec.into_iter().map(|(_, s)| T::from_structured_json(s)).collect()
+            Self::NestedCollection(vec) => {
+                let mut numbered = Vec::new();
+                for (name, s) in vec {
+                    let number = match slot_number(&name) {
+                        Some((number, _)) => number,
+                        None => return Err(StructuredJsonError::InvalidSlotName(name))
+                    };
+                    if numbered.iter().any(|(n, _, _)| *n == number) {
+                        return Err(StructuredJsonError::DuplicateSlot(number));
+                    }
+                    numbered.push((number, name, s));
+                }
+                numbered.sort_by_key(|(n, _, _)| *n);
+                numbered.into_iter()
+                    .map(|(n, name, s)| match T::from_structured_json(s) {
+                        Ok(t) => Ok((n, name, t)),
+                        Err(e) => Err(e.in_node(&name))
+                    })
+                    .collect()
+            }
         }
     }
 
     pub fn to_array<T: Json + Debug, const N: usize>(self) -> Result<Box<[T; N]>, StructuredJsonError> {
-        let vec = self.to_vec()?;
-        let array: [T; N] = vec.try_into().unwrap();
+        let numbered = self.numbered_items::<T>()?;
+        let extra: Vec<String> = numbered.iter().filter(|(n, _, _)| *n < 1 || *n > N).map(|(_, name, _)| name.clone()).collect();
+        if !extra.is_empty() {
+            return Err(StructuredJsonError::ExtraSlots(extra));
+        }
+        let missing: Vec<usize> = (1..=N).filter(|i| !numbered.iter().any(|(n, _, _)| n == i)).collect();
+        if !missing.is_empty() {
+            return Err(StructuredJsonError::MissingSlots(missing));
+        }
+        let vec: Vec<T> = numbered.into_iter().map(|(_, _, t)| t).collect();
+        let array: [T; N] = vec.try_into().unwrap(); // safe because there is exactly one item for each slot
         Ok(Box::new(array))
     }
 
+    // rewrite the numeric prefixes of every collection within the folder, so they are sequential (in their current order),
+    // adding any entries of a collection which don't have a numeric prefix to `unnumbered` (they are left as they are)
+    pub fn renumber(path: PathBuf, format: FileFormat, unnumbered: &mut Vec<PathBuf>) -> Result<usize, io::Error> {
+        let mut renamed = 0;
+        let mut entries = Vec::new();
+        for entry in path.read_dir()? {
+            let e = entry?;
+            let name = e.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') {
+                continue; // ignore hidden files
+            }
+            if e.path().is_dir() {
+                renamed += Self::renumber(e.path(), format, unnumbered)?;
+            }
+            if (e.path().is_dir() && Self::contains_json(&e.path(), format)?) || (!e.path().is_dir() && Self::node_name(&name, format).is_some()) {
+                entries.push(name);
+            }
+        }
+        let (mut entries, others): (Vec<String>, Vec<String>) = entries.into_iter().partition(|n| slot_number(n).is_some());
+        if entries.is_empty() {
+            return Ok(renamed); // not a collection
+        }
+        unnumbered.extend(others.into_iter().map(|n| path.join(n)));
+        entries.sort_by(|a, b| slot_number(a).unwrap().0.cmp(&slot_number(b).unwrap().0).then(a.cmp(b)));
+        let pad_length = digits(entries.len());
+        let mut changes = Vec::new();
+        for (i, name) in entries.into_iter().enumerate() {
+            let new_name = format!("{}{}", pad(i + 1, pad_length, '0'), slot_number(&name).unwrap().1);
+            if new_name != name {
+                changes.push((name, new_name));
+            }
+        }
+        // rename via temporary names first, so that swapped numbers don't collide
+        for (i, (name, _)) in changes.iter().enumerate() {
+            fs::rename(path.join(name), path.join(format!(".renumber{}", i)))?;
+        }
+        for (i, (_, new_name)) in changes.iter().enumerate() {
+            fs::rename(path.join(format!(".renumber{}", i)), path.join(new_name))?;
+        }
+        Ok(renamed + changes.len())
+    }
+
     pub fn to_single_json(self) -> Result<String, StructuredJsonError> {
         match self {
             Self::SingleJson(json) => Ok(json),
@@ -171,6 +452,14 @@ impl StructuredJson {
     }
 }
 
+#[derive(Default)]
+pub struct UpdateSummary {
+    pub added: Vec<PathBuf>,
+    pub changed: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>
+}
+
+#[derive(Default)]
 pub struct FileCount {
     pub files: usize,
     pub folders: usize
@@ -192,6 +481,20 @@ impl AddAssign for FileCount {
     }
 }
 
+// parses a name like `05-NAME` into its number and the remainder (eg. `-NAME`)
+fn slot_number(name: &str) -> Option<(usize, &str)> {
+    let digit_count = name.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digit_count == 0 {
+        return None;
+    }
+    let (number, rest) = name.split_at(digit_count);
+    if rest.is_empty() || rest.starts_with('-') || rest.starts_with('.') {
+        Some((number.parse().ok()?, rest))
+    } else {
+        None
+    }
+}
+
 fn digits(mut number: usize) -> usize {
     let mut length = 0;
     while number != 0 {
@@ -0,0 +1,90 @@
+// Patches are small reusable edits to a live set, either as a JSON merge patch (RFC 7386) where the patch is a partial
+// live set and null removes a field, or as a JSON patch (RFC 6902) where the patch is an array of operations, eg:
+//   { "common": { "key_touch_curve_type": "Heavy" } }
+//   [ { "op": "replace", "path": "/common/key_touch_curve_type", "value": "Heavy" } ]
+
+use std::error::Error;
+use std::fmt::Display;
+
+use serde_json::{Map, Value};
+use validator::Validate;
+
+use crate::roland::live_set::LiveSet;
+
+use super::validation::error_paths;
+
+pub enum LiveSetPatch {
+    Merge(Value),
+    Json(json_patch::Patch)
+}
+
+#[derive(Debug)]
+pub enum PatchError {
+    InvalidOperation(json_patch::PatchError),
+    InvalidResult(serde_path_to_error::Error<serde_json::Error>),
+    FailedValidation(Vec<String>)
+}
+
+impl Error for PatchError {}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidOperation(e) => write!(f, "{}", e),
+            Self::InvalidResult(e) => write!(f, "Patched live set is invalid at path '{}': {}", e.path(), e.inner()),
+            Self::FailedValidation(errors) => write!(f, "Patched live set failed validation: {}", errors.join("; "))
+        }
+    }
+}
+
+impl LiveSetPatch {
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let value: Value = serde_json::from_str(json)?;
+        if value.is_array() {
+            Ok(Self::Json(serde_json::from_value(value)?))
+        } else {
+            Ok(Self::Merge(value))
+        }
+    }
+
+    pub fn apply(&self, live_set: &LiveSet) -> Result<LiveSet, PatchError> {
+        let mut value = serde_json::to_value(live_set).unwrap();
+        match self {
+            Self::Merge(patch) => json_patch::merge(&mut value, patch),
+            Self::Json(patch) => json_patch::patch(&mut value, patch).map_err(PatchError::InvalidOperation)?
+        }
+        let json = value.to_string();
+        let patched: LiveSet = serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(&json)).map_err(PatchError::InvalidResult)?;
+        if let Err(errors) = patched.validate() {
+            return Err(PatchError::FailedValidation(error_paths(&errors)));
+        }
+        Ok(patched)
+    }
+}
+
+// the smallest merge patch which turns `from` into `to`, arrays are always replaced as a whole
+pub fn merge_diff(from: &Value, to: &Value) -> Value {
+    match (from, to) {
+        (Value::Object(from_map), Value::Object(to_map)) => {
+            let mut diff = Map::new();
+            for (key, to_value) in to_map {
+                match from_map.get(key) {
+                    Some(from_value) if from_value == to_value => {},
+                    Some(from_value) => {
+                        diff.insert(key.clone(), merge_diff(from_value, to_value));
+                    },
+                    None => {
+                        diff.insert(key.clone(), to_value.clone());
+                    }
+                }
+            }
+            for key in from_map.keys() {
+                if !to_map.contains_key(key) {
+                    diff.insert(key.clone(), Value::Null);
+                }
+            }
+            Value::Object(diff)
+        },
+        _ => to.clone()
+    }
+}
@@ -0,0 +1,171 @@
+// An effect preset library is a folder of JSON files, each holding one named MFX, reverb or chorus (with all of its
+// parameters) which can be saved from a live set and applied to others, eg. `presets/Tuned Rotary.json`:
+//   { "name": "Tuned Rotary", "mfx": { "enable": true, "mfx_type": { "Rotary": { "speed": "Slow", ... } } } }
+
+// A preset is validated before it is applied, so that a hand edited preset cannot put parameters out of range.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use validator::{Validate, ValidationErrors};
+
+use crate::roland::live_set::LiveSet;
+use crate::roland::live_set::chorus::Chorus;
+use crate::roland::live_set::mfx::Mfx;
+use crate::roland::live_set::reverb::Reverb;
+use crate::roland::types::effects::Effect;
+
+use super::Json;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EffectPreset {
+    pub name: String,
+    #[serde(flatten)]
+    pub effect: PresetEffect
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PresetEffect {
+    Mfx(Mfx),
+    Reverb(Reverb),
+    Chorus(Chorus)
+}
+
+#[derive(Debug)]
+pub enum PresetError {
+    InvalidName(String),
+    NotFound(String, PathBuf),
+    InvalidPreset(PathBuf, serde_json::Error),
+    OutOfRange(String, ValidationErrors),
+    Io(PathBuf, io::Error)
+}
+
+impl Error for PresetError {}
+
+impl Display for PresetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "Preset name '{}' cannot be used as a file name", name),
+            Self::NotFound(name, folder) => write!(f, "Preset '{}' was not found in '{}'", name, folder.display()),
+            Self::InvalidPreset(path, e) => write!(f, "Preset '{}' is not valid: {}", path.display(), e),
+            Self::OutOfRange(name, e) => write!(f, "Preset '{}' has parameters out of range: {}", name, e),
+            Self::Io(path, e) => write!(f, "Failed to access '{}': {}", path.display(), e)
+        }
+    }
+}
+
+impl EffectPreset {
+    pub fn from_live_set(name: String, live_set: &LiveSet, effect: Effect) -> Self {
+        // effects cannot be cloned, but always round trip through JSON
+        let effect = match effect {
+            Effect::Mfx => PresetEffect::Mfx(Mfx::from_json(live_set.mfx.to_json()).unwrap()),
+            Effect::Reverb => PresetEffect::Reverb(Reverb::from_json(live_set.reverb.to_json()).unwrap()),
+            Effect::Chorus => PresetEffect::Chorus(Chorus::from_json(live_set.chorus.to_json()).unwrap())
+        };
+        Self { name, effect }
+    }
+
+    pub fn effect(&self) -> Effect {
+        match &self.effect {
+            PresetEffect::Mfx(_) => Effect::Mfx,
+            PresetEffect::Reverb(_) => Effect::Reverb,
+            PresetEffect::Chorus(_) => Effect::Chorus
+        }
+    }
+
+    pub fn type_name(&self) -> String {
+        match &self.effect {
+            PresetEffect::Mfx(m) => m.mfx_type.name(),
+            PresetEffect::Reverb(r) => r.reverb_type.name().to_string(),
+            PresetEffect::Chorus(c) => c.chorus_type.name().to_string()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), PresetError> {
+        let result = match &self.effect {
+            PresetEffect::Mfx(m) => m.validate(),
+            PresetEffect::Reverb(r) => r.validate(),
+            PresetEffect::Chorus(c) => c.validate()
+        };
+        result.map_err(|e| PresetError::OutOfRange(self.name.clone(), e))
+    }
+
+    // replaces the effect of the live set with a copy of this preset (which should already be validated)
+    pub fn apply(&self, live_set: &mut LiveSet) {
+        match &self.effect {
+            PresetEffect::Mfx(m) => live_set.mfx = Mfx::from_json(m.to_json()).unwrap(),
+            PresetEffect::Reverb(r) => live_set.reverb = Reverb::from_json(r.to_json()).unwrap(),
+            PresetEffect::Chorus(c) => live_set.chorus = Chorus::from_json(c.to_json()).unwrap()
+        }
+    }
+}
+
+pub struct PresetLibrary {
+    folder: PathBuf
+}
+
+impl PresetLibrary {
+    pub fn new(folder: &Path) -> Self {
+        Self {
+            folder: folder.to_path_buf()
+        }
+    }
+
+    fn path_for(&self, name: &str) -> Result<PathBuf, PresetError> {
+        if name.trim().is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            Err(PresetError::InvalidName(name.to_string()))
+        } else {
+            Ok(self.folder.join(format!("{}.json", name)))
+        }
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>, PresetError> {
+        let mut files = Vec::new();
+        if !self.folder.exists() {
+            return Ok(files);
+        }
+        let entries = fs::read_dir(&self.folder).map_err(|e| PresetError::Io(self.folder.clone(), e))?;
+        for entry in entries {
+            let path = entry.map_err(|e| PresetError::Io(self.folder.clone(), e))?.path();
+            if path.extension().map(|e| e.eq_ignore_ascii_case("json")).unwrap_or(false) {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    }
+
+    // every preset in the library, sorted by name
+    pub fn list(&self) -> Result<Vec<EffectPreset>, PresetError> {
+        let mut presets = self.files()?.iter().map(|path| Self::load(path)).collect::<Result<Vec<_>, _>>()?;
+        presets.sort_by_key(|p| p.name.to_lowercase());
+        Ok(presets)
+    }
+
+    pub fn get(&self, name: &str) -> Result<EffectPreset, PresetError> {
+        let path = self.path_for(name)?;
+        if path.exists() {
+            return Self::load(&path);
+        }
+        // the file name may differ from the name inside it (eg. if renamed by hand)
+        self.files()?.iter().filter_map(|path| Self::load(path).ok()).find(|p| p.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| PresetError::NotFound(name.to_string(), self.folder.clone()))
+    }
+
+    // returns true if an existing preset was replaced
+    pub fn save(&self, preset: &EffectPreset) -> Result<bool, PresetError> {
+        let path = self.path_for(&preset.name)?;
+        let existed = path.exists();
+        fs::create_dir_all(&self.folder).map_err(|e| PresetError::Io(self.folder.clone(), e))?;
+        fs::write(&path, serde_json::to_string_pretty(preset).unwrap()).map_err(|e| PresetError::Io(path.clone(), e))?;
+        Ok(existed)
+    }
+
+    fn load(path: &Path) -> Result<EffectPreset, PresetError> {
+        let json = fs::read_to_string(path).map_err(|e| PresetError::Io(path.to_path_buf(), e))?;
+        serde_json::from_str(&json).map_err(|e| PresetError::InvalidPreset(path.to_path_buf(), e))
+    }
+}
@@ -24,6 +24,31 @@ pub fn one_of_schema_with_default(sub_schemas: Vec<Schema>, default: Option<Valu
     .into()
 }
 
+// gives the schema of a property a title and description, wrapping a reference in allOf (as schemars does for fields)
+pub fn described_schema(schema: Schema, title: &str, description: &str) -> Schema {
+    let mut object = match schema {
+        Schema::Object(object) if !object.is_ref() => object,
+        schema => SchemaObject {
+            subschemas: Some(Box::new(SubschemaValidation {
+                all_of: Some(vec![schema]),
+                ..Default::default()
+            })),
+            ..Default::default()
+        }
+    };
+    let metadata = object.metadata();
+    metadata.title = Some(title.into());
+    metadata.description = Some(description.into());
+    object.into()
+}
+
+// adds the unit which a value is shown in on the keyboard (eg. "Hz"), as the non-standard "unit" keyword
+pub fn unit_schema(schema: Schema, unit: &str) -> Schema {
+    let mut object = schema.into_object();
+    object.extensions.insert("unit".into(), unit.into());
+    object.into()
+}
+
 pub fn u16_schema(min: u16, max: u16) -> Schema {
     integer_schema(min, max, "uint16")
 }
@@ -94,6 +119,13 @@ pub fn single_property_schema_of<T: Default + Serialize + JsonSchema>(property:
     object_schema(vec![(property, gen.subschema_for::<T>())], Some(value))
 }
 
+pub fn described_property_schema_of<T: Default + Serialize + JsonSchema>(property: &str, title: &str, description: &str, gen: &mut schemars::gen::SchemaGenerator) -> Schema {
+    let mut map = serde_json::Map::new();
+    map.insert(property.to_string(), serde_json::to_value(T::default()).unwrap());
+    let value = Value::Object(map);
+    object_schema(vec![(property, described_schema(gen.subschema_for::<T>(), title, description))], Some(value))
+}
+
 pub fn single_property_schema(property: &str, schema: Schema) -> Schema {
     object_schema(vec![(property, schema)], None)
 }
@@ -0,0 +1,73 @@
+// A selection of live sets, written as a comma separated list of set numbers or ranges, each optionally prefixed by
+// its bank (which defaults to user sets), eg. "1,3,10-20" or "user_sets/1-5,piano/2"
+
+use std::str::FromStr;
+
+use crate::roland::rd300nx::RD300NX;
+
+pub const BANKS: [&str; 3] = ["user_sets", "piano", "e_piano"];
+const DEFAULT_BANK: &str = "user_sets";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetSelection(Vec<(&'static str, usize)>);
+
+impl SetSelection {
+    pub fn sets(&self) -> &[(&'static str, usize)] {
+        &self.0
+    }
+
+    pub fn contains(&self, bank: &str, index: usize) -> bool {
+        self.0.iter().any(|(b, i)| *b == bank && *i == index)
+    }
+}
+
+impl FromStr for SetSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sets = Vec::new();
+        for part in s.split(',').map(|p| p.trim()) {
+            let (bank, range) = match part.split_once('/') {
+                Some((bank, range)) => (BANKS.into_iter().find(|b| *b == bank).ok_or(format!("Invalid bank '{}' (expected one of: {})", bank, BANKS.join(", ")))?, range),
+                None => (DEFAULT_BANK, part)
+            };
+            let (first, last) = range.split_once('-').unwrap_or((range, range));
+            let first: usize = first.parse().map_err(|_| format!("Invalid set number '{}'", first))?;
+            let last: usize = last.parse().map_err(|_| format!("Invalid set number '{}'", last))?;
+            let size = bank_size(bank);
+            if first < 1 || last > size || first > last {
+                return Err(format!("Invalid range '{}' ({} must be within 1-{})", range, bank, size));
+            }
+            for number in first..=last {
+                let set = (bank, number - 1);
+                if !sets.contains(&set) {
+                    sets.push(set);
+                }
+            }
+        }
+        Ok(Self(sets))
+    }
+}
+
+pub fn bank_size(bank: &str) -> usize {
+    match bank {
+        "piano" => RD300NX::PIANO_SETS,
+        "e_piano" => RD300NX::E_PIANO_SETS,
+        _ => RD300NX::USER_SETS
+    }
+}
+
+pub fn set_label(bank: &str, index: usize) -> String {
+    format!("{}/{}", bank, index + 1) // intentionally 1-indexed because user facing
+}
+
+pub fn parse_set_label(label: &str) -> Option<(&'static str, usize)> {
+    let (bank, number) = label.split_once('/')?;
+    let bank = BANKS.into_iter().find(|b| *b == bank)?;
+    let number: usize = number.parse().ok()?;
+    if number == 0 {
+        None
+    } else {
+        Some((bank, number - 1))
+    }
+}
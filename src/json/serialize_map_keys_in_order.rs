@@ -4,6 +4,12 @@ use serde::{Serialize, Deserialize, ser::SerializeMap};
 use strum::IntoEnumIterator;
 
 use super::type_name_pretty;
+use super::schema::described_schema;
+
+// a key of a map in the JSON, which is described in the schema
+pub trait MapKey: IntoEnumIterator + Display {
+    fn describe(&self) -> String; // eg. "MIDI channel 1"
+}
 
 pub fn deserialize<'de, D, K: Deserialize<'de> + Eq + Hash, V: Deserialize<'de>>(deserializer: D) -> Result<HashMap<K, V>, D::Error> 
 where D: serde::Deserializer<'de>
@@ -23,9 +29,9 @@ where S: serde::Serializer
     map.end()
 }
 
-pub struct OptionalMapSchema<K: IntoEnumIterator + Display, V: JsonSchema>(PhantomData<K>, PhantomData<V>);
+pub struct OptionalMapSchema<K: MapKey, V: JsonSchema>(PhantomData<K>, PhantomData<V>);
 
-impl<K: IntoEnumIterator + Display, V: JsonSchema> JsonSchema for OptionalMapSchema<K, V> {
+impl<K: MapKey, V: JsonSchema> JsonSchema for OptionalMapSchema<K, V> {
     fn schema_name() -> String {
         format!("Optional_map_of_{}_to_{}", type_name_pretty::<K>(), V::schema_name())
     }
@@ -35,9 +41,9 @@ impl<K: IntoEnumIterator + Display, V: JsonSchema> JsonSchema for OptionalMapSch
     }
 }
 
-pub struct RequiredMapSchema<K: IntoEnumIterator + Display, V: JsonSchema>(PhantomData<K>, PhantomData<V>);
+pub struct RequiredMapSchema<K: MapKey, V: JsonSchema>(PhantomData<K>, PhantomData<V>);
 
-impl<K: IntoEnumIterator + Display, V: JsonSchema> JsonSchema for RequiredMapSchema<K, V> {
+impl<K: MapKey, V: JsonSchema> JsonSchema for RequiredMapSchema<K, V> {
     fn schema_name() -> String {
         format!("Required_map_of_{}_to_{}", type_name_pretty::<K>(), V::schema_name())
     }
@@ -47,11 +53,11 @@ impl<K: IntoEnumIterator + Display, V: JsonSchema> JsonSchema for RequiredMapSch
     }
 }
 
-fn map_schema<K: IntoEnumIterator + Display, V: JsonSchema>(gen: &mut schemars::gen::SchemaGenerator, include_required: bool) -> schemars::schema::Schema {
+fn map_schema<K: MapKey, V: JsonSchema>(gen: &mut schemars::gen::SchemaGenerator, include_required: bool) -> schemars::schema::Schema {
     let mut properties = Map::<String, Schema>::new();
     let mut required = Set::<String>::new();
     for key in K::iter() {
-        properties.insert(key.to_string(), gen.subschema_for::<V>());
+        properties.insert(key.to_string(), described_schema(gen.subschema_for::<V>(), &key.to_string(), &format!("The value for {}", key.describe())));
         if include_required {
             required.insert(key.to_string());
         }
@@ -0,0 +1,239 @@
+// A set list describes a show as an ordered list of songs, each pointing at a live set JSON file in a patch library,
+// from which a complete RD300NX can be built. It can be written in YAML or JSON, eg:
+//   base: BASE.RDS                # optional, provides the system settings and piano/e-piano sets
+//   favorites:                    # optional, fills favorite banks with the user sets which have a tag
+//     A: opener
+//   sets:
+//     1: patches/piano.json
+//     2:
+//       patch: patches/strings.json
+//       song: Moonlight Sonata
+//       name: Moonlight
+//       tempo: 96
+//       tags: [opener]
+//       tone_remain: true         # keep held notes when changing to this set from the previous one
+
+// User set slots which are not in the set list are filled with blank live sets. Patch files can be written in any file
+// format (by extension), are upgraded if they were written by an older version, and can declare a base (eg. a template
+// in a `templates` folder next to the set list, or another slot of the set list as "user_sets/N").
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::roland::live_set::LiveSet;
+use crate::roland::live_set::metadata::LiveSetMetadata;
+use crate::roland::rd300nx::RD300NX;
+use crate::roland::system::favorites::{Bank, Favorite, Favorites};
+use crate::roland::types::metadata::{BySet, PatchRange, ToneRemain, ToneRemainRanges, ToneRemainSets};
+
+use serde_json::{json, Value};
+
+use super::Json;
+use super::format::FileFormat;
+use super::migration::{json_version, migrate_json, VERSION_FIELD};
+use super::templates::{Templated, Templates};
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SetList {
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub favorites: BTreeMap<char, String>,
+    pub sets: BTreeMap<usize, SetListEntry>
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum SetListEntry {
+    Patch(String),
+    Song(Song)
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Song {
+    pub patch: String,
+    #[serde(default)]
+    pub song: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub tempo: Option<u16>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub tone_remain: bool
+}
+
+#[derive(Debug)]
+pub enum SetListError {
+    InvalidSlot(usize),
+    InvalidPatch(PathBuf, String),
+    InvalidFavoriteBank(char),
+    TooManyFavorites(char, String, usize),
+    InvalidToneRemain(usize),
+    InvalidTemplates(String)
+}
+
+impl Error for SetListError {}
+
+impl Display for SetListError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSlot(slot) => write!(f, "Slot {} is not a valid user set (should be 1-{})", slot, RD300NX::USER_SETS),
+            Self::InvalidPatch(path, e) => write!(f, "Invalid patch '{}': {}", path.display(), e),
+            Self::InvalidFavoriteBank(letter) => write!(f, "Favorite bank '{}' should be one of: {:?}", letter, Favorites::BANK_LETTERS),
+            Self::TooManyFavorites(letter, tag, count) => write!(f, "Favorite bank {} only has {} favorites, but {} sets are tagged '{}'", letter, Bank::USED_FAVORITES, count, tag),
+            Self::InvalidToneRemain(slot) => write!(f, "Slot {} cannot have tone_remain, as there is no set before it", slot),
+            Self::InvalidTemplates(e) => write!(f, "Invalid templates: {}", e)
+        }
+    }
+}
+
+impl SetList {
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        let text = fs::read_to_string(path)?;
+        let is_yaml = path.extension().map(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml")).unwrap_or(false);
+        let result = if is_yaml {
+            serde_yaml::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        };
+        result.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid set list '{}': {}", path.display(), e)))
+    }
+
+    // builds onto the given base (which provides everything except the user sets), where patch files are relative to the folder
+    pub fn build(&self, mut rds: RD300NX, folder: &Path) -> Result<RD300NX, SetListError> {
+        rds.user_sets = RD300NX::blank().user_sets;
+        let mut patches = vec![Value::Null; RD300NX::USER_SETS];
+        for (slot, entry) in &self.sets {
+            if *slot < 1 || *slot > RD300NX::USER_SETS {
+                return Err(SetListError::InvalidSlot(*slot));
+            }
+            patches[slot - 1] = entry.load_patch(folder)?;
+        }
+        // patches are resolved together, so that a patch can have another slot of the set list as its base
+        let templates = Templates::find(&Some(folder.to_string_lossy().to_string())).map_err(|e| SetListError::InvalidTemplates(e.to_string()))?;
+        let mut patches = json!({ "user_sets": patches });
+        Templated::resolve(&mut patches, templates).map_err(|e| SetListError::InvalidTemplates(e.to_string()))?;
+        for (slot, entry) in &self.sets {
+            rds.user_sets[slot - 1] = entry.to_live_set(folder, patches["user_sets"][slot - 1].take())?;
+        }
+        for (letter, tag) in &self.favorites {
+            let slots: Vec<usize> = self.sets.iter().filter(|(_, e)| e.tags().contains(tag)).map(|(s, _)| *s).collect();
+            let bank = rds.system.favorites_mut().bank_mut(*letter).ok_or(SetListError::InvalidFavoriteBank(*letter))?;
+            if slots.len() > Bank::USED_FAVORITES {
+                return Err(SetListError::TooManyFavorites(*letter, tag.clone(), slots.len()));
+            }
+            for (favorite, slot) in bank.used_favorites_mut().iter_mut().zip(slots) {
+                *favorite = Favorite::user_set(slot - 1);
+            }
+        }
+        if let Some(user_sets) = self.tone_remain_ranges()? {
+            let (piano, e_piano) = match std::mem::replace(&mut rds.system.common.tone_remain, ToneRemain::Always(false)) {
+                ToneRemain::Always(b) => (BySet::Always(b), BySet::Always(b)),
+                ToneRemain::BySet(s) => (s.piano, s.e_piano)
+            };
+            rds.system.common.tone_remain = ToneRemain::BySet(ToneRemainSets {
+                user_sets: BySet::ByRange(user_sets),
+                piano,
+                e_piano
+            });
+        }
+        Ok(rds)
+    }
+
+    // each run of sets marked with tone_remain becomes a range, which starts from the set before the first one marked
+    fn tone_remain_ranges(&self) -> Result<Option<ToneRemainRanges<{RD300NX::USER_SETS}>>, SetListError> {
+        let marked: Vec<usize> = self.sets.iter().filter(|(_, e)| e.tone_remain()).map(|(s, _)| *s).collect();
+        if marked.is_empty() {
+            return Ok(None);
+        }
+        let mut ranges = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+        for slot in marked {
+            if slot < 2 {
+                return Err(SetListError::InvalidToneRemain(slot));
+            }
+            current = match current {
+                Some((from, to)) if to == slot - 1 => Some((from, slot)),
+                Some((from, to)) => {
+                    ranges.push(PatchRange::new(from, to));
+                    Some((slot - 1, slot))
+                },
+                None => Some((slot - 1, slot))
+            };
+        }
+        if let Some((from, to)) = current {
+            ranges.push(PatchRange::new(from, to));
+        }
+        Ok(Some(ToneRemainRanges::new(ranges)))
+    }
+}
+
+impl SetListEntry {
+    fn song(&self) -> Option<&Song> {
+        match self {
+            Self::Patch(_) => None,
+            Self::Song(song) => Some(song)
+        }
+    }
+
+    fn tags(&self) -> &[String] {
+        self.song().map(|s| s.tags.as_slice()).unwrap_or_default()
+    }
+
+    fn tone_remain(&self) -> bool {
+        self.song().map(|s| s.tone_remain).unwrap_or_default()
+    }
+
+    fn path(&self, folder: &Path) -> PathBuf {
+        let patch = match self {
+            Self::Patch(patch) => patch,
+            Self::Song(song) => &song.patch
+        };
+        folder.join(patch)
+    }
+
+    // reads the patch file as a live set JSON value, upgraded to the current format version (but not yet resolved)
+    fn load_patch(&self, folder: &Path) -> Result<Value, SetListError> {
+        let path = self.path(folder);
+        let invalid = |e: String| SetListError::InvalidPatch(path.clone(), e);
+        let text = fs::read_to_string(&path).map_err(|e| invalid(e.to_string()))?;
+        let mut patch = FileFormat::for_file(&Some(path.to_string_lossy().to_string())).to_value(&text).map_err(|e| invalid(e.to_string()))?;
+        let version = json_version(&patch).map_err(|e| invalid(e.to_string()))?;
+        patch.as_object_mut().map(|m| m.remove(VERSION_FIELD));
+        let mut wrapped = json!({ VERSION_FIELD: version, "user_sets": [patch] });
+        migrate_json(&mut wrapped).map_err(|e| invalid(e.to_string()))?;
+        Ok(wrapped["user_sets"][0].take())
+    }
+
+    fn to_live_set(&self, folder: &Path, patch: Value) -> Result<LiveSet, SetListError> {
+        let mut live_set = LiveSet::from_json(patch.to_string()).map_err(|e| SetListError::InvalidPatch(self.path(folder), e.to_string()))?;
+        if let Some(song) = self.song() {
+            if let Some(name) = &song.name {
+                live_set.common.set_name(name);
+            }
+            if let Some(tempo) = song.tempo {
+                live_set.common.set_live_set_tempo(tempo);
+            }
+            if song.song.is_some() || !song.tags.is_empty() {
+                let metadata = live_set.metadata.get_or_insert_with(LiveSetMetadata::default);
+                if song.song.is_some() {
+                    metadata.song = song.song.clone();
+                }
+                for tag in &song.tags {
+                    if !metadata.tags.contains(tag) {
+                        metadata.tags.push(tag.clone());
+                    }
+                }
+            }
+        }
+        Ok(live_set)
+    }
+}
@@ -0,0 +1,306 @@
+// A cue sheet summarises the selected live sets for use during a performance, showing what the player will actually
+// get from each one (where System mode pedal/switch settings override the live set's own), rendered as Markdown or HTML.
+// Notes can also be provided in a sidecar YAML or JSON file, keyed by set number (or bank/number), eg:
+//   3: Hold the sustain through the change
+//   piano/1: Encore only
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use strum::IntoEnumIterator;
+
+use crate::roland::live_set::{LiveSet, mfx::Mfx};
+use crate::roland::rd300nx::RD300NX;
+use crate::roland::types::enums::{ButtonFunction, Layer, PedalFunction, SettingMode, SliderFunction};
+
+use super::selection::{bank_size, parse_set_label, set_label};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SheetFormat {
+    Markdown,
+    Html
+}
+
+impl SheetFormat {
+    // html for a .html/.htm file, otherwise markdown
+    pub fn for_path(path: &Option<String>) -> Self {
+        let is_html = path.as_ref()
+            .and_then(|p| Path::new(p).extension())
+            .map(|e| e.eq_ignore_ascii_case("html") || e.eq_ignore_ascii_case("htm"))
+            .unwrap_or(false);
+        if is_html {
+            Self::Html
+        } else {
+            Self::Markdown
+        }
+    }
+}
+
+pub struct SheetNotes(BTreeMap<(&'static str, usize), String>);
+
+impl SheetNotes {
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        let text = fs::read_to_string(path)?;
+        let is_yaml = path.extension().map(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml")).unwrap_or(false);
+        let result: Result<BTreeMap<String, String>, String> = if is_yaml {
+            serde_yaml::from_str(&text).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&text).map_err(|e| e.to_string())
+        };
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid notes '{}': {}", path.display(), e));
+        let mut notes = BTreeMap::new();
+        for (key, note) in result.map_err(invalid)? {
+            let set = match key.parse::<usize>() {
+                Ok(number) if number > 0 => Some(("user_sets", number - 1)),
+                Ok(_) => None,
+                Err(_) => parse_set_label(&key)
+            };
+            match set {
+                Some((bank, index)) if index < bank_size(bank) => notes.insert((bank, index), note),
+                _ => return Err(invalid(format!("'{}' is not a valid live set", key)))
+            };
+        }
+        Ok(Self(notes))
+    }
+
+    pub fn get(&self, bank: &'static str, index: usize) -> Option<&String> {
+        self.0.get(&(bank, index))
+    }
+}
+
+pub struct CueSheet {
+    pub sets: Vec<SheetSet>
+}
+
+pub struct SheetSet {
+    pub label: String,
+    pub name: String,
+    pub song: Option<String>,
+    pub tags: Vec<String>,
+    pub notes: Vec<String>,
+    pub layers: Vec<SheetLayer>,
+    pub controls: Vec<(String, String)>
+}
+
+pub struct SheetLayer {
+    pub layer: Layer,
+    pub external: bool, // an external layer sends MIDI, so its tone is the port and channel it sends on
+    pub tone: String,
+    pub keys: String
+}
+
+impl SheetLayer {
+    pub fn name(&self) -> String {
+        if self.external {
+            format!("{} (external)", self.layer)
+        } else {
+            self.layer.to_string()
+        }
+    }
+}
+
+impl CueSheet {
+    // the user sets which have been given a name, for when no selection is made
+    pub fn named_user_sets(rds: &RD300NX) -> Vec<(&'static str, usize)> {
+        rds.user_sets.iter().enumerate()
+            .filter(|(_, ls)| !ls.has_blank_name() && !ls.has_default_name())
+            .map(|(i, _)| ("user_sets", i))
+            .collect()
+    }
+
+    pub fn new(rds: &RD300NX, sets: &[(&'static str, usize)], notes: Option<&SheetNotes>) -> Self {
+        let system = &rds.system.common;
+        let mut sheet_sets = Vec::new();
+        for (bank, index) in sets {
+            let live_set = match *bank {
+                "piano" => &rds.piano[*index],
+                "e_piano" => &rds.e_piano[*index],
+                _ => &rds.user_sets[*index]
+            };
+            let metadata = live_set.metadata.clone().unwrap_or_default();
+            let mut set_notes: Vec<String> = metadata.notes.into_iter().collect();
+            if let Some(note) = notes.and_then(|n| n.get(bank, *index)) {
+                set_notes.push(note.clone());
+            }
+            let (fc1, fc2, pedal_source) = match system.pedal_mode {
+                SettingMode::LiveSet => (live_set.common.fc1_assign, live_set.common.fc2_assign, ""),
+                SettingMode::System => (system.fc1_assign, system.fc2_assign, " (system)")
+            };
+            let (s1, s2, button_source) = match system.s1_s2_mode {
+                SettingMode::LiveSet => (live_set.common.s1_assign, live_set.common.s2_assign, ""),
+                SettingMode::System => (system.s1_assign, system.s2_assign, " (system)")
+            };
+            let layers = sheet_layers(live_set);
+            let mut controls = vec![
+                ("FC1".to_string(), format!("{}{}", pedal_name(fc1), pedal_source)),
+                ("FC2".to_string(), format!("{}{}", pedal_name(fc2), pedal_source)),
+                ("S1".to_string(), format!("{}{}", button_with_state(s1, live_set.common.s1_state, &live_set.mfx), button_source)),
+                ("S2".to_string(), format!("{}{}", button_with_state(s2, live_set.common.s2_state, &live_set.mfx), button_source))
+            ];
+            for layer in Layer::iter().filter(|l| layers.iter().any(|sl| sl.layer == *l)) {
+                controls.push((format!("Slider {}", layer), slider_name(live_set.common.slider_assign(&layer))));
+            }
+            sheet_sets.push(SheetSet {
+                label: if *bank == "user_sets" { (index + 1).to_string() } else { set_label(bank, *index) },
+                name: live_set.name_string().trim().to_string(),
+                song: metadata.song,
+                tags: metadata.tags,
+                notes: set_notes,
+                layers,
+                controls
+            });
+        }
+        Self {
+            sets: sheet_sets
+        }
+    }
+
+    pub fn render(&self, format: SheetFormat) -> String {
+        match format {
+            SheetFormat::Markdown => self.to_markdown(),
+            SheetFormat::Html => self.to_html()
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Live Sets\n");
+        for set in &self.sets {
+            md.push_str(&format!("\n## {}. {}\n\n", set.label, markdown_escape(&set.name)));
+            if let Some(song) = &set.song {
+                md.push_str(&format!("*{}*\n\n", markdown_escape(song)));
+            }
+            if !set.tags.is_empty() {
+                md.push_str(&format!("Tags: {}\n\n", markdown_escape(&set.tags.join(", "))));
+            }
+            for note in &set.notes {
+                md.push_str(&format!("> {}\n\n", markdown_escape(note).replace('\n', "\n> ")));
+            }
+            if set.layers.is_empty() {
+                md.push_str("No layers enabled.\n\n");
+            } else {
+                md.push_str("| Layer | Tone | Keys |\n|---|---|---|\n");
+                for layer in &set.layers {
+                    md.push_str(&format!("| {} | {} | {} |\n", layer.name(), markdown_escape(&layer.tone), layer.keys));
+                }
+                md.push('\n');
+            }
+            md.push_str("| Control | Assignment |\n|---|---|\n");
+            for (control, assignment) in &set.controls {
+                md.push_str(&format!("| {} | {} |\n", control, assignment));
+            }
+        }
+        md
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Live Sets</title>\n");
+        html.push_str("<style>body { font-family: sans-serif; } table { border-collapse: collapse; margin-bottom: 1em; } th, td { border: 1px solid #999; padding: 2px 8px; text-align: left; } section { page-break-inside: avoid; }</style>\n");
+        html.push_str("</head>\n<body>\n<h1>Live Sets</h1>\n");
+        for set in &self.sets {
+            html.push_str(&format!("<section>\n<h2>{}. {}</h2>\n", set.label, html_escape(&set.name)));
+            if let Some(song) = &set.song {
+                html.push_str(&format!("<p><em>{}</em></p>\n", html_escape(song)));
+            }
+            if !set.tags.is_empty() {
+                html.push_str(&format!("<p>Tags: {}</p>\n", html_escape(&set.tags.join(", "))));
+            }
+            for note in &set.notes {
+                html.push_str(&format!("<blockquote>{}</blockquote>\n", html_escape(note).replace('\n', "<br>")));
+            }
+            if set.layers.is_empty() {
+                html.push_str("<p>No layers enabled.</p>\n");
+            } else {
+                html.push_str("<table>\n<tr><th>Layer</th><th>Tone</th><th>Keys</th></tr>\n");
+                for layer in &set.layers {
+                    html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n", layer.name(), html_escape(&layer.tone), layer.keys));
+                }
+                html.push_str("</table>\n");
+            }
+            html.push_str("<table>\n<tr><th>Control</th><th>Assignment</th></tr>\n");
+            for (control, assignment) in &set.controls {
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", control, assignment));
+            }
+            html.push_str("</table>\n</section>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn sheet_layers(live_set: &LiveSet) -> Vec<SheetLayer> {
+    let mut layers = Vec::new();
+    for (layer, logical) in Layer::iter().zip(live_set.layers.iter()) {
+        if let Some((lower, upper)) = logical.internal.key_range(live_set.common.split_switch_internal) {
+            layers.push(SheetLayer {
+                layer,
+                external: false,
+                tone: logical.tone.tone_name(),
+                keys: format!("{:?}-{:?}", lower, upper)
+            });
+        }
+    }
+    for (layer, logical) in Layer::iter().zip(live_set.layers.iter()) {
+        let external = &logical.external;
+        if let Some((lower, upper)) = external.key_range(live_set.common.split_switch_external) {
+            let program = if external.transmit_program_change() {
+                format!(" PC {}", external.program_change() as usize + 1) // as shown on the keyboard
+            } else {
+                String::new()
+            };
+            layers.push(SheetLayer {
+                layer,
+                external: true,
+                tone: format!("MIDI {} ({:?}){}", external.transmit_channel(), external.transmit_port(), program),
+                keys: format!("{:?}-{:?}", lower, upper)
+            });
+        }
+    }
+    layers
+}
+
+// the state the switch starts in, which for the MFX switch is whether the MFX is enabled (the live set's state is ignored)
+fn button_with_state(function: ButtonFunction, state: bool, mfx: &Mfx) -> String {
+    let state = if function == ButtonFunction::Mfx1Switch { mfx.enable } else { state };
+    if function == ButtonFunction::Off {
+        button_name(function)
+    } else if state {
+        format!("{} (on)", button_name(function))
+    } else {
+        format!("{} (off)", button_name(function))
+    }
+}
+
+fn pedal_name(function: PedalFunction) -> String {
+    match function {
+        PedalFunction::ControlChange(cc) => format!("CC{}", cc),
+        _ => format!("{:?}", function)
+    }
+}
+
+fn slider_name(function: SliderFunction) -> String {
+    match function {
+        SliderFunction::ControlChange(cc) => format!("CC{}", cc),
+        _ => format!("{:?}", function)
+    }
+}
+
+fn button_name(function: ButtonFunction) -> String {
+    format!("{:?}", function)
+}
+
+fn markdown_escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "\\`*_[]|<>#".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
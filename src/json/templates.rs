@@ -0,0 +1,345 @@
+// A live set can declare a base, being either another live set (eg. "user_sets/3") or a named template (eg. "template:NAME"),
+// in which case it only needs to contain the fields which differ from its base (applied as a JSON merge patch), eg:
+//   { "base": "template:Piano", "common": { "name": "Moonlight" } }
+
+// Templates are partial or complete live sets stored as `templates/NAME.json` next to the JSON file (or inside the split folder),
+// which can also be YAML or TOML (by extension), and are written in the format of the JSON file they were extracted into.
+// The metadata of a base is never inherited. Live sets are always resolved in full before being validated or encoded.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::{Map, Value};
+
+use crate::roland::live_set::LiveSet;
+
+use super::canonical::to_canonical_json;
+use super::format::FileFormat;
+use super::patch::merge_diff;
+use super::selection::{parse_set_label, set_label, SetSelection, BANKS};
+use super::{slot_number, Json, StructuredJson, StructuredJsonError};
+
+pub const TEMPLATES_FOLDER: &str = "templates";
+const BASE_FIELD: &str = "base";
+const METADATA_FIELD: &str = "metadata";
+const TEMPLATE_PREFIX: &str = "template:";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    InvalidBase(String, String),
+    BaseNotFound(String, String),
+    CircularBase(Vec<String>),
+    InvalidTemplate(String, serde_json::Error),
+    Structured(StructuredJsonError)
+}
+
+impl From<StructuredJsonError> for TemplateError {
+    fn from(value: StructuredJsonError) -> Self {
+        Self::Structured(value)
+    }
+}
+
+impl Error for TemplateError {}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBase(set, base) => write!(f, "{} has an invalid base '{}' (expected 'BANK/NUMBER' or '{}NAME')", set, base, TEMPLATE_PREFIX),
+            Self::BaseNotFound(set, base) => write!(f, "{} has a base '{}' which does not exist", set, base),
+            Self::CircularBase(chain) => write!(f, "Circular base: {}", chain.join(" -> ")),
+            Self::InvalidTemplate(name, e) => write!(f, "Template '{}' is not valid JSON: {}", name, e),
+            Self::Structured(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Templates(Vec<(String, Value)>);
+
+impl Templates {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    pub fn insert(&mut self, name: String, template: Value) {
+        if let Some(existing) = self.0.iter_mut().find(|(n, _)| *n == name) {
+            existing.1 = template;
+        } else {
+            self.0.push((name, template));
+        }
+    }
+
+    // templates apply to the JSON file or split folder they are found next to (or the current directory for STDIN/STDOUT)
+    pub fn path_for(file: &Option<String>) -> PathBuf {
+        let folder = match file {
+            Some(p) if Path::new(p).is_dir() => PathBuf::from(p),
+            Some(p) => Path::new(p).parent().map(|f| f.to_path_buf()).unwrap_or_default(),
+            None => PathBuf::new()
+        };
+        folder.join(TEMPLATES_FOLDER)
+    }
+
+    pub fn find(input: &Option<String>) -> Result<Self, io::Error> {
+        let folder = Self::path_for(input);
+        let mut templates = Self::default();
+        if folder.is_dir() {
+            let mut entries: Vec<PathBuf> = folder.read_dir()?.map(|e| e.map(|e| e.path())).collect::<Result<_, _>>()?;
+            entries.sort();
+            for path in entries {
+                if let Some((name, format)) = Self::template_name(&path) {
+                    let template = format.to_value(&fs::read_to_string(&path)?)
+                        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid template '{}': {}", path.display(), e)))?;
+                    templates.insert(name, template);
+                }
+            }
+        }
+        Ok(templates)
+    }
+
+    // writes each template which does not already exist (in any format) next to the output file, in the format of the
+    // output file, returning how many were written
+    pub fn save(&self, output: &Option<String>) -> Result<usize, io::Error> {
+        let folder = Self::path_for(output);
+        let format = FileFormat::for_file(output);
+        let existing: Vec<String> = if folder.is_dir() {
+            folder.read_dir()?.filter_map(|e| e.ok().and_then(|e| Self::template_name(&e.path())).map(|(name, _)| name)).collect()
+        } else {
+            Vec::new()
+        };
+        let mut count = 0;
+        for (name, template) in &self.0 {
+            if !existing.contains(name) {
+                fs::create_dir_all(&folder)?;
+                fs::write(folder.join(format!("{}.{}", name, format.extension())), format.from_json(&to_canonical_json(template).unwrap())?)?;
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    // the name and format of a template file, if it is one
+    fn template_name(path: &Path) -> Option<(String, FileFormat)> {
+        let format = FileFormat::from_path(path)?;
+        let file_name = path.file_name()?.to_str()?;
+        Some((format.strip_extension(file_name)?.to_string(), format))
+    }
+
+    fn extract_from(structured_json: &mut StructuredJson) -> Result<Self, TemplateError> {
+        let mut templates = Self::default();
+        if let Some(node) = structured_json.extract_optional(TEMPLATES_FOLDER)? {
+            let StructuredJson::NestedCollection(vec) = node else {
+                return Err(StructuredJsonError::ExpectedFolderButFoundFile.in_node(TEMPLATES_FOLDER).into());
+            };
+            for (name, s) in vec {
+                let json = s.to_single_json().map_err(|e| e.in_node(TEMPLATES_FOLDER))?;
+                let template = serde_json::from_str(&json).map_err(|e| TemplateError::InvalidTemplate(name.clone(), e))?;
+                templates.insert(name, template);
+            }
+        }
+        Ok(templates)
+    }
+
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::NestedCollection(self.0.iter()
+            .map(|(name, template)| (name.clone(), StructuredJson::SingleJson(to_canonical_json(template).unwrap())))
+            .collect())
+    }
+}
+
+// a live set which was declared with a base, as it was written before being resolved
+pub struct TemplatedSet {
+    pub bank: &'static str,
+    pub index: usize,
+    pub raw: Value
+}
+
+// the templating of a JSON file or split folder, which is kept so that it can be written back out unresolved
+#[derive(Default)]
+pub struct Templated {
+    pub sets: Vec<TemplatedSet>,
+    pub templates: Templates
+}
+
+impl Templated {
+    // resolves every live set with a base within the JSON value of an RD300NX
+    pub fn resolve(rds: &mut Value, templates: Templates) -> Result<Self, TemplateError> {
+        let mut sets = Vec::new();
+        for bank in BANKS {
+            if let Some(Value::Array(live_sets)) = rds.get(bank) {
+                for (index, live_set) in live_sets.iter().enumerate() {
+                    if live_set.get(BASE_FIELD).is_some() {
+                        sets.push(TemplatedSet { bank, index, raw: live_set.clone() });
+                    }
+                }
+            }
+        }
+        for set in &sets {
+            let resolved = resolve_live_set(set.raw.clone(), set_label(set.bank, set.index), rds, &templates, &mut Vec::new())?;
+            rds[set.bank][set.index] = resolved;
+        }
+        Ok(Self { sets, templates })
+    }
+
+    // resolves every live set with a base within a split folder, replacing them with their resolved JSON
+    pub fn resolve_structure(structured_json: &mut StructuredJson) -> Result<Self, TemplateError> {
+        let templates = Templates::extract_from(structured_json)?;
+        let StructuredJson::NestedCollection(nodes) = structured_json else {
+            return Ok(Self { sets: Vec::new(), templates });
+        };
+        let mut rds = Map::new();
+        let mut any_base = false;
+        for (bank_name, bank) in nodes.iter() {
+            let Some(bank_name) = BANKS.iter().find(|b| *b == bank_name) else { continue };
+            let StructuredJson::NestedCollection(items) = bank else { continue };
+            let mut live_sets = Vec::new();
+            for (name, item) in items {
+                let Some((number, _)) = slot_number(name) else { continue };
+                let value = match item {
+                    StructuredJson::SingleJson(json) => serde_json::from_str(json)
+                        .map_err(|e| StructuredJsonError::from(e).in_node(name).in_node(bank_name))?,
+                    StructuredJson::NestedCollection(_) => serde_json::to_value(LiveSet::from_structured_json(item.clone())
+                        .map_err(|e| e.in_node(name).in_node(bank_name))?).unwrap()
+                };
+                any_base |= value.get(BASE_FIELD).is_some();
+                live_sets.push((number, value));
+            }
+            live_sets.sort_by_key(|(n, _)| *n);
+            if live_sets.iter().enumerate().all(|(i, (n, _))| *n == i + 1) {
+                rds.insert(bank_name.to_string(), Value::Array(live_sets.into_iter().map(|(_, v)| v).collect()));
+            } // otherwise the slot numbers are invalid, which will be reported when loading the structure
+        }
+        if !any_base {
+            return Ok(Self { sets: Vec::new(), templates });
+        }
+        let mut rds = Value::Object(rds);
+        let templated = Self::resolve(&mut rds, templates)?;
+        for set in &templated.sets {
+            let resolved = to_canonical_json(&rds[set.bank][set.index]).unwrap();
+            if let Some(node) = bank_item(structured_json, set.bank, set.index) {
+                *node = StructuredJson::SingleJson(resolved);
+            }
+        }
+        Ok(templated)
+    }
+
+    // puts the live sets which had a base back into the JSON value of an RD300NX as they were written
+    pub fn unresolve(&self, rds: &mut Value) {
+        for set in &self.sets {
+            rds[set.bank][set.index] = set.raw.clone();
+        }
+    }
+
+    // puts the live sets which had a base back into a split structure as they were written (always as a single file),
+    // along with the templates themselves
+    pub fn unresolve_structure(&self, structured_json: &mut StructuredJson) {
+        for set in &self.sets {
+            if let Some(node) = bank_item(structured_json, set.bank, set.index) {
+                *node = StructuredJson::SingleJson(to_canonical_json(&set.raw).unwrap());
+            }
+        }
+        if !self.templates.is_empty() {
+            if let StructuredJson::NestedCollection(nodes) = structured_json {
+                nodes.push((TEMPLATES_FOLDER.to_string(), self.templates.to_structured_json()));
+            }
+        }
+    }
+
+    // factors the fields which the chosen (resolved) live sets have in common out into a new template, and replaces those
+    // live sets with just the fields which differ from the template
+    pub fn extract_template(&mut self, rds: &Value, sets: &SetSelection, name: String) {
+        let mut live_sets = Vec::new();
+        for (bank, index) in sets.sets() {
+            let mut live_set = rds[bank][*index].clone();
+            live_set.as_object_mut().map(|m| m.remove(METADATA_FIELD));
+            live_sets.push(live_set);
+        }
+        let template = most_common(&live_sets.iter().collect::<Vec<_>>());
+        for ((bank, index), live_set) in sets.sets().iter().zip(live_sets) {
+            let mut raw = Map::new();
+            raw.insert(BASE_FIELD.to_string(), Value::String(format!("{}{}", TEMPLATE_PREFIX, name)));
+            if let Some(metadata) = rds[bank][*index].get(METADATA_FIELD) {
+                raw.insert(METADATA_FIELD.to_string(), metadata.clone());
+            }
+            if let Value::Object(diff) = merge_diff(&template, &live_set) {
+                raw.extend(diff);
+            }
+            let raw = Value::Object(raw);
+            self.sets.retain(|s| s.bank != *bank || s.index != *index);
+            self.sets.push(TemplatedSet { bank, index: *index, raw });
+        }
+        self.templates.insert(name, template);
+    }
+}
+
+fn bank_item<'a>(structured_json: &'a mut StructuredJson, bank: &str, index: usize) -> Option<&'a mut StructuredJson> {
+    let StructuredJson::NestedCollection(nodes) = structured_json else { return None };
+    let (_, StructuredJson::NestedCollection(items)) = nodes.iter_mut().find(|(n, _)| n == bank)? else { return None };
+    items.iter_mut().find(|(n, _)| slot_number(n).map(|(number, _)| number) == Some(index + 1)).map(|(_, s)| s)
+}
+
+fn resolve_live_set(live_set: Value, label: String, rds: &mut Value, templates: &Templates, chain: &mut Vec<String>) -> Result<Value, TemplateError> {
+    let base = match live_set.get(BASE_FIELD) {
+        None => return Ok(live_set),
+        Some(Value::String(base)) => base.clone(),
+        Some(other) => return Err(TemplateError::InvalidBase(label, other.to_string()))
+    };
+    if chain.contains(&label) {
+        chain.push(label);
+        return Err(TemplateError::CircularBase(chain.clone()));
+    }
+    chain.push(label.clone());
+    let mut resolved = if let Some(name) = base.strip_prefix(TEMPLATE_PREFIX) {
+        let template = templates.get(name).ok_or_else(|| TemplateError::BaseNotFound(label.clone(), base.clone()))?;
+        resolve_live_set(template.clone(), base.clone(), rds, templates, chain)?
+    } else {
+        let (bank, index) = parse_set_label(&base).ok_or_else(|| TemplateError::InvalidBase(label.clone(), base.clone()))?;
+        let base_set = rds.get(bank).and_then(|b| b.get(index)).cloned()
+            .ok_or_else(|| TemplateError::BaseNotFound(label.clone(), base.clone()))?;
+        let resolved = resolve_live_set(base_set, set_label(bank, index), rds, templates, chain)?;
+        rds[bank][index] = resolved.clone();
+        resolved
+    };
+    chain.pop();
+    resolved.as_object_mut().map(|m| m.remove(METADATA_FIELD));
+    let mut overrides = live_set;
+    overrides.as_object_mut().map(|m| m.remove(BASE_FIELD));
+    json_patch::merge(&mut resolved, &overrides);
+    Ok(resolved)
+}
+
+// the value found in the most items, or for objects, the most common value of each field found in at least half of the items
+fn most_common(values: &[&Value]) -> Value {
+    if values.iter().all(|v| v.is_object()) {
+        let mut result = Map::new();
+        for value in values {
+            for key in value.as_object().unwrap().keys() {
+                if result.contains_key(key) {
+                    continue;
+                }
+                let fields: Vec<&Value> = values.iter().filter_map(|v| v.get(key)).collect();
+                if fields.len() * 2 >= values.len() {
+                    result.insert(key.clone(), most_common(&fields));
+                }
+            }
+        }
+        Value::Object(result)
+    } else {
+        let mut best = values[0];
+        let mut best_count = 0;
+        for value in values {
+            let count = values.iter().filter(|v| *v == value).count();
+            if count > best_count {
+                best = value;
+                best_count = count;
+            }
+        }
+        best.clone()
+    }
+}
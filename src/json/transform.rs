@@ -0,0 +1,280 @@
+// A transform applies the same edits to every live set which matches a filter, then validates each edited live set and
+// reports every setting which changed, eg. to lower the volume of every layer in live sets using the concert grand:
+//   --tone ConcertGrand volume-=6
+// Edits are written as NAME=VALUE, or NAME+=AMOUNT / NAME-=AMOUNT for numbers (which stop at the limits of their range):
+//   volume, chorus_send, reverb_send   applied to every layer (0-127)
+//   tempo                              the live set tempo (10-500)
+//   key_touch_velocity                 'real' or a fixed velocity (1-127)
+//   key_touch_curve                    super_light, light, medium, heavy or super_heavy
+//   tone=OLD:NEW                       replaces one tone with another, on any layer which has it selected
+
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use regex::Regex;
+use serde_json::Value;
+use strum::IntoEnumIterator;
+use validator::Validate;
+
+use crate::roland::live_set::LiveSet;
+use crate::roland::rd300nx::RD300NX;
+use crate::roland::tones::ToneNumber;
+use crate::roland::types::enums::{KeyTouchCurveType, KeyTouchVelocity, Layer};
+
+use super::selection::{bank_size, set_label, SetSelection, BANKS};
+use super::validation::error_paths;
+
+// every criteria which is set must match, so the default filter matches every live set
+#[derive(Default)]
+pub struct LiveSetFilter {
+    pub banks: Vec<&'static str>,
+    pub sets: Option<SetSelection>,
+    pub name: Option<Regex>,
+    pub tone: Option<ToneNumber>
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Adjustment {
+    Set(i32),
+    Add(i32)
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum Edit {
+    Volume(Adjustment),
+    ChorusSend(Adjustment),
+    ReverbSend(Adjustment),
+    Tempo(Adjustment),
+    KeyTouchVelocity(KeyTouchVelocity),
+    KeyTouchCurve(KeyTouchCurveType),
+    ReplaceTone(ToneNumber, ToneNumber)
+}
+
+// Every setting of a live set which was changed by a transform, as "path: old -> new"
+pub struct SetChanges {
+    pub bank: &'static str,
+    pub index: usize,
+    pub name: String,
+    pub changes: Vec<String>
+}
+
+#[derive(Debug)]
+pub enum TransformError {
+    FailedValidation(Vec<(String, Vec<String>)>)
+}
+
+impl Error for TransformError {}
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FailedValidation(sets) => {
+                let lines: Vec<String> = sets.iter().map(|(label, errors)| format!("{}: {}", label, errors.join("; "))).collect();
+                write!(f, "{} live sets failed validation after editing:\n{}", sets.len(), lines.join("\n"))
+            }
+        }
+    }
+}
+
+impl LiveSetFilter {
+    pub fn set_bank(&mut self, bank: &str) -> Result<(), String> {
+        let bank = BANKS.into_iter().find(|b| *b == bank).ok_or(format!("Invalid bank '{}' (expected one of: {})", bank, BANKS.join(", ")))?;
+        self.banks.push(bank);
+        Ok(())
+    }
+
+    pub fn set_name(&mut self, pattern: &str) -> Result<(), String> {
+        self.name = Some(Regex::new(pattern).map_err(|e| format!("Invalid name pattern '{}': {}", pattern, e))?);
+        Ok(())
+    }
+
+    pub fn matches(&self, bank: &str, index: usize, live_set: &LiveSet) -> bool {
+        if !self.banks.is_empty() && !self.banks.contains(&bank) {
+            return false;
+        }
+        if self.sets.as_ref().is_some_and(|s| !s.contains(bank, index)) {
+            return false;
+        }
+        if self.name.as_ref().is_some_and(|n| !n.is_match(live_set.name_string().trim())) {
+            return false;
+        }
+        match self.tone {
+            Some(tone) => live_set.layers.iter().any(|l| l.tone.tone_number == tone),
+            None => true
+        }
+    }
+}
+
+impl Adjustment {
+    fn apply(&self, value: i32, min: i32, max: i32) -> i32 {
+        match self {
+            Self::Set(v) => *v,
+            Self::Add(v) => value + v
+        }.clamp(min, max)
+    }
+}
+
+impl Edit {
+    pub fn apply(&self, live_set: &mut LiveSet) {
+        match self {
+            Self::Volume(a) => for l in live_set.layers.iter_mut() {
+                let volume = a.apply(l.internal.volume() as i32, 0, 127) as u8;
+                l.internal.set_volume(volume);
+            },
+            Self::ChorusSend(a) => for l in live_set.layers.iter_mut() {
+                l.internal.chorus = a.apply(l.internal.chorus as i32, 0, 127) as u8;
+            },
+            Self::ReverbSend(a) => for l in live_set.layers.iter_mut() {
+                l.internal.reverb = a.apply(l.internal.reverb as i32, 0, 127) as u8;
+            },
+            Self::Tempo(a) => {
+                let tempo = a.apply(live_set.common.live_set_tempo() as i32, 10, 500) as u16;
+                live_set.common.set_live_set_tempo(tempo);
+            },
+            Self::KeyTouchVelocity(v) => live_set.common.set_key_touch_velocity(*v),
+            Self::KeyTouchCurve(c) => live_set.common.set_key_touch_curve_type(*c),
+            Self::ReplaceTone(old, new) => for l in live_set.layers.iter_mut() {
+                if l.tone.tone_number == *old {
+                    l.tone.tone_number = *new;
+                    // the piano designer settings follow the tone, when it is one of the SuperNATURAL pianos
+                    if let Some(piano_tone) = new.as_piano_tone() {
+                        l.piano.tone_number = piano_tone;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Edit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, operator, value) = if let Some((name, value)) = s.split_once("+=") {
+            (name, "+=", value)
+        } else if let Some((name, value)) = s.split_once("-=") {
+            (name, "-=", value)
+        } else if let Some((name, value)) = s.split_once('=') {
+            (name, "=", value)
+        } else {
+            return Err(format!("Edit should be NAME=VALUE, NAME+=AMOUNT or NAME-=AMOUNT, but found '{}'", s));
+        };
+        let numeric = |edit: fn(Adjustment) -> Self| {
+            let number: i32 = value.trim().parse().map_err(|_| format!("Expected a number for '{}', but found '{}'", name, value))?;
+            Ok(edit(match operator {
+                "+=" => Adjustment::Add(number),
+                "-=" => Adjustment::Add(-number),
+                _ => Adjustment::Set(number)
+            }))
+        };
+        if operator != "=" && ["key_touch_velocity", "key_touch_curve", "tone"].contains(&name.trim()) {
+            return Err(format!("'{}' can only be set (with '='), not adjusted", name));
+        }
+        match name.trim() {
+            "volume" => numeric(Self::Volume),
+            "chorus_send" => numeric(Self::ChorusSend),
+            "reverb_send" => numeric(Self::ReverbSend),
+            "tempo" => numeric(Self::Tempo),
+            "key_touch_velocity" => {
+                if value.eq_ignore_ascii_case("real") {
+                    Ok(Self::KeyTouchVelocity(KeyTouchVelocity::Real))
+                } else {
+                    let fixed: u8 = value.parse().map_err(|_| format!("Key touch velocity should be 'real' or a number (1-127), but found '{}'", value))?;
+                    Ok(Self::KeyTouchVelocity(KeyTouchVelocity::Fixed(fixed)))
+                }
+            },
+            "key_touch_curve" => {
+                let simplified: String = value.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+                KeyTouchCurveType::iter().find(|c| format!("{:?}", c).eq_ignore_ascii_case(&simplified)).map(Self::KeyTouchCurve)
+                    .ok_or_else(|| format!("Key touch curve should be one of super_light, light, medium, heavy or super_heavy, but found '{}'", value))
+            },
+            "tone" => {
+                let (old, new) = value.split_once(':').ok_or_else(|| format!("Tone replacement should be OLD:NEW, but found '{}'", value))?;
+                Ok(Self::ReplaceTone(old.trim().parse()?, new.trim().parse()?))
+            },
+            _ => Err(format!("Unknown setting '{}' (expected one of: volume, chorus_send, reverb_send, tempo, key_touch_velocity, key_touch_curve, tone)", name))
+        }
+    }
+}
+
+// calls `f` on every live set which matches the filter, returning the changes made to each live set which changed
+// (if any edited live set fails validation, the error lists every problem and the RD300NX should not be used)
+pub fn map<F: FnMut(&mut LiveSet)>(rds: &mut RD300NX, filter: &LiveSetFilter, mut f: F) -> Result<Vec<SetChanges>, TransformError> {
+    let mut changed = Vec::new();
+    let mut errors = Vec::new();
+    for bank in BANKS {
+        for index in 0..bank_size(bank) {
+            let live_set = rds.live_set_mut(bank, index).unwrap(); // safe because index is within the bank size
+            if !filter.matches(bank, index, live_set) {
+                continue;
+            }
+            let before = serde_json::to_value(&*live_set).unwrap();
+            f(live_set);
+            let after = serde_json::to_value(&*live_set).unwrap();
+            let mut changes = Vec::new();
+            value_changes(&mut changes, "", &before, &after);
+            if changes.is_empty() {
+                continue;
+            }
+            if let Err(e) = live_set.validate() {
+                errors.push((set_label(bank, index), error_paths(&e)));
+            }
+            changed.push(SetChanges {
+                bank,
+                index,
+                name: live_set.name_string().trim().to_string(),
+                changes
+            });
+        }
+    }
+    if errors.is_empty() {
+        Ok(changed)
+    } else {
+        Err(TransformError::FailedValidation(errors))
+    }
+}
+
+// applies each edit in order to every live set which matches the filter
+pub fn transform(rds: &mut RD300NX, filter: &LiveSetFilter, edits: &[Edit]) -> Result<Vec<SetChanges>, TransformError> {
+    map(rds, filter, |live_set| {
+        for edit in edits {
+            edit.apply(live_set);
+        }
+    })
+}
+
+fn value_changes(changes: &mut Vec<String>, path: &str, before: &Value, after: &Value) {
+    match (before, after) {
+        (Value::Object(b), Value::Object(a)) => {
+            for (key, b_value) in b {
+                let a_value = a.get(key).unwrap_or(&Value::Null);
+                value_changes(changes, &child_path(path, key), b_value, a_value);
+            }
+            for (key, a_value) in a {
+                if !b.contains_key(key) {
+                    value_changes(changes, &child_path(path, key), &Value::Null, a_value);
+                }
+            }
+        },
+        (Value::Array(b), Value::Array(a)) if b.len() == a.len() => {
+            for (i, (b_value, a_value)) in b.iter().zip(a).enumerate() {
+                let key = match path {
+                    "layers" => Layer::from(i as u8).to_string(),
+                    _ => i.to_string()
+                };
+                value_changes(changes, &child_path(path, &key), b_value, a_value);
+            }
+        },
+        _ if before != after => changes.push(format!("{}: {} -> {}", path, before, after)),
+        _ => {}
+    }
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
@@ -0,0 +1,303 @@
+// Micro tuning can be imported from (and exported to) the Scala tuning formats (https://www.huygens-fokker.org/scala/):
+// a scale file (.scl) lists the pitch of each degree of the scale, and an optional keyboard mapping file (.kbm) chooses
+// which MIDI notes play which degrees, and the frequency of a reference note. Without a keyboard mapping, the scale is
+// mapped linearly from C4 (middle C), with A4 at 440Hz.
+// Alternatively, micro tuning can be set to one of the temperaments which the keyboard can otherwise only apply to every
+// live set (in the system settings), from any key.
+// The micro tuning of each note is its difference (in cents) from equal temperament with A4 at 440Hz, which the RD300NX
+// limits to +/-50 cents, so any notes further from equal temperament than that are clamped.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use strum::IntoEnumIterator;
+
+use crate::roland::layers::PianoLayer;
+use crate::roland::types::enums::Temperament;
+use crate::roland::types::notes::{KeyNote, MidiNote};
+
+pub const MICRO_TUNE_LIMIT: f64 = 50.0;
+const NOTES: usize = 128;
+const A4: i32 = 69;
+const C4: u8 = 60;
+const PURE_FIFTH: f64 = 701.955;
+const SYNTONIC_COMMA: f64 = 21.506;
+const PYTHAGOREAN_COMMA: f64 = 23.460;
+const SCHISMA: f64 = 1.954;
+
+pub enum TuningSource {
+    Scala(ScalaScale, KeyboardMapping),
+    Temperament(Temperament, KeyNote)
+}
+
+impl TuningSource {
+    pub fn description(&self) -> String {
+        match self {
+            Self::Scala(scale, _) => scale.description.clone(),
+            Self::Temperament(temperament, key) => format!("{:?} in {:?}", temperament, key)
+        }
+    }
+
+    fn micro_tune_offsets(&self) -> Result<Vec<Option<f64>>, String> {
+        match self {
+            Self::Scala(scale, mapping) => micro_tune_offsets(scale, mapping),
+            Self::Temperament(temperament, key) => {
+                let offsets = temperament_offsets(*temperament, *key);
+                Ok((0..NOTES).map(|note| Some(offsets[note % 12])).collect())
+            }
+        }
+    }
+}
+
+pub struct ScalaScale {
+    pub description: String,
+    pub pitches: Vec<f64> // in cents, for degrees 1 to N, where the last is the period (usually an octave)
+}
+
+impl ScalaScale {
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        Self::parse(&fs::read_to_string(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid scale '{}': {}", path.display(), e)))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().filter(|l| !l.starts_with('!'));
+        let description = lines.next().ok_or("missing description")?.trim().to_string();
+        let count: usize = first_word(lines.next().ok_or("missing number of notes")?).parse().map_err(|_| "invalid number of notes")?;
+        let pitches = lines.take(count).map(parse_pitch).collect::<Result<Vec<f64>, String>>()?;
+        if pitches.len() != count {
+            return Err(format!("expected {} notes but found {}", count, pitches.len()));
+        }
+        if count == 0 {
+            return Err("a scale must have at least 1 note".into());
+        }
+        Ok(Self {
+            description,
+            pitches
+        })
+    }
+
+    pub fn to_scl(&self) -> String {
+        let mut scl = format!("! Written by roland-rds\n!\n{}\n {}\n!\n", self.description, self.pitches.len());
+        for pitch in &self.pitches {
+            scl.push_str(&format!(" {:.1}\n", pitch));
+        }
+        scl
+    }
+
+    // the pitch in cents of any degree, where degree 0 is the unison and degrees beyond the scale repeat each period
+    fn cents(&self, degree: i32) -> f64 {
+        let size = self.pitches.len() as i32;
+        let period = *self.pitches.last().unwrap(); // safe because a scale has at least 1 note
+        let (repeats, step) = (degree.div_euclid(size), degree.rem_euclid(size));
+        let pitch = if step == 0 { 0.0 } else { self.pitches[step as usize - 1] };
+        repeats as f64 * period + pitch
+    }
+}
+
+pub struct KeyboardMapping {
+    pub first_note: u8,
+    pub last_note: u8,
+    pub middle_note: u8,
+    pub reference_note: u8,
+    pub reference_frequency: f64,
+    pub octave_degree: usize,
+    pub mapping: Vec<Option<usize>> // empty for a linear mapping, otherwise the degree of each key in the pattern (or None if it is not mapped)
+}
+
+impl Default for KeyboardMapping {
+    fn default() -> Self {
+        Self {
+            first_note: 0,
+            last_note: NOTES as u8 - 1,
+            middle_note: C4,
+            reference_note: A4 as u8,
+            reference_frequency: 440.0,
+            octave_degree: 0,
+            mapping: Vec::new()
+        }
+    }
+}
+
+impl KeyboardMapping {
+    pub fn load(path: &Path) -> Result<Self, io::Error> {
+        Self::parse(&fs::read_to_string(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("Invalid keyboard mapping '{}': {}", path.display(), e)))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut values = text.lines().filter(|l| !l.starts_with('!') && !l.trim().is_empty()).map(first_word);
+        let mut next = |name: &str| values.next().ok_or(format!("missing {}", name));
+        let note = |s: &str, name: &str| s.parse::<u8>().ok().filter(|n| (*n as usize) < NOTES).ok_or(format!("invalid {} ({})", name, s));
+        let size: usize = next("map size")?.parse().map_err(|_| "invalid map size")?;
+        let first_note = note(next("first note")?, "first note")?;
+        let last_note = note(next("last note")?, "last note")?;
+        let middle_note = note(next("middle note")?, "middle note")?;
+        let reference_note = note(next("reference note")?, "reference note")?;
+        let reference_frequency: f64 = next("reference frequency")?.parse().ok().filter(|f: &f64| *f > 0.0).ok_or("invalid reference frequency")?;
+        let octave_degree: usize = next("octave degree")?.parse().map_err(|_| "invalid octave degree")?;
+        let mut mapping = Vec::new();
+        for _ in 0..size {
+            let key = next("mapping")?;
+            mapping.push(if key == "x" { None } else { Some(key.parse().map_err(|_| format!("invalid mapping ({})", key))?) });
+        }
+        Ok(Self {
+            first_note,
+            last_note,
+            middle_note,
+            reference_note,
+            reference_frequency,
+            octave_degree,
+            mapping
+        })
+    }
+
+    pub fn to_kbm(&self) -> String {
+        let mut kbm = String::from("! Written by roland-rds\n");
+        kbm.push_str(&format!("! Map size\n{}\n", self.mapping.len()));
+        kbm.push_str(&format!("! First MIDI note to retune\n{}\n", self.first_note));
+        kbm.push_str(&format!("! Last MIDI note to retune\n{}\n", self.last_note));
+        kbm.push_str(&format!("! Middle note where the first entry of the mapping is mapped to\n{}\n", self.middle_note));
+        kbm.push_str(&format!("! Reference note for which frequency is given\n{}\n", self.reference_note));
+        kbm.push_str(&format!("! Frequency to tune the above note to\n{:.6}\n", self.reference_frequency));
+        kbm.push_str(&format!("! Scale degree to consider as formal octave\n{}\n", self.octave_degree));
+        kbm.push_str("! Mapping\n");
+        for key in &self.mapping {
+            match key {
+                Some(degree) => kbm.push_str(&format!("{}\n", degree)),
+                None => kbm.push_str("x\n")
+            }
+        }
+        kbm
+    }
+
+    // the scale degree played by a MIDI note, if it is mapped
+    fn degree(&self, note: u8, scale: &ScalaScale) -> Option<i32> {
+        if note < self.first_note || note > self.last_note {
+            return None;
+        }
+        let offset = note as i32 - self.middle_note as i32;
+        if self.mapping.is_empty() {
+            return Some(offset);
+        }
+        let size = self.mapping.len() as i32;
+        let octave_degree = if self.octave_degree == 0 { scale.pitches.len() } else { self.octave_degree };
+        let key = self.mapping[offset.rem_euclid(size) as usize]?;
+        Some(key as i32 + offset.div_euclid(size) * octave_degree as i32)
+    }
+}
+
+// the difference of each MIDI note from equal temperament (in cents), or None if the note is not mapped
+pub fn micro_tune_offsets(scale: &ScalaScale, mapping: &KeyboardMapping) -> Result<Vec<Option<f64>>, String> {
+    let reference = mapping.degree(mapping.reference_note, scale)
+        .ok_or(format!("the reference note ({:?}) is not mapped to a scale degree", MidiNote::from(mapping.reference_note)))?;
+    let shift = 1200.0 * (mapping.reference_frequency / 440.0).log2() - scale.cents(reference) + 100.0 * (A4 - mapping.reference_note as i32) as f64;
+    Ok((0..NOTES as u8).map(|note| {
+        mapping.degree(note, scale).map(|degree| scale.cents(degree) + shift - 100.0 * (note as i32 - mapping.reference_note as i32) as f64)
+    }).collect())
+}
+
+// the difference of each note (from C to B) from equal temperament (in cents), where the tonic of the key is not detuned
+pub fn temperament_offsets(temperament: Temperament, key: KeyNote) -> [f64; 12] {
+    let cents = match temperament {
+        Temperament::Equal => [0.0, 100.0, 200.0, 300.0, 400.0, 500.0, 600.0, 700.0, 800.0, 900.0, 1000.0, 1100.0],
+        Temperament::JustMajor => ratios([(1, 1), (16, 15), (9, 8), (6, 5), (5, 4), (4, 3), (45, 32), (3, 2), (8, 5), (5, 3), (16, 9), (15, 8)]),
+        Temperament::JustMinor => ratios([(1, 1), (16, 15), (9, 8), (6, 5), (5, 4), (4, 3), (45, 32), (3, 2), (8, 5), (5, 3), (9, 5), (15, 8)]),
+        Temperament::Pythagorean => circle_of_fifths(&[]),
+        Temperament::MeanTone => circle_of_fifths(&[0, 1, 2, 3, 4, 5, 6, 7, 9, 10, 11].map(|i| (i, SYNTONIC_COMMA / 4.0))), // quarter comma
+        Temperament::Kirnberger => circle_of_fifths(&[(0, SYNTONIC_COMMA / 4.0), (1, SYNTONIC_COMMA / 4.0), (2, SYNTONIC_COMMA / 4.0), (3, SYNTONIC_COMMA / 4.0), (6, SCHISMA)]), // III
+        Temperament::Werckmeister => circle_of_fifths(&[(0, PYTHAGOREAN_COMMA / 4.0), (1, PYTHAGOREAN_COMMA / 4.0), (2, PYTHAGOREAN_COMMA / 4.0), (5, PYTHAGOREAN_COMMA / 4.0)]), // III
+        Temperament::Arabic => [0.0, 100.0, 200.0, 300.0, 350.0, 500.0, 600.0, 700.0, 800.0, 900.0, 1000.0, 1050.0] // E and B are a quarter tone flat
+    };
+    let tonic: u8 = key.into();
+    let mut offsets = [0.0; 12];
+    for (note, offset) in offsets.iter_mut().enumerate() {
+        let degree = (note + 12 - tonic as usize) % 12;
+        *offset = cents[degree] - 100.0 * degree as f64;
+    }
+    offsets
+}
+
+fn ratios(ratios: [(u32, u32); 12]) -> [f64; 12] {
+    ratios.map(|(numerator, denominator)| 1200.0 * (numerator as f64 / denominator as f64).log2())
+}
+
+// the pitch of each note (from C), found by stacking fifths around the circle from C (C-G, G-D, ... F-C), where the
+// given fifths are narrowed by the given amounts, and the fifth from G# to Eb is whatever is left to close the circle
+fn circle_of_fifths(narrowed: &[(usize, f64)]) -> [f64; 12] {
+    let mut fifths = [PURE_FIFTH; 12];
+    for (i, amount) in narrowed {
+        fifths[*i] -= amount;
+    }
+    fifths[8] = 0.0;
+    fifths[8] = 8400.0 - fifths.iter().sum::<f64>();
+    let mut cents = [0.0; 12];
+    let mut pitch = 0.0;
+    for (i, fifth) in fifths.iter().take(11).enumerate() {
+        pitch += fifth;
+        cents[(7 * (i + 1)) % 12] = pitch % 1200.0;
+    }
+    cents
+}
+
+// replaces the micro tuning of a piano layer, returning warnings for any notes which were clamped to the supported range
+pub fn import_tuning(piano: &mut PianoLayer, source: &TuningSource) -> Result<Vec<String>, String> {
+    let offsets = source.micro_tune_offsets()?;
+    let mut clamped = Vec::new();
+    let mut cents = [0.0; NOTES];
+    for (note, offset) in MidiNote::iter().zip(offsets) {
+        let offset = offset.unwrap_or_default(); // notes which are not mapped are left in equal temperament
+        if offset.abs() > MICRO_TUNE_LIMIT + 0.05 {
+            clamped.push(format!("{:?} ({:+.1})", note, offset));
+        }
+        let note: u8 = note.into();
+        cents[note as usize] = offset.clamp(-MICRO_TUNE_LIMIT, MICRO_TUNE_LIMIT);
+    }
+    piano.set_micro_tune_cents(&cents);
+    let mut warnings = Vec::new();
+    if !clamped.is_empty() {
+        warnings.push(format!("{} notes are more than {} cents from equal temperament, so were clamped: {}", clamped.len(), MICRO_TUNE_LIMIT, clamped.join(", ")));
+    }
+    Ok(warnings)
+}
+
+// the micro tuning of a piano layer as a 12 note scale from C4 (if every octave is tuned the same), otherwise as a scale
+// with a degree for every MIDI note, along with the keyboard mapping which reproduces it exactly
+pub fn export_tuning(piano: &PianoLayer, description: String) -> (ScalaScale, KeyboardMapping) {
+    let cents = piano.micro_tune_cents();
+    let repeats_each_octave = (0..NOTES - 12).all(|n| (cents[n] - cents[n + 12]).abs() < 0.05);
+    let (middle_note, size) = if repeats_each_octave { (C4, 12) } else { (0, NOTES) };
+    let base = cents[middle_note as usize];
+    let mut pitches: Vec<f64> = (1..size).map(|degree| 100.0 * degree as f64 + cents[middle_note as usize + degree] - base).collect();
+    pitches.push(100.0 * size as f64); // the period is a pure octave (or the note past the end of the keyboard, in equal temperament)
+    let scale = ScalaScale {
+        description,
+        pitches
+    };
+    let mapping = KeyboardMapping {
+        middle_note,
+        reference_frequency: 440.0 * 2f64.powf(cents[A4 as usize] / 1200.0),
+        octave_degree: size,
+        ..Default::default()
+    };
+    (scale, mapping)
+}
+
+fn first_word(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or_default()
+}
+
+// a pitch is either in cents (containing a '.') or a ratio (eg. '3/2' or '2')
+fn parse_pitch(line: &str) -> Result<f64, String> {
+    let pitch = first_word(line);
+    let invalid = || format!("invalid pitch ({})", pitch);
+    if pitch.contains('.') {
+        return pitch.parse().map_err(|_| invalid());
+    }
+    let (numerator, denominator) = pitch.split_once('/').unwrap_or((pitch, "1"));
+    let numerator: f64 = numerator.parse().map_err(|_| invalid())?;
+    let denominator: f64 = denominator.parse().map_err(|_| invalid())?;
+    if numerator <= 0.0 || denominator <= 0.0 {
+        return Err(invalid());
+    }
+    Ok(1200.0 * (numerator / denominator).log2())
+}
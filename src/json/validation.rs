@@ -2,7 +2,7 @@ use std::{borrow::Cow, collections::HashMap};
 
 use serde::Serialize;
 use strum::IntoEnumIterator;
-use validator::{ValidationError, Validate, ValidationErrors};
+use validator::{ValidationError, Validate, ValidationErrors, ValidationErrorsKind};
 use std::hash::Hash;
 use std::cmp::Eq;
 
@@ -117,6 +117,42 @@ pub fn merge_all_fixed(parent: Result<(), ValidationErrors>, field: &'static str
     ValidationErrors::merge_all(parent, field, results)
 }
 
+// flattens nested validation errors into one line per error, prefixed by the path to the field (eg. `common.name[2]`)
+pub fn error_paths(errors: &ValidationErrors) -> Vec<String> {
+    let mut lines = Vec::new();
+    add_error_paths(&mut lines, "", errors);
+    lines
+}
+
+fn add_error_paths(lines: &mut Vec<String>, prefix: &str, errors: &ValidationErrors) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by_key(|(field, _)| *field);
+    for (field, kind) in fields {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(field_errors) => {
+                for e in field_errors {
+                    let mut params: Vec<String> = e.params.iter().filter(|(k, _)| *k != "value").map(|(k, v)| format!("{}={}", k, v)).collect();
+                    params.sort();
+                    if params.is_empty() {
+                        lines.push(format!("{}: {}", path, e.code));
+                    } else {
+                        lines.push(format!("{}: {} ({})", path, e.code, params.join(", ")));
+                    }
+                }
+            },
+            ValidationErrorsKind::Struct(inner) => add_error_paths(lines, &path, inner),
+            ValidationErrorsKind::List(items) => {
+                let mut items: Vec<_> = items.iter().collect();
+                items.sort_by_key(|(i, _)| **i);
+                for (i, inner) in items {
+                    add_error_paths(lines, &format!("{}[{}]", path, i), inner);
+                }
+            }
+        }
+    }
+}
+
 pub trait LayerRanges {
     fn is_enabled(&self) -> bool;
     fn get_range_upper(&self) -> PianoKey;
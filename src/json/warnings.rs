@@ -4,91 +4,177 @@
 // a warning only indicates that although valid, the configuration provided may not operate as expected
 // in some scenarios.
 
+use std::fmt::Display;
 use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 
-use crate::roland::{live_set::{LiveSet, mfx::Mfx, reverb::Reverb, chorus::Chorus}, layers::InternalLayer, types::{enums::{Layer, PedalFunction, ButtonFunction}, metadata::BySet}};
+use crate::bytes::Bytes;
+use crate::roland::{live_set::{LiveSet, mfx::Mfx, reverb::Reverb, chorus::Chorus}, layers::InternalLayer, system::favorites::Favorites, types::{enums::{Layer, PedalFunction, ButtonFunction, PatchCategory}, metadata::BySet}};
 
 use super::validation::LayerRanges;
 
 pub trait Warnings {
-    fn warnings(&self) -> Vec<String>;
+    fn warnings(&self) -> Vec<Warning>;
 }
 
-pub fn split_switch_warning<'a, L: LayerRanges + 'a, I: Iterator<Item = &'a L>>(name: &str, enabled: bool, layers: I) -> Option<String> {
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Hash, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningRule {
+    SplitSwitch,
+    MfxState,
+    ToneRemain,
+    DuplicateName,
+    IdenticalLiveSet,
+    FavoriteUnused
+}
+
+#[derive(Debug)]
+pub struct Warning {
+    pub rule: WarningRule,
+    pub message: String
+}
+
+impl Warning {
+    pub fn new(rule: WarningRule, message: String) -> Self {
+        Self {
+            rule,
+            message
+        }
+    }
+
+    pub fn prefixed(self, prefix: &str) -> Self {
+        Self {
+            rule: self.rule,
+            message: format!("{}{}", prefix, self.message)
+        }
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+pub fn split_switch_warning<'a, L: LayerRanges + 'a, I: Iterator<Item = &'a L>>(name: &str, enabled: bool, layers: I) -> Option<Warning> {
     if !enabled {
         let layers: Vec<usize> = layers.enumerate()
             .filter(|(_, l)| l.is_enabled() && !l.uses_full_range())
             .map(|(i, _)| i).collect();
         if layers.len() > 0 {
-            return Some(format!("{} layers {:?} have non-full ranges, but split switch is OFF", name, layers));
+            return Some(Warning::new(WarningRule::SplitSwitch, format!("{} layers {:?} have non-full ranges, but split switch is OFF", name, layers)));
         }
     }
     None
 }
 
-pub fn tone_remain_warnings<const N: usize>(required: &BySet<N>, name: &str, live_sets: &Box<[LiveSet; N]>, fc1_from_system: Option<PedalFunction>, fc2_from_system: Option<PedalFunction>) -> Vec<String> {
-    let mut warnings = Vec::new();
+pub struct ToneRemainIssue {
+    pub reason: String, // what changes between the live sets
+    pub effect: String, // what happens to notes held across the change
+    pub suggestion: String // the smallest change to the next live set which avoids it
+}
+
+impl ToneRemainIssue {
+    pub fn new(reason: String, effect: String, suggestion: String) -> Self {
+        Self {
+            reason,
+            effect,
+            suggestion
+        }
+    }
+}
+
+pub struct ToneRemainTransition {
+    pub bank: &'static str,
+    pub from_number: usize, // 1-indexed, the transition is always to the following live set
+    pub from_name: String,
+    pub to_name: String,
+    pub issues: Vec<ToneRemainIssue>
+}
+
+impl ToneRemainTransition {
+    pub fn is_seamless(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn set_before(&self) -> String {
+        format!("{} #{}", self.bank, self.from_number)
+    }
+
+    pub fn set_after(&self) -> String {
+        format!("{} #{}", self.bank, self.from_number + 1)
+    }
+
+    pub fn warnings(&self) -> Vec<Warning> {
+        self.issues.iter()
+            .map(|issue| Warning::new(WarningRule::ToneRemain, format!("{} #{}-#{}: Tone remain may malfunction because {}", self.bank, self.from_number, self.from_number + 1, issue.reason)))
+            .collect()
+    }
+}
+
+pub fn tone_remain_transitions<const N: usize>(required: &BySet<N>, bank: &'static str, live_sets: &[LiveSet; N], fc1_from_system: Option<PedalFunction>, fc2_from_system: Option<PedalFunction>) -> Vec<ToneRemainTransition> {
+    let mut transitions = Vec::new();
     for i in 0..(live_sets.len() - 1) {
         if required.includes(i) {
-            let reasons = individual_tone_remain_warnings(
-                &live_sets[i], 
-                &live_sets[i+1],
-                fc1_from_system,
-                fc2_from_system
-            );
-            for reason in reasons {
-                warnings.push(format!("{} #{}-#{}: Tone remain may malfunction because {}", name, i+1, i+2, reason));
-            }
+            transitions.push(ToneRemainTransition {
+                bank,
+                from_number: i + 1,
+                from_name: live_sets[i].name_string().trim().to_string(),
+                to_name: live_sets[i+1].name_string().trim().to_string(),
+                issues: individual_tone_remain_issues(
+                    &live_sets[i], 
+                    &live_sets[i+1],
+                    fc1_from_system,
+                    fc2_from_system
+                )
+            });
         }
     }
-    warnings
+    transitions
 }
 
-fn individual_tone_remain_warnings(a: &LiveSet, b: &LiveSet, fc1_from_system: Option<PedalFunction>, fc2_from_system: Option<PedalFunction>) -> Vec<String> {
-    let mut reasons = Vec::new();
-    if let Some(reason) = Mfx::tone_remain_warning(
+fn individual_tone_remain_issues(a: &LiveSet, b: &LiveSet, fc1_from_system: Option<PedalFunction>, fc2_from_system: Option<PedalFunction>) -> Vec<ToneRemainIssue> {
+    let mut issues = Vec::new();
+    if let Some(issue) = Mfx::tone_remain_issue(
         &a.mfx,
         &b.mfx,
         &a.layers[0].internal.active()
     ) {
-        reasons.push(reason);
+        issues.push(issue);
     }
-    if let Some(reason) = Reverb::tone_remain_warning(
+    if let Some(issue) = Reverb::tone_remain_issue(
         &a.reverb,
         &b.reverb,
         a.layers.iter().map(|l| l.internal.reverb).max().unwrap(),
         b.layers.iter().map(|l| l.internal.reverb).max().unwrap()
     ) {
-        reasons.push(reason);
+        issues.push(issue);
     }
-    if let Some(reason) = Chorus::tone_remain_warning(
+    if let Some(issue) = Chorus::tone_remain_issue(
         &a.chorus,
         &b.chorus,
         a.layers.iter().map(|l| l.internal.chorus).max().unwrap(),
         b.layers.iter().map(|l| l.internal.chorus).max().unwrap()
     ) {
-        reasons.push(reason);
+        issues.push(issue);
     }
-    let layer_names: Vec<_> = Layer::iter().collect();
-    for i in 0..a.layers.len() {
-        if let Some(reason) = InternalLayer::tone_remain_warning(
-            &layer_names[i],
+    for (i, layer) in Layer::iter().enumerate().take(a.layers.len()) {
+        issues.append(&mut InternalLayer::tone_remain_issues(
+            &layer,
             &a.layers[i].internal, 
             &b.layers[i].internal,
             !a.chorus.chorus_type.is_off(),
-            a.reverb.reverb_type.is_off(),
+            !a.reverb.reverb_type.is_off(),
             &fc1_from_system.unwrap_or(a.common.fc1_assign),
             &fc1_from_system.unwrap_or(b.common.fc1_assign),
             &fc2_from_system.unwrap_or(a.common.fc2_assign),
             &fc2_from_system.unwrap_or(b.common.fc2_assign)
-        ) {
-            reasons.push(reason);
-        }
+        ));
     }
-    reasons
+    issues
 }
 
-pub fn mfx_state_warnings(ls: &LiveSet, s1_from_system: &Option<ButtonFunction>, s2_from_system: &Option<ButtonFunction>) -> Vec<String> {
+pub fn mfx_state_warnings(ls: &LiveSet, s1_from_system: &Option<ButtonFunction>, s2_from_system: &Option<ButtonFunction>) -> Vec<Warning> {
     let mut warnings = Vec::new();
     if let Some(warning) = individual_mfx_state_warning("S1", s1_from_system.as_ref().unwrap_or(&ls.common.s1_assign), ls.common.s1_state, &ls.mfx) {
         warnings.push(warning);
@@ -99,11 +185,88 @@ pub fn mfx_state_warnings(ls: &LiveSet, s1_from_system: &Option<ButtonFunction>,
     warnings
 }
 
-fn individual_mfx_state_warning(s_name: &str, s_assign: &ButtonFunction, s_state: bool, mfx: &Mfx) -> Option<String> {
+fn individual_mfx_state_warning(s_name: &str, s_assign: &ButtonFunction, s_state: bool, mfx: &Mfx) -> Option<Warning> {
     // When s1/s2 is assigned to MfxSwitch, s1/s2_state is ignored and starting state is mfx.enable
     if *s_assign == ButtonFunction::Mfx1Switch && mfx.enable != s_state {
-        Some(format!("{} is set to {:?}, but state ({}) doesn't match Mfx.Enable ({})", s_name, s_assign, s_state, mfx.enable))
+        Some(Warning::new(WarningRule::MfxState, format!("{} is set to {:?}, but state ({}) doesn't match Mfx.Enable ({})", s_name, s_assign, s_state, mfx.enable)))
     } else {
         None
     }
-}
\ No newline at end of file
+}
+
+pub fn duplicate_name_warnings(name: &str, live_sets: &[LiveSet]) -> Vec<Warning> {
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (i, ls) in live_sets.iter().enumerate() {
+        if ls.has_blank_name() || ls.has_default_name() {
+            continue; // unused live sets are expected to share a name
+        }
+        let trimmed = ls.name_string().trim().to_string();
+        match groups.iter_mut().find(|(n, _)| *n == trimmed) {
+            Some((_, numbers)) => numbers.push(i + 1),
+            None => groups.push((trimmed, vec![i + 1]))
+        }
+    }
+    groups.into_iter()
+        .filter(|(_, numbers)| numbers.len() > 1)
+        .map(|(ls_name, numbers)| Warning::new(WarningRule::DuplicateName, format!("{} {}: Live sets share the name '{}'", name, set_numbers(&numbers), ls_name)))
+        .collect()
+}
+
+pub fn identical_live_set_warnings(user_sets: &[LiveSet], piano: &[LiveSet], e_piano: &[LiveSet]) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    let others: Vec<(String, Box<[u8; LiveSet::BYTE_SIZE]>)> = piano.iter().enumerate().map(|(i, ls)| (format!("Piano #{}", i + 1), ls))
+        .chain(e_piano.iter().enumerate().map(|(i, ls)| (format!("EPiano #{}", i + 1), ls)))
+        .filter_map(|(n, ls)| ls.to_bytes().ok().map(|b| (n, b)))
+        .collect();
+    let mut groups: Vec<(Box<[u8; LiveSet::BYTE_SIZE]>, Vec<usize>)> = Vec::new();
+    for (i, ls) in user_sets.iter().enumerate() {
+        if ls.has_blank_name() || ls.has_default_name() {
+            continue; // unused live sets are expected to be identical
+        }
+        if let Ok(bytes) = ls.to_bytes() {
+            match groups.iter_mut().find(|(b, _)| *b == bytes) {
+                Some((_, numbers)) => numbers.push(i + 1),
+                None => groups.push((bytes, vec![i + 1]))
+            }
+        }
+    }
+    for (bytes, numbers) in groups {
+        let matching_others: Vec<&str> = others.iter().filter(|(_, b)| *b == bytes).map(|(n, _)| n.as_str()).collect();
+        if numbers.len() > 1 {
+            warnings.push(Warning::new(WarningRule::IdenticalLiveSet, format!("User {}: Live sets are identical", set_numbers(&numbers))));
+        }
+        if !matching_others.is_empty() {
+            warnings.push(Warning::new(WarningRule::IdenticalLiveSet, format!("User {}: Live set is identical to {}", set_numbers(&numbers), matching_others.join(", "))));
+        }
+    }
+    warnings
+}
+
+pub fn favorite_warnings(favorites: &Favorites, user_sets: &[LiveSet]) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    for (bank, letter) in favorites.all_banks().into_iter().zip(Favorites::BANK_LETTERS) {
+        for (i, favorite) in bank.used_favorites().iter().enumerate() {
+            if favorite.category() != PatchCategory::User {
+                continue; // only user sets are expected to change from their factory state
+            }
+            let index = favorite.live_set_index();
+            let reason = if let Some(ls) = user_sets.get(index) {
+                if ls.has_blank_name() {
+                    "which has a blank name"
+                } else if ls.has_default_name() {
+                    "which still has the default name"
+                } else {
+                    continue;
+                }
+            } else {
+                "which does not exist"
+            };
+            warnings.push(Warning::new(WarningRule::FavoriteUnused, format!("Favorite {}{}: Points to User #{} {}", letter, i + 1, index + 1, reason)));
+        }
+    }
+    warnings
+}
+
+fn set_numbers(numbers: &[usize]) -> String {
+    numbers.iter().map(|n| format!("#{}", n)).collect::<Vec<_>>().join(", ")
+}
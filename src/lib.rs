@@ -0,0 +1,42 @@
+//! Library for reading, editing and writing Roland RD300NX live set files (RDS), either as raw bytes or as JSON.
+//!
+//! The whole file is an [`RD300NX`], holding the user and piano [`LiveSet`]s along with the system settings.
+//! It is read from and written to the raw file with [`RD300NX::from_bytes`] and [`Bytes::to_bytes`], and to JSON
+//! through [`Json`] (or split across a folder of files through [`StructuredJson`]). Each live set has its
+//! [`layers`] and [`effects`], and settings which aren't public fields can be read through accessor methods of
+//! the same name, eg:
+//!
+//! ```ignore
+//! let rds = RD300NX::from_bytes(bytes)?;
+//! let name = rds.user_sets[0].common.name();
+//! let volume = rds.user_sets[0].layers[0].internal.volume();
+//! ```
+//!
+//! Live sets can be built from scratch with [`LiveSet::builder`], and any problems found in a file are reported as
+//! [`Warnings`]. The `roland-rds` command line tool is a thin binary on top of this library, through `cli::run`.
+
+pub(crate) mod roland;
+pub(crate) mod bytes;
+pub(crate) mod json;
+#[doc(hidden)]
+pub mod cli;
+
+#[macro_use]
+extern crate serde_derive;
+
+#[macro_use]
+extern crate roland_rds_derive;
+
+pub use bytes::{Bytes, BytesError, Bit, Bits, BitsError, BitStream};
+pub use json::{Json, StructuredJson, StructuredJsonError, SplitDepth, UpdateSummary, FileCount};
+pub use json::format::FileFormat;
+pub use json::warnings::{Warnings, Warning, WarningRule, ToneRemainIssue, ToneRemainTransition};
+pub use json::migration::MigrationError;
+pub use json::patch::PatchError;
+pub use json::presets::PresetError;
+pub use json::set_list::SetListError;
+pub use json::templates::TemplateError;
+pub use roland::rd300nx::RD300NX;
+pub use roland::live_set::{self, LiveSet};
+pub use roland::{layers, system, tones, types};
+pub use roland::types::effects;
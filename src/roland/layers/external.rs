@@ -6,8 +6,10 @@ use strum::IntoEnumIterator;
 use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
+use crate::json::{Json, StructuredJson, StructuredJsonError};
 use crate::json::serialize_map_keys_in_order;
 use crate::json::validation::{contains_all_keys, LayerRanges, valid_key_range, valid_velocity_range};
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::numeric::OffsetU8;
 use crate::roland::types::notes::PianoKey;
 use crate::roland::types::enums::{Layer, TransmitPort, MonoPolyOnly, Pan, MidiChannel};
@@ -16,99 +18,163 @@ use crate::roland::types::enums::{Layer, TransmitPort, MonoPolyOnly, Pan, MidiCh
 #[validate(schema(function = "valid_key_range"))]
 #[validate(schema(function = "valid_velocity_range"))]
 pub struct ExternalLayer {
+    #[schemars(title = "Key Range Lower", description = "Lowest key transmitted by this layer, when the external split switch is on")]
     range_lower: PianoKey, // max 87 (A0-C8)
+    #[schemars(title = "Key Range Upper", description = "Highest key transmitted by this layer, when the external split switch is on")]
     range_upper: PianoKey, // max 87 (A0-C8), must be >= lower
+    #[schemars(title = "Velocity Range Lower", description = "Softest velocity which is transmitted by this layer (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_range_lower: u8,
+    #[schemars(title = "Velocity Range Upper", description = "Hardest velocity which is transmitted by this layer (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_range_upper: u8,
+    #[schemars(title = "Velocity Sens", description = "How much the transmitted velocity follows how hard the keys are played (-63 to +63)")]
     #[validate]
     velocity_sensitivity: OffsetU8<64, 1, 127>, // 1-127 (-63 - +63)
+    #[schemars(title = "Velocity Max", description = "Highest velocity which will be transmitted (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_max: u8,
+    #[schemars(title = "Transpose", description = "Pitch shift of the transmitted notes, in semitones (-48 to +48)")]
     #[validate]
     transpose: OffsetU8<64, 16, 112>, // 16-112 (-48 - +48)
+    #[schemars(title = "Layer Switch", description = "Whether this layer transmits to external MIDI devices")]
     enable: bool,
+    #[schemars(title = "Damper", description = "Whether the damper pedal is transmitted")]
     damper: bool,
+    #[schemars(title = "FC1", description = "Whether the FC1 pedal is transmitted")]
     fc1: bool,
+    #[schemars(title = "FC2", description = "Whether the FC2 pedal is transmitted")]
     fc2: bool,
+    #[schemars(title = "Modulation", description = "Whether the modulation lever is transmitted")]
     modulation: bool,
+    #[schemars(title = "Bender", description = "Whether the pitch bend lever is transmitted")]
     bender: bool,
+    #[schemars(title = "MFX Switch", description = "Whether the MFX switch is transmitted")]
     control_mfx_switch: bool,
+    #[schemars(title = "Control Slider", description = "Whether each layer's slider is transmitted, when Slider Select is Control")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::RequiredMapSchema::<Layer, bool>")]
     #[validate(custom = "contains_all_keys")]
     control_slider: HashMap<Layer, bool>,
+    #[schemars(title = "Tx Port", description = "Which MIDI port notes are transmitted on")]
     transmit_port: TransmitPort,
+    #[schemars(title = "Tx Channel", description = "Which MIDI channel notes are transmitted on")]
     transmit_channel: MidiChannel,
+    #[schemars(title = "Tx Bank Select MSB", description = "Whether Bank Select MSB is transmitted when the live set is selected")]
     transmit_bank_select_msb: bool,
+    #[schemars(title = "Bank Select MSB", description = "Bank Select MSB (CC#0) value to transmit (0-127)")]
     #[validate(range(max = 127))]
     bank_select_msb: u8,
+    #[schemars(title = "Tx Bank Select LSB", description = "Whether Bank Select LSB is transmitted when the live set is selected")]
     transmit_bank_select_lsb: bool,
+    #[schemars(title = "Bank Select LSB", description = "Bank Select LSB (CC#32) value to transmit (0-127)")]
     #[validate(range(max = 127))]
     bank_select_lsb: u8,
+    #[schemars(title = "Tx Program Change", description = "Whether a program change is transmitted when the live set is selected")]
     transmit_program_change: bool,
+    #[schemars(title = "Program Number", description = "Program change value to transmit (0-127, which is shown as 1-128 on the keyboard)")]
     #[validate(range(max = 127))]
     program_change: u8,
+    #[schemars(title = "Tx Level", description = "Whether the level is transmitted when the live set is selected")]
     transmit_level: bool,
+    #[schemars(title = "Level", description = "Level (CC#7) to transmit (0-127)")]
     #[validate(range(max = 127))]
     level: u8,
+    #[schemars(title = "Tx Pan", description = "Whether the pan is transmitted when the live set is selected")]
     transmit_pan: bool,
+    #[schemars(title = "Pan", description = "Pan (CC#10) to transmit")]
     #[validate]
     pan: Pan,
+    #[schemars(title = "Tx Coarse Tune", description = "Whether the coarse tune is transmitted when the live set is selected")]
     transmit_course_tune: bool,
+    #[schemars(title = "Coarse Tune", description = "Coarse tune to transmit, in semitones (-48 to +48)")]
     #[validate]
     course_tune_semitones: OffsetU8<64, 16, 112>, // 16-112 (-48 - +48)
+    #[schemars(title = "Tx Fine Tune", description = "Whether the fine tune is transmitted when the live set is selected")]
     transmit_fine_tine: bool,
+    #[schemars(title = "Fine Tune", description = "Fine tune to transmit, in cents (-50 to +50)")]
     #[validate]
     fine_tune_percent: OffsetU8<64, 14, 114>, // 14-114 (-50 - + 50)
+    #[schemars(title = "Tx Mono/Poly", description = "Whether mono/poly mode is transmitted when the live set is selected")]
     transmit_mono_poly: bool,
+    #[schemars(title = "Mono/Poly", description = "Mono/poly mode to transmit")]
     mono_poly: MonoPolyOnly,
+    #[schemars(title = "Tx Portamento", description = "Whether the portamento switch is transmitted when the live set is selected")]
     transmit_portamento: bool,
+    #[schemars(title = "Portamento Switch", description = "Portamento switch to transmit")]
     portamento_switch: bool,
+    #[schemars(title = "Tx Portamento Time", description = "Whether the portamento time is transmitted when the live set is selected")]
     transmit_portamento_time: bool,
+    #[schemars(title = "Portamento Time", description = "Portamento time to transmit (0-127)")]
     #[validate(range(max = 127))]
     portamento_time: u8,
+    #[schemars(title = "Tx Cutoff", description = "Whether the filter cutoff is transmitted when the live set is selected")]
     transmit_cutoff: bool,
+    #[schemars(title = "Cutoff", description = "Filter cutoff offset to transmit (-64 to +63)")]
     #[validate]
     cutoff: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Tx Resonance", description = "Whether the filter resonance is transmitted when the live set is selected")]
     transmit_resonance: bool,
+    #[schemars(title = "Resonance", description = "Filter resonance offset to transmit (-64 to +63)")]
     #[validate]
     resonance: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Tx Attack Time", description = "Whether the attack time is transmitted when the live set is selected")]
     transmit_attack_time: bool,
+    #[schemars(title = "Attack Time", description = "Envelope attack time offset to transmit (-64 to +63)")]
     #[validate]
     attack_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Tx Decay Time", description = "Whether the decay time is transmitted when the live set is selected")]
     transmit_decay_time: bool,
+    #[schemars(title = "Decay Time", description = "Envelope decay time offset to transmit (-64 to +63)")]
     #[validate]
     decay_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Tx Release Time", description = "Whether the release time is transmitted when the live set is selected")]
     transmit_release_time: bool,
+    #[schemars(title = "Release Time", description = "Envelope release time offset to transmit (-64 to +63)")]
     #[validate]
     release_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Tx Bend Range", description = "Whether the pitch bend range is transmitted when the live set is selected")]
     transmit_pitch_bend_range: bool,
+    #[schemars(title = "Bend Range", description = "Pitch bend range to transmit, in semitones (0-48)")]
     #[validate(range(max = 48))]
     pitch_bend_range_semitones: u8,
+    #[schemars(title = "Tx Modulation Depth", description = "Whether the modulation depth is transmitted when the live set is selected")]
     transmit_modulation_depth: bool,
+    #[schemars(title = "Modulation Depth", description = "Modulation depth to transmit (0-127)")]
     #[validate(range(max = 127))]
     modulation_depth: u8,
+    #[schemars(title = "Tx Chorus Level", description = "Whether the chorus send level is transmitted when the live set is selected")]
     transmit_chorus_level: bool,
+    #[schemars(title = "Chorus Level", description = "Chorus send level (CC#93) to transmit (0-127)")]
     #[validate(range(max = 127))]
     chorus_level: u8,
+    #[schemars(title = "Tx Reverb Level", description = "Whether the reverb send level is transmitted when the live set is selected")]
     transmit_reverb_level: bool,
+    #[schemars(title = "Reverb Level", description = "Reverb send level (CC#91) to transmit (0-127)")]
     #[validate(range(max = 127))]
     reverb_level: u8,
+    #[schemars(title = "Tx Control Change 1", description = "Whether the first control change is transmitted when the live set is selected")]
     transmit_control_change_1: bool,
+    #[schemars(title = "Control Change 1 Number", description = "Controller number of the first control change to transmit (0-127)")]
     #[validate(range(max = 127))]
     control_change_1_number: u8,
+    #[schemars(title = "Control Change 1 Value", description = "Value of the first control change to transmit (0-127)")]
     #[validate(range(max = 127))]
     control_change_1_value: u8,
+    #[schemars(title = "Tx Control Change 2", description = "Whether the second control change is transmitted when the live set is selected")]
     transmit_control_change_2: bool,
+    #[schemars(title = "Control Change 2 Number", description = "Controller number of the second control change to transmit (0-127)")]
     #[validate(range(max = 127))]
     control_change_2_number: u8,
+    #[schemars(title = "Control Change 2 Value", description = "Value of the second control change to transmit (0-127)")]
     #[validate(range(max = 127))]
     control_change_2_value: u8,
+    #[schemars(title = "S1", description = "Whether the S1 switch affects this layer's external part")]
     s1: bool,
+    #[schemars(title = "S2", description = "Whether the S2 switch affects this layer's external part")]
     s2: bool,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<1>::zero")]
     unused: Bits<1>
 }
@@ -276,6 +342,289 @@ impl Bytes<30> for ExternalLayer {
     }
 }
 
+impl Json for ExternalLayer {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
+
+impl ExternalLayer {
+    pub fn range_lower(&self) -> PianoKey {
+        self.range_lower
+    }
+
+    pub fn range_upper(&self) -> PianoKey {
+        self.range_upper
+    }
+
+    pub fn velocity_range_lower(&self) -> u8 {
+        self.velocity_range_lower
+    }
+
+    pub fn velocity_range_upper(&self) -> u8 {
+        self.velocity_range_upper
+    }
+
+    pub fn velocity_sensitivity(&self) -> i8 {
+        self.velocity_sensitivity.value()
+    }
+
+    pub fn velocity_max(&self) -> u8 {
+        self.velocity_max
+    }
+
+    pub fn enable(&self) -> bool {
+        self.enable
+    }
+
+    pub fn damper(&self) -> bool {
+        self.damper
+    }
+
+    pub fn fc1(&self) -> bool {
+        self.fc1
+    }
+
+    pub fn fc2(&self) -> bool {
+        self.fc2
+    }
+
+    pub fn modulation(&self) -> bool {
+        self.modulation
+    }
+
+    pub fn bender(&self) -> bool {
+        self.bender
+    }
+
+    pub fn control_mfx_switch(&self) -> bool {
+        self.control_mfx_switch
+    }
+
+    pub fn control_slider(&self) -> &HashMap<Layer, bool> {
+        &self.control_slider
+    }
+
+    pub fn transmit_port(&self) -> TransmitPort {
+        self.transmit_port
+    }
+
+    pub fn transmit_channel(&self) -> MidiChannel {
+        self.transmit_channel
+    }
+
+    pub fn transmit_bank_select_msb(&self) -> bool {
+        self.transmit_bank_select_msb
+    }
+
+    pub fn bank_select_msb(&self) -> u8 {
+        self.bank_select_msb
+    }
+
+    pub fn transmit_bank_select_lsb(&self) -> bool {
+        self.transmit_bank_select_lsb
+    }
+
+    pub fn bank_select_lsb(&self) -> u8 {
+        self.bank_select_lsb
+    }
+
+    pub fn transmit_program_change(&self) -> bool {
+        self.transmit_program_change
+    }
+
+    pub fn program_change(&self) -> u8 {
+        self.program_change
+    }
+
+    pub fn transmit_level(&self) -> bool {
+        self.transmit_level
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn transmit_pan(&self) -> bool {
+        self.transmit_pan
+    }
+
+    pub fn pan(&self) -> Pan {
+        self.pan
+    }
+
+    pub fn transmit_course_tune(&self) -> bool {
+        self.transmit_course_tune
+    }
+
+    pub fn course_tune_semitones(&self) -> i8 {
+        self.course_tune_semitones.value()
+    }
+
+    pub fn transmit_fine_tine(&self) -> bool {
+        self.transmit_fine_tine
+    }
+
+    pub fn fine_tune_percent(&self) -> i8 {
+        self.fine_tune_percent.value()
+    }
+
+    pub fn transmit_mono_poly(&self) -> bool {
+        self.transmit_mono_poly
+    }
+
+    pub fn mono_poly(&self) -> MonoPolyOnly {
+        self.mono_poly
+    }
+
+    pub fn transmit_portamento(&self) -> bool {
+        self.transmit_portamento
+    }
+
+    pub fn portamento_switch(&self) -> bool {
+        self.portamento_switch
+    }
+
+    pub fn transmit_portamento_time(&self) -> bool {
+        self.transmit_portamento_time
+    }
+
+    pub fn portamento_time(&self) -> u8 {
+        self.portamento_time
+    }
+
+    pub fn transmit_cutoff(&self) -> bool {
+        self.transmit_cutoff
+    }
+
+    pub fn cutoff(&self) -> i8 {
+        self.cutoff.value()
+    }
+
+    pub fn transmit_resonance(&self) -> bool {
+        self.transmit_resonance
+    }
+
+    pub fn resonance(&self) -> i8 {
+        self.resonance.value()
+    }
+
+    pub fn transmit_attack_time(&self) -> bool {
+        self.transmit_attack_time
+    }
+
+    pub fn attack_time(&self) -> i8 {
+        self.attack_time.value()
+    }
+
+    pub fn transmit_decay_time(&self) -> bool {
+        self.transmit_decay_time
+    }
+
+    pub fn decay_time(&self) -> i8 {
+        self.decay_time.value()
+    }
+
+    pub fn transmit_release_time(&self) -> bool {
+        self.transmit_release_time
+    }
+
+    pub fn release_time(&self) -> i8 {
+        self.release_time.value()
+    }
+
+    pub fn transmit_pitch_bend_range(&self) -> bool {
+        self.transmit_pitch_bend_range
+    }
+
+    pub fn pitch_bend_range_semitones(&self) -> u8 {
+        self.pitch_bend_range_semitones
+    }
+
+    pub fn transmit_modulation_depth(&self) -> bool {
+        self.transmit_modulation_depth
+    }
+
+    pub fn modulation_depth(&self) -> u8 {
+        self.modulation_depth
+    }
+
+    pub fn transmit_chorus_level(&self) -> bool {
+        self.transmit_chorus_level
+    }
+
+    pub fn chorus_level(&self) -> u8 {
+        self.chorus_level
+    }
+
+    pub fn transmit_reverb_level(&self) -> bool {
+        self.transmit_reverb_level
+    }
+
+    pub fn reverb_level(&self) -> u8 {
+        self.reverb_level
+    }
+
+    pub fn transmit_control_change_1(&self) -> bool {
+        self.transmit_control_change_1
+    }
+
+    pub fn control_change_1_number(&self) -> u8 {
+        self.control_change_1_number
+    }
+
+    pub fn control_change_1_value(&self) -> u8 {
+        self.control_change_1_value
+    }
+
+    pub fn transmit_control_change_2(&self) -> bool {
+        self.transmit_control_change_2
+    }
+
+    pub fn control_change_2_number(&self) -> u8 {
+        self.control_change_2_number
+    }
+
+    pub fn control_change_2_value(&self) -> u8 {
+        self.control_change_2_value
+    }
+
+    pub fn s1(&self) -> bool {
+        self.s1
+    }
+
+    pub fn s2(&self) -> bool {
+        self.s2
+    }
+
+    // the keys which this layer actually plays, as the range is ignored when the live set's split switch is off
+    pub fn key_range(&self, split_switch: bool) -> Option<(PianoKey, PianoKey)> {
+        if !self.enable {
+            None
+        } else if split_switch {
+            Some((self.range_lower, self.range_upper))
+        } else {
+            Some((PianoKey::A0, PianoKey::C8))
+        }
+    }
+
+    pub fn transpose_semitones(&self) -> i8 {
+        self.transpose.value()
+    }
+}
+
 impl LayerRanges for ExternalLayer {
     fn is_enabled(&self) -> bool {
         self.enable
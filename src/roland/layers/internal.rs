@@ -6,8 +6,12 @@ use strum::IntoEnumIterator;
 use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
+use crate::json::{Json, StructuredJson, StructuredJsonError};
 use crate::json::serialize_map_keys_in_order;
 use crate::json::validation::{contains_all_keys, LayerRanges, valid_key_range, valid_velocity_range};
+use crate::json::warnings::ToneRemainIssue;
+use crate::json::canonical::to_canonical_json;
+use crate::roland::layers::LogicalLayer;
 use crate::roland::types::enums::{Pan, Layer, PedalFunction};
 use crate::roland::types::notes::PianoKey;
 use crate::roland::types::numeric::OffsetU8;
@@ -16,52 +20,87 @@ use crate::roland::types::numeric::OffsetU8;
 #[validate(schema(function = "valid_key_range"))]
 #[validate(schema(function = "valid_velocity_range"))]
 pub struct InternalLayer {
+    #[schemars(title = "Volume", description = "Level of this layer (0-127)")]
     #[validate(range(max = 127))]
     volume: u8,
+    #[schemars(title = "Pan", description = "Stereo position of this layer")]
     #[validate]
     pan: Pan,
+    #[schemars(title = "Chorus Send", description = "Amount of this layer sent to the chorus (0-127)")]
     #[validate(range(max = 127))]
     pub chorus: u8,
+    #[schemars(title = "Reverb Send", description = "Amount of this layer sent to the reverb (0-127)")]
     #[validate(range(max = 127))]
     pub reverb: u8,
+    #[schemars(title = "Key Range Lower", description = "Lowest key played by this layer, when the internal split switch is on")]
     range_lower: PianoKey,
+    #[schemars(title = "Key Range Upper", description = "Highest key played by this layer, when the internal split switch is on")]
     range_upper: PianoKey,
+    #[schemars(title = "Velocity Range Lower", description = "Softest velocity which plays this layer (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_range_lower: u8,
+    #[schemars(title = "Velocity Range Upper", description = "Hardest velocity which plays this layer (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_range_upper: u8,
+    #[schemars(title = "Velocity Sens", description = "How much the volume follows velocity, where negative values make soft notes louder (-63 to +63)")]
     #[validate]
     velocity_sensitivity: OffsetU8<64, 1, 127>, // 1-127 (-63 - +63)
+    #[schemars(title = "Velocity Max", description = "Highest velocity this layer will play at, however hard the keys are played (1-127)")]
     #[validate(range(min = 1, max = 127))]
     velocity_max: u8,
+    #[schemars(title = "Transpose", description = "Pitch shift of this layer, in semitones (-48 to +48)")]
     #[validate]
     transpose: OffsetU8<64, 16, 112>, // 16-112 (-48 - +48)
+    #[schemars(title = "Layer Switch", description = "Whether this layer is on")]
     enable: bool,
+    #[schemars(title = "Damper", description = "Whether the damper pedal affects this layer")]
     damper: bool,
+    #[schemars(title = "FC1", description = "Whether the FC1 pedal affects this layer")]
     fc1: bool,
+    #[schemars(title = "FC2", description = "Whether the FC2 pedal affects this layer")]
     fc2: bool,
+    #[schemars(title = "Modulation", description = "Whether the modulation lever affects this layer")]
     modulation: bool,
+    #[schemars(title = "Bender", description = "Whether the pitch bend lever affects this layer")]
     bender: bool,
+    #[schemars(title = "Control Slider", description = "Whether each layer's slider affects this layer, when Slider Select is Control")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::RequiredMapSchema::<Layer, bool>")]
     #[validate(custom = "contains_all_keys")]
     control_slider: HashMap<Layer, bool>,
+    #[schemars(title = "S1", description = "Whether the S1 switch affects this layer")]
     s1: bool,
+    #[schemars(title = "S2", description = "Whether the S2 switch affects this layer")]
     s2: bool,
     // flags below are not editable on the keyboard
+    #[schemars(title = "Rx Bank Select", description = "Whether bank select messages are received")]
     receive_bank_select: bool,
+    #[schemars(title = "Rx Program Change", description = "Whether program change messages are received")]
     receive_program_change: bool,
+    #[schemars(title = "Rx Bender", description = "Whether pitch bend messages are received")]
     receive_bender: bool,
+    #[schemars(title = "Rx Modulation", description = "Whether modulation messages are received")]
     receive_modulation: bool,
+    #[schemars(title = "Rx Volume", description = "Whether volume messages are received")]
     receive_volume: bool,
+    #[schemars(title = "Rx Pan", description = "Whether pan messages are received")]
     receive_pan: bool,
+    #[schemars(title = "Rx Hold-1", description = "Whether hold (damper) messages are received")]
     receive_hold_1: bool,
+    #[schemars(title = "Rx Expression", description = "Whether expression messages are received")]
     receive_expression: bool,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<15>::zero")]
     unused: Bits<15>
 }
 
+impl Default for InternalLayer {
+    fn default() -> Self {
+        LogicalLayer::default().internal
+    }
+}
+
 impl Bytes<14> for InternalLayer {
     fn to_bytes(&self) -> Result<Box<[u8; 14]>, BytesError> {
         BitStream::write_fixed(|bits| {
@@ -158,6 +197,24 @@ impl Bytes<14> for InternalLayer {
     }
 }
 
+impl Json for InternalLayer {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
+
 impl LayerRanges for InternalLayer {
     fn is_enabled(&self) -> bool {
         self.enable
@@ -181,6 +238,106 @@ impl LayerRanges for InternalLayer {
 }
 
 impl InternalLayer {
+    pub fn volume(&self) -> u8 {
+        self.volume
+    }
+
+    pub fn pan(&self) -> Pan {
+        self.pan
+    }
+
+    pub fn range_lower(&self) -> PianoKey {
+        self.range_lower
+    }
+
+    pub fn range_upper(&self) -> PianoKey {
+        self.range_upper
+    }
+
+    pub fn velocity_range_lower(&self) -> u8 {
+        self.velocity_range_lower
+    }
+
+    pub fn velocity_range_upper(&self) -> u8 {
+        self.velocity_range_upper
+    }
+
+    pub fn velocity_sensitivity(&self) -> i8 {
+        self.velocity_sensitivity.value()
+    }
+
+    pub fn velocity_max(&self) -> u8 {
+        self.velocity_max
+    }
+
+    pub fn enable(&self) -> bool {
+        self.enable
+    }
+
+    pub fn damper(&self) -> bool {
+        self.damper
+    }
+
+    pub fn fc1(&self) -> bool {
+        self.fc1
+    }
+
+    pub fn fc2(&self) -> bool {
+        self.fc2
+    }
+
+    pub fn modulation(&self) -> bool {
+        self.modulation
+    }
+
+    pub fn bender(&self) -> bool {
+        self.bender
+    }
+
+    pub fn control_slider(&self) -> &HashMap<Layer, bool> {
+        &self.control_slider
+    }
+
+    pub fn s1(&self) -> bool {
+        self.s1
+    }
+
+    pub fn s2(&self) -> bool {
+        self.s2
+    }
+
+    pub fn receive_bank_select(&self) -> bool {
+        self.receive_bank_select
+    }
+
+    pub fn receive_program_change(&self) -> bool {
+        self.receive_program_change
+    }
+
+    pub fn receive_bender(&self) -> bool {
+        self.receive_bender
+    }
+
+    pub fn receive_modulation(&self) -> bool {
+        self.receive_modulation
+    }
+
+    pub fn receive_volume(&self) -> bool {
+        self.receive_volume
+    }
+
+    pub fn receive_pan(&self) -> bool {
+        self.receive_pan
+    }
+
+    pub fn receive_hold_1(&self) -> bool {
+        self.receive_hold_1
+    }
+
+    pub fn receive_expression(&self) -> bool {
+        self.receive_expression
+    }
+
     pub fn active(&self) -> bool {
         if !self.enable {
             false
@@ -192,34 +349,129 @@ impl InternalLayer {
             true
         }
     }
+
+    // the keys which this layer actually plays, as the range is ignored when the live set's split switch is off
+    pub fn key_range(&self, split_switch: bool) -> Option<(PianoKey, PianoKey)> {
+        if split_switch {
+            if self.active() {
+                Some((self.range_lower, self.range_upper))
+            } else {
+                None
+            }
+        } else if self.enable && self.volume != 0 {
+            Some((PianoKey::A0, PianoKey::C8))
+        } else {
+            None
+        }
+    }
+
+    pub fn transpose_semitones(&self) -> i8 {
+        self.transpose.value()
+    }
+
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+
+    pub fn set_volume(&mut self, volume: u8) {
+        self.volume = volume;
+    }
+
+    pub fn set_pan(&mut self, pan: Pan) {
+        self.pan = pan;
+    }
+
+    pub fn set_key_range(&mut self, lower: PianoKey, upper: PianoKey) {
+        self.range_lower = lower;
+        self.range_upper = upper;
+    }
+
+    pub fn set_velocity_range(&mut self, lower: u8, upper: u8) {
+        self.velocity_range_lower = lower;
+        self.velocity_range_upper = upper;
+    }
+
+    pub fn set_transpose_semitones(&mut self, semitones: i8) {
+        self.transpose = OffsetU8::new(semitones);
+    }
 }
 
 impl InternalLayer {
-    pub fn tone_remain_warning(id: &Layer, a: &Self, b: &Self, chorus_active: bool, reverb_active: bool, a_fc1: &PedalFunction, b_fc1: &PedalFunction, a_fc2: &PedalFunction, b_fc2: &PedalFunction) -> Option<String> {
+    pub fn tone_remain_issues(id: &Layer, a: &Self, b: &Self, chorus_active: bool, reverb_active: bool, a_fc1: &PedalFunction, b_fc1: &PedalFunction, a_fc2: &PedalFunction, b_fc2: &PedalFunction) -> Vec<ToneRemainIssue> {
+        let mut issues = Vec::new();
         if !a.active() {
-            None // if this layer wasn't on to begin with then it can't have any tone which needs remaining
+            // if this layer wasn't on to begin with then it can't have any tone which needs remaining
         } else if !b.enable {
-            Some(format!("Layer[{}] turns OFF", id))
-        } else if a.volume != b.volume {
-            Some(format!("Layer[{}] volume changes from {} to {}", id, a.volume, b.volume))
-        } else if a.pan != b.pan {
-            Some(format!("Layer[{}] pan moves from {:?} to {:?}", id, a.pan, b.pan))
-        } else if chorus_active && a.chorus != b.chorus {
-            Some(format!("Layer[{}] chorus level changes from {} to {}", id, a.chorus, b.chorus))
-        } else if reverb_active && a.reverb != b.reverb {
-            Some(format!("Layer[{}] reverb level changes from {} to {}", id, a.reverb, b.reverb))
-        } else if a.damper && !b.damper {
-            Some(format!("Layer[{}] damper pedal STOPS working", id))
-        } else if a.modulation && !b.modulation {
-            Some(format!("Layer[{}] modulation STOPS working", id))
-        } else if a.bender && !b.bender {
-            Some(format!("Layer[{}] bend STOPS working", id))
-        } else if let Some(fc1_reason) = PedalFunction::tone_remain_warning(a_fc1, b_fc1, a.fc1, b.fc1) {
-            Some(format!("Layer[{}] fc1 pedal {}", id, fc1_reason))
-        } else if let Some(fc2_reason) = PedalFunction::tone_remain_warning(a_fc2, b_fc2, a.fc2, b.fc2) {
-            Some(format!("Layer[{}] fc2 pedal {}", id, fc2_reason))
+            issues.push(ToneRemainIssue::new(
+                format!("Layer[{}] turns OFF", id),
+                format!("held notes on Layer[{}] are cut off", id),
+                format!("this cannot be made seamless, as a layer which is turned off (or set to volume 0) cannot keep its held notes sounding; keep Layer[{}] enabled with the same settings if they should remain", id)
+            ));
         } else {
-            None
+            if a.volume != b.volume {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] volume changes from {} to {}", id, a.volume, b.volume),
+                    format!("held notes on Layer[{}] jump in volume", id),
+                    format!("set Layer[{}] volume to {}", id, a.volume)
+                ));
+            }
+            if a.pan != b.pan {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] pan moves from {:?} to {:?}", id, a.pan, b.pan),
+                    format!("held notes on Layer[{}] jump across the stereo field", id),
+                    format!("set Layer[{}] pan to {:?}", id, a.pan)
+                ));
+            }
+            if chorus_active && a.chorus != b.chorus {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] chorus level changes from {} to {}", id, a.chorus, b.chorus),
+                    format!("held notes on Layer[{}] change how much chorus they have", id),
+                    format!("set Layer[{}] chorus to {}", id, a.chorus)
+                ));
+            }
+            if reverb_active && a.reverb != b.reverb {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] reverb level changes from {} to {}", id, a.reverb, b.reverb),
+                    format!("held notes on Layer[{}] change how much reverb they have", id),
+                    format!("set Layer[{}] reverb to {}", id, a.reverb)
+                ));
+            }
+            if a.damper && !b.damper {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] damper pedal STOPS working", id),
+                    format!("notes on Layer[{}] which are only held by the damper pedal are cut off", id),
+                    format!("enable damper on Layer[{}]", id)
+                ));
+            }
+            if a.modulation && !b.modulation {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] modulation STOPS working", id),
+                    format!("held notes on Layer[{}] stay stuck at their current modulation", id),
+                    format!("enable modulation on Layer[{}]", id)
+                ));
+            }
+            if a.bender && !b.bender {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] bend STOPS working", id),
+                    format!("held notes on Layer[{}] stay stuck at their current pitch bend", id),
+                    format!("enable bender on Layer[{}]", id)
+                ));
+            }
+            if let Some(fc1_reason) = PedalFunction::tone_remain_warning(a_fc1, b_fc1, a.fc1, b.fc1) {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] fc1 pedal {}", id, fc1_reason),
+                    format!("held notes on Layer[{}] respond differently to the fc1 pedal", id),
+                    format!("assign fc1 to {:?} and set Layer[{}] fc1 to {}", a_fc1, id, a.fc1)
+                ));
+            }
+            if let Some(fc2_reason) = PedalFunction::tone_remain_warning(a_fc2, b_fc2, a.fc2, b.fc2) {
+                issues.push(ToneRemainIssue::new(
+                    format!("Layer[{}] fc2 pedal {}", id, fc2_reason),
+                    format!("held notes on Layer[{}] respond differently to the fc2 pedal", id),
+                    format!("assign fc2 to {:?} and set Layer[{}] fc2 to {}", a_fc2, id, a.fc2)
+                ));
+            }
         }
+        issues
     }
 }
@@ -15,16 +15,28 @@ pub use e_piano::EPianoLayer;
 pub use tone_wheel::ToneWheelLayer;
 use validator::Validate;
 
-use crate::json::{Json, StructuredJson, StructuredJsonError, validation::matching_piano_tone};
+use crate::json::{Json, StructuredJson, StructuredJsonError, SplitDepth, validation::matching_piano_tone};
+use crate::json::canonical::to_canonical_json;
+use crate::roland::live_set::LiveSet;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 #[validate(schema(function = "matching_piano_tone"))]
 pub struct LogicalLayer {
+    #[schemars(title = "Internal", description = "How this layer plays the internal sound generator")]
+    #[validate]
     pub internal: InternalLayer,
+    #[schemars(title = "External", description = "How this layer transmits to external MIDI devices")]
+    #[validate]
     pub external: ExternalLayer,
+    #[schemars(title = "Tone", description = "The internal tone played by this layer, and how it is modified")]
+    #[validate]
     pub tone: ToneLayer,
+    #[schemars(title = "Piano", description = "Piano designer settings, used when the tone is one of the SuperNATURAL pianos")]
+    #[validate]
     pub piano: PianoLayer,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     pub unused_e_piano: EPianoLayer,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     pub unused_tone_wheel: ToneWheelLayer
 }
 
@@ -51,17 +63,57 @@ impl LogicalLayer {
     }
 }
 
+// the Upper 1 layer of a blank live set, which is the only layer switched on
+impl Default for LogicalLayer {
+    fn default() -> Self {
+        let [upper1, ..] = *LiveSet::blank().layers;
+        upper1
+    }
+}
+
 impl Json for LogicalLayer {
     fn to_structured_json(&self) -> StructuredJson {
         StructuredJson::SingleJson(self.to_json())
     }
 
-    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
-        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    fn to_structured_json_at_depth(&self, depth: SplitDepth) -> StructuredJson {
+        if depth != SplitDepth::Full {
+            return self.to_structured_json();
+        }
+        StructuredJson::NestedCollection(vec![
+            ("internal".to_string(), self.internal.to_structured_json()),
+            ("external".to_string(), self.external.to_structured_json()),
+            ("tone".to_string(), self.tone.to_structured_json()),
+            ("piano".to_string(), self.piano.to_structured_json()),
+            ("unused_e_piano".to_string(), self.unused_e_piano.to_structured_json()),
+            ("unused_tone_wheel".to_string(), self.unused_tone_wheel.to_structured_json())
+        ])
+    }
+
+    fn from_structured_json(mut structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        if let StructuredJson::SingleJson(json) = structured_json {
+            return Self::from_json(json).map_err(|e| e.into());
+        }
+        // split at Full depth
+        let internal = structured_json.extract("internal")?.to()?;
+        let external = structured_json.extract("external")?.to()?;
+        let tone = structured_json.extract("tone")?.to()?;
+        let piano = structured_json.extract("piano")?.to()?;
+        let unused_e_piano = structured_json.extract("unused_e_piano")?.to()?;
+        let unused_tone_wheel = structured_json.extract("unused_tone_wheel")?.to()?;
+        structured_json.done()?;
+        Ok(Self {
+            internal,
+            external,
+            tone,
+            piano,
+            unused_e_piano,
+            unused_tone_wheel
+        })
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
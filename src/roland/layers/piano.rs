@@ -5,43 +5,122 @@ use strum::IntoEnumIterator;
 use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
+use crate::json::{Json, StructuredJson, StructuredJsonError};
 use crate::roland::tones::PianoToneNumber;
 use crate::roland::types::enums::{StretchTuneType, NuanceType};
 use crate::roland::types::notes::MidiNote;
 use crate::roland::types::numeric::{Offset1Dp, OffsetU8};
 use crate::json::serialize_map_keys_in_order;
+use crate::json::canonical::to_canonical_json;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct PianoLayer {
+    #[schemars(title = "Piano Tone", description = "The SuperNATURAL piano which these settings apply to")]
     #[validate]
     pub tone_number: PianoToneNumber,
+    #[schemars(title = "Stereo Width", description = "Width of the stereo image of the piano (0-63)")]
     #[validate(range(max = 63))]
     stereo_width: u8,
+    #[schemars(title = "Nuance", description = "Subtle changes in the way the piano sounds when played, by changing the phase of the left and right channels")]
     nuance: NuanceType,
+    #[schemars(title = "Duplex Scale", description = "Volume of the sympathetic vibrations of the duplex scale (0-127)")]
     #[validate(range(max = 127))]
     duplex_scale_level: u8,
+    #[schemars(title = "Hammer Noise", description = "Volume of the sound of the hammers striking the strings (-2 to +2)")]
     #[validate]
     hammer_noise_level: OffsetU8<4, 2, 6>, // 2-6 (-2 - +2)
+    #[schemars(title = "Damper Noise", description = "Volume of the sound of the dampers leaving the strings when the damper pedal is pressed (0-127)")]
     #[validate(range(max = 127))]
     damper_noise_level: u8,
+    #[schemars(title = "String Resonance", description = "Volume of the sympathetic resonance of the other strings (0-127)")]
     #[validate(range(max = 127))]
     string_resonance_level: u8,
+    #[schemars(title = "Key Off Resonance", description = "Volume of the resonance when keys are released (0-127)")]
     #[validate(range(max = 127))]
     key_off_resonance_level: u8,
+    #[schemars(title = "Sound Lift", description = "How much the sound is lifted and brightened, as if the piano lid were opened (0-127)")]
     #[validate(range(max = 127))]
     sound_lift: u8,
+    #[schemars(title = "Tone Character", description = "Brightness of the piano, where positive values are harder and brighter (-5 to +5)")]
     #[validate]
     tone_character: OffsetU8<8, 3, 13>, // 3-13 (-5 - +5)
+    #[schemars(title = "Stretch Tune", description = "Whether the high and low notes are tuned slightly sharp and flat, as pianos are usually tuned")]
     stretch_tune_type: StretchTuneType,
+    #[schemars(title = "Micro Tune", description = "Tuning of each note, in cents (-50.0 to +50.0), where notes not listed are not detuned")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::OptionalMapSchema::<MidiNote, Offset1Dp<512, 12, 1012>>")]
     #[validate]
     micro_tune_percent: HashMap<MidiNote, Offset1Dp<512, 12, 1012>>, // each 12-1012 (-50.0 - +50.0)
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<5>::zero")]
     unused: Bits<5>
 }
 
+impl PianoLayer {
+    pub fn stereo_width(&self) -> u8 {
+        self.stereo_width
+    }
+
+    pub fn nuance(&self) -> NuanceType {
+        self.nuance
+    }
+
+    pub fn duplex_scale_level(&self) -> u8 {
+        self.duplex_scale_level
+    }
+
+    pub fn hammer_noise_level(&self) -> i8 {
+        self.hammer_noise_level.value()
+    }
+
+    pub fn damper_noise_level(&self) -> u8 {
+        self.damper_noise_level
+    }
+
+    pub fn string_resonance_level(&self) -> u8 {
+        self.string_resonance_level
+    }
+
+    pub fn key_off_resonance_level(&self) -> u8 {
+        self.key_off_resonance_level
+    }
+
+    pub fn sound_lift(&self) -> u8 {
+        self.sound_lift
+    }
+
+    pub fn tone_character(&self) -> i8 {
+        self.tone_character.value()
+    }
+
+    pub fn stretch_tune_type(&self) -> StretchTuneType {
+        self.stretch_tune_type
+    }
+
+    // the micro tuning of every MIDI note, in cents
+    pub fn micro_tune_cents(&self) -> [f64; 128] {
+        let mut cents = [0.0; 128];
+        for (note, value) in &self.micro_tune_percent {
+            let note: u8 = (*note).into();
+            let raw: u16 = (*value).into();
+            cents[note as usize] = (raw as f64 - 512.0) / 10.0;
+        }
+        cents
+    }
+
+    // each value is rounded to the nearest 0.1 cent, and must already be within +/-50 cents
+    pub fn set_micro_tune_cents(&mut self, cents: &[f64; 128]) {
+        self.micro_tune_percent.clear();
+        for (note, value) in MidiNote::iter().zip(cents) {
+            let raw = ((value * 10.0).round() as i32 + 512) as u16;
+            if raw != 512 {
+                self.micro_tune_percent.insert(note, Offset1Dp::from(raw));
+            }
+        }
+    }
+}
+
 impl Bytes<264> for PianoLayer {
     fn to_bytes(&self) -> Result<Box<[u8; 264]>, BytesError> {
         BitStream::write_fixed(|bits| {
@@ -106,3 +185,21 @@ impl Bytes<264> for PianoLayer {
         })
     }
 }
+
+impl Json for PianoLayer {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
@@ -3,6 +3,8 @@ use schemars::JsonSchema;
 use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
+use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::enums::MonoPoly;
 use crate::roland::types::numeric::OffsetU8;
 
@@ -10,33 +12,90 @@ use super::super::tones::ToneNumber;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct ToneLayer {
+    #[schemars(title = "Tone", description = "The internal tone played by this layer")]
     #[validate]
     pub tone_number: ToneNumber,
+    #[schemars(title = "Coarse Tune", description = "Pitch of the tone, in semitones (-48 to +48)")]
     #[validate]
     course_tune_semitones: OffsetU8<64, 16, 112>, // 16-112 (-48 - +48)
+    #[schemars(title = "Fine Tune", description = "Fine pitch of the tone, in cents (-50 to +50)")]
     #[validate]
     fine_tune_percent: OffsetU8<64, 14, 114>, // 14-114 (-50 - + 50)
+    #[schemars(title = "Mono/Poly", description = "Whether the tone plays one note at a time (Mono), chords (Poly), or one note without retriggering (Mono/Legato)")]
     mono_poly: MonoPoly, // 0=Mono, 1=Poly, 2=Mono/Legato
+    #[schemars(title = "Bend Range", description = "How far the pitch bend lever changes the pitch, in semitones (0-24)")]
     #[validate(range(max = 24))]
     pitch_bend_range_semitones: u8,
+    #[schemars(title = "Portamento Switch", description = "Whether the pitch glides from one note to the next")]
     portamento_switch: bool,
+    #[schemars(title = "Portamento Time", description = "How long the pitch takes to glide between notes (0-127)")]
     #[validate(range(max = 127))]
     portamento_time: u8,
+    #[schemars(title = "Cutoff", description = "Brightness of the tone, relative to its own setting (-64 to +63)")]
     #[validate]
     cutoff: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Resonance", description = "Emphasis of the sound around the cutoff, relative to the tone's own setting (-64 to +63)")]
     #[validate]
     resonance: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Attack Time", description = "How quickly the sound starts, relative to the tone's own setting (-64 to +63)")]
     #[validate]
     attack_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Decay Time", description = "How quickly the sound fades while held, relative to the tone's own setting (-64 to +63)")]
     #[validate]
     decay_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Release Time", description = "How quickly the sound fades after the key is released, relative to the tone's own setting (-64 to +63)")]
     #[validate]
     release_time: OffsetU8<64, 0, 127>, // max 127 (-64 - +63)
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<10>::zero")]
     unused: Bits<10>
 }
 
 impl ToneLayer {
+    pub fn course_tune_semitones(&self) -> i8 {
+        self.course_tune_semitones.value()
+    }
+
+    pub fn fine_tune_percent(&self) -> i8 {
+        self.fine_tune_percent.value()
+    }
+
+    pub fn mono_poly(&self) -> MonoPoly {
+        self.mono_poly
+    }
+
+    pub fn pitch_bend_range_semitones(&self) -> u8 {
+        self.pitch_bend_range_semitones
+    }
+
+    pub fn portamento_switch(&self) -> bool {
+        self.portamento_switch
+    }
+
+    pub fn portamento_time(&self) -> u8 {
+        self.portamento_time
+    }
+
+    pub fn cutoff(&self) -> i8 {
+        self.cutoff.value()
+    }
+
+    pub fn resonance(&self) -> i8 {
+        self.resonance.value()
+    }
+
+    pub fn attack_time(&self) -> i8 {
+        self.attack_time.value()
+    }
+
+    pub fn decay_time(&self) -> i8 {
+        self.decay_time.value()
+    }
+
+    pub fn release_time(&self) -> i8 {
+        self.release_time.value()
+    }
+
     pub fn tone_name(&self) -> String {
         self.tone_number.details().name.to_owned()
     }
@@ -88,3 +147,21 @@ impl Bytes<12> for ToneLayer {
         })
     }
 }
+
+impl Json for ToneLayer {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
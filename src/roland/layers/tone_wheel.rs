@@ -3,6 +3,8 @@ use std::fmt::Debug;
 use schemars::JsonSchema;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
+use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct ToneWheelLayer(Bits<48>);
@@ -20,3 +22,21 @@ impl Bytes<6> for ToneWheelLayer {
         })
     }
 }
+
+impl Json for ToneWheelLayer {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
@@ -0,0 +1,157 @@
+// A live set builder starts from a blank live set (as the keyboard is initialised) and changes only what is given, eg:
+//   LiveSet::builder().name("BALLAD").split(true)
+//     .layer(Layer::Upper1, |l| l.tone(ToneNumber::from(1)).range(PianoKey::C4, PianoKey::C8))
+//     .layer(Layer::Lower1, |l| l.tone(strings).range(PianoKey::A0, PianoKey::B3).volume(90))
+//     .build()?
+// Configuring a layer switches it on, and the key ranges only apply when the split switch is on.
+
+use crate::json::validation::error_paths;
+use crate::roland::layers::LogicalLayer;
+use crate::roland::tones::ToneNumber;
+use crate::roland::types::enums::{Layer, Pan};
+use crate::roland::types::notes::PianoKey;
+use super::LiveSet;
+use super::chorus::Chorus;
+use super::metadata::LiveSetMetadata;
+use super::mfx::Mfx;
+use super::reverb::Reverb;
+
+use validator::Validate;
+
+pub struct LiveSetBuilder {
+    live_set: LiveSet,
+    errors: Vec<String>
+}
+
+pub struct LayerBuilder<'a> {
+    layer: &'a mut LogicalLayer
+}
+
+impl LiveSetBuilder {
+    pub fn new() -> Self {
+        Self {
+            live_set: LiveSet::blank(),
+            errors: Vec::new()
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        if name.chars().count() > 16 {
+            self.errors.push(format!("Live set name '{}' is longer than 16 characters", name));
+        }
+        self.live_set.common.set_name(name);
+        self
+    }
+
+    pub fn tempo(mut self, bpm: u16) -> Self {
+        self.live_set.common.set_live_set_tempo(bpm);
+        self
+    }
+
+    pub fn split(mut self, split_switch: bool) -> Self {
+        self.live_set.common.split_switch_internal = split_switch;
+        self
+    }
+
+    pub fn layer<F>(mut self, layer: Layer, f: F) -> Self where F: FnOnce(LayerBuilder) -> LayerBuilder {
+        let index: u8 = layer.into();
+        match self.live_set.layers.get_mut(index as usize) {
+            Some(logical) => {
+                logical.internal.set_enable(true);
+                f(LayerBuilder { layer: logical });
+            },
+            None => self.errors.push(format!("Layer {} is not used by the RD300NX", layer))
+        }
+        self
+    }
+
+    pub fn mfx(mut self, mfx: Mfx) -> Self {
+        self.live_set.mfx = mfx;
+        self
+    }
+
+    pub fn reverb(mut self, reverb: Reverb) -> Self {
+        self.live_set.reverb = reverb;
+        self
+    }
+
+    pub fn chorus(mut self, chorus: Chorus) -> Self {
+        self.live_set.chorus = chorus;
+        self
+    }
+
+    pub fn metadata(mut self, metadata: LiveSetMetadata) -> Self {
+        self.live_set.metadata = Some(metadata);
+        self
+    }
+
+    // the built live set is always valid, otherwise every problem is listed
+    pub fn build(self) -> Result<LiveSet, String> {
+        let mut errors = self.errors;
+        if let Err(e) = self.live_set.validate() {
+            errors.append(&mut error_paths(&e));
+        }
+        if errors.is_empty() {
+            Ok(self.live_set)
+        } else {
+            Err(format!("Live set is not valid:\n{}", errors.join("\n")))
+        }
+    }
+}
+
+impl Default for LiveSetBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> LayerBuilder<'a> {
+    // the piano designer settings follow the tone, when it is one of the SuperNATURAL pianos
+    pub fn tone(self, tone: ToneNumber) -> Self {
+        self.layer.tone.tone_number = tone;
+        if let Some(piano_tone) = tone.as_piano_tone() {
+            self.layer.piano.tone_number = piano_tone;
+        }
+        self
+    }
+
+    pub fn enable(self, enable: bool) -> Self {
+        self.layer.internal.set_enable(enable);
+        self
+    }
+
+    pub fn range(self, lower: PianoKey, upper: PianoKey) -> Self {
+        self.layer.internal.set_key_range(lower, upper);
+        self
+    }
+
+    pub fn velocity_range(self, lower: u8, upper: u8) -> Self {
+        self.layer.internal.set_velocity_range(lower, upper);
+        self
+    }
+
+    pub fn volume(self, volume: u8) -> Self {
+        self.layer.internal.set_volume(volume);
+        self
+    }
+
+    pub fn pan(self, pan: Pan) -> Self {
+        self.layer.internal.set_pan(pan);
+        self
+    }
+
+    pub fn transpose(self, semitones: i8) -> Self {
+        self.layer.internal.set_transpose_semitones(semitones);
+        self
+    }
+
+    pub fn chorus(self, send: u8) -> Self {
+        self.layer.internal.chorus = send;
+        self
+    }
+
+    pub fn reverb(self, send: u8) -> Self {
+        self.layer.internal.reverb = send;
+        self
+    }
+}
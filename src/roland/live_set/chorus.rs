@@ -4,20 +4,27 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::warnings::ToneRemainIssue;
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::enums::OutputSelect;
 use crate::roland::types::effects::chorus::ChorusType;
 use crate::roland::types::numeric::Parameter;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Chorus {
+    #[schemars(title = "Chorus Type", description = "Type of chorus effect and its parameters")]
     #[validate]
     pub chorus_type: ChorusType,
+    #[schemars(title = "Chorus Depth", description = "Overall amount of the chorus effect (0-127)")]
     #[validate(range(max = 127))]
     depth: u8,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<2>::zero")]
     unused1: Bits<2>,
+    #[schemars(title = "Chorus Output Select", description = "Where the chorus output is sent, either directly to the output or also through the reverb")]
     output_select: OutputSelect,
     // [Parameter; 20]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<1>::zero")]
     unused: Bits<1>
 }
@@ -68,7 +75,7 @@ impl Json for Chorus {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -77,25 +84,57 @@ impl Json for Chorus {
 }
 
 impl Chorus {
-    pub fn tone_remain_warning(a: &Self, b: &Self, a_max_chorus_level: u8, b_max_chorus_level: u8) -> Option<String> {
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn output_select(&self) -> OutputSelect {
+        self.output_select
+    }
+
+    pub fn tone_remain_issue(a: &Self, b: &Self, a_max_chorus_level: u8, b_max_chorus_level: u8) -> Option<ToneRemainIssue> {
         let a_off = a_max_chorus_level == 0 || a.chorus_type.is_off();
         let b_off = b_max_chorus_level == 0 || b.chorus_type.is_off();
         if a_off && !b_off {
-            Some(format!("Chorus ({}) turns ON", b.chorus_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) turns ON", b.chorus_type.name()),
+                format!("held notes suddenly gain {} chorus", b.chorus_type.name()),
+                "set every layer's chorus level to 0, or turn Chorus off".to_string()
+            ))
         } else if !a_off && b_off {
-            Some(format!("Chorus ({}) turns OFF", a.chorus_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) turns OFF", a.chorus_type.name()),
+                format!("held notes lose their {} chorus", a.chorus_type.name()),
+                format!("change Chorus type to {} and keep the chorus level of the held layers above 0", a.chorus_type.name())
+            ))
         } else if a_off && b_off {
             None // other changes to Chorus are irrelevant if Chorus is off 
         } else if a.chorus_type.number() != b.chorus_type.number() {
-            Some(format!("Chorus ({}) changes to {:?}", a.chorus_type.name(), b.chorus_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) changes to {}", a.chorus_type.name(), b.chorus_type.name()),
+                format!("held notes switch from {} chorus to {}", a.chorus_type.name(), b.chorus_type.name()),
+                format!("change Chorus type to {} with the same parameters", a.chorus_type.name())
+            ))
         } else if a.depth != b.depth {
-            Some(format!("Chorus ({}) depth changes from {} to {}", a.chorus_type.name(), a.depth, b.depth))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) depth changes from {} to {}", a.chorus_type.name(), a.depth, b.depth),
+                "the chorus on held notes changes depth".to_string(),
+                format!("set Chorus depth to {}", a.depth)
+            ))
         } else if a.output_select != b.output_select {
-            Some(format!("Chorus ({}) output changes from {:?} to {:?}", a.chorus_type.name(), a.output_select, b.output_select))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) output changes from {:?} to {:?}", a.chorus_type.name(), a.output_select, b.output_select),
+                "the chorus on held notes is routed differently".to_string(),
+                format!("set Chorus output to {:?}", a.output_select)
+            ))
         } else if a.chorus_type.parameters() != b.chorus_type.parameters() {
-            Some(format!("Chorus ({}) parameters change", a.chorus_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Chorus ({}) parameters change", a.chorus_type.name()),
+                "the chorus on held notes changes character".to_string(),
+                "copy the Chorus parameters from the previous live set".to_string()
+            ))
         } else {
             None
         }
     }
-}
+}
\ No newline at end of file
@@ -7,73 +7,107 @@ use validator::Validate;
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, serialize_chars_as_string, StructuredJsonError};
 use crate::json::validation::{valid_chars, contains_all_keys, not_system_only_button_function, not_system_only_pedal_function};
+use crate::roland::live_set::LiveSet;
 use crate::roland::types::StateMap;
 use crate::roland::types::enums::{Layer, SliderSelect, KeyOffPosition, KeyTouchVelocity, KeyTouchCurveType, VoiceReserve, HarmonicBar, MidiChannel, ButtonFunction, PedalFunction, SliderFunction, SoundFocusType};
 use crate::roland::types::numeric::OffsetU8;
 use crate::json::serialize_map_keys_in_order;
+use crate::json::canonical::to_canonical_json;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 #[schemars(rename = "LiveSetCommon")]
 pub struct Common {
+    #[schemars(title = "Live Set Name", description = "Name shown on the display, up to 16 characters")]
     #[serde(deserialize_with = "serialize_chars_as_string::deserialize")]
     #[serde(serialize_with = "serialize_chars_as_string::serialize")]
     #[schemars(with = "serialize_chars_as_string::StringSchema::<16>")]
     #[validate(custom = "valid_chars")]
     name: [char; 16], // 32-127 (ascii)
+    #[schemars(title = "Voice Reserve", description = "Number of voices reserved for each MIDI channel, where channels not listed use the default")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::OptionalMapSchema::<MidiChannel, VoiceReserve>")]
     #[validate]
     voice_reserve: HashMap<MidiChannel, VoiceReserve>,
+    #[schemars(title = "Live Set Tempo", description = "Tempo used by the rhythm and tempo synced effects, in beats per minute (10-500)")]
     #[validate(range(min = 10, max = 500))]
     live_set_tempo: u16,
+    #[schemars(title = "FC1 Assign", description = "Function of the FC1 pedal, unless Pedal Mode is System")]
     #[validate]
     #[validate(custom = "not_system_only_pedal_function")]
     pub fc1_assign: PedalFunction, // 0-144
+    #[schemars(title = "FC2 Assign", description = "Function of the FC2 pedal, unless Pedal Mode is System")]
     #[validate]
     #[validate(custom = "not_system_only_pedal_function")]
     pub fc2_assign: PedalFunction, // 0-144
+    #[schemars(title = "Sound Focus Switch", description = "Whether sound focus is on, which makes the sound stand out in a band")]
     sound_focus_switch: bool,
+    #[schemars(title = "Sound Focus Type", description = "Type of sound focus processing")]
     #[validate]
     sound_focus_type: SoundFocusType,
+    #[schemars(title = "Sound Focus Value", description = "Amount of sound focus (0-127)")]
     #[validate(range(max = 127))]
     sound_focus_value: u8,
+    #[schemars(title = "S1 Assign", description = "Function of the S1 switch, unless S1/S2 Mode is System")]
     #[validate(custom = "not_system_only_button_function")]
     pub s1_assign: ButtonFunction, // 0-17
+    #[schemars(title = "S2 Assign", description = "Function of the S2 switch, unless S1/S2 Mode is System")]
     #[validate(custom = "not_system_only_button_function")]
     pub s2_assign: ButtonFunction, // 0-17
+    #[schemars(title = "S1 State", description = "Whether the S1 switch is on when the live set is selected")]
     pub s1_state: bool,
+    #[schemars(title = "S2 State", description = "Whether the S2 switch is on when the live set is selected")]
     pub s2_state: bool,
+    #[schemars(title = "EQ Settings", description = "Equalizer settings (not yet decoded)")]
     unused_eq_settings: Bits<68>,
+    #[schemars(title = "Key Touch Velocity", description = "Whether velocity follows how hard the keys are played (Real), or is always a fixed value (1-127)")]
     #[validate]
     key_touch_velocity: KeyTouchVelocity,
+    #[schemars(title = "Key Touch", description = "How much force is needed to play loudly, from Super Light to Super Heavy")]
     key_touch_curve_type: KeyTouchCurveType,
+    #[schemars(title = "Key Touch Offset", description = "Fine adjustment of the key touch curve (-10 to +9)")]
     #[validate]
     key_touch_curve_offset: OffsetU8<10, 0, 19>, // 0-19 (-10 - +9)
+    #[schemars(title = "Velocity Delay Sens", description = "How much later notes sound when played softly, where positive values delay soft notes more (-63 to +63)")]
     #[validate]
     key_touch_velocity_delay_sense: OffsetU8<64, 1, 127>, // 1-127 (-63 - +63)
+    #[schemars(title = "Velocity Key Follow", description = "How velocity changes across the keyboard, where positive values make higher notes louder (-63 to +63)")]
     #[validate]
     key_touch_velocity_key_follow: OffsetU8<64, 1, 127>, // 1-127 (-63 - +63)
+    #[schemars(title = "Key Off Position", description = "Whether notes stop when the key is nearly released (Standard) or fully released (Deep)")]
     key_off_position: KeyOffPosition,
+    #[schemars(title = "Slider Select", description = "Whether the sliders control the layer levels or their assigned functions (Control)")]
     slider_select: SliderSelect,
+    #[schemars(title = "Slider Assign", description = "Function of each layer's slider, when Slider Select is Control")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::RequiredMapSchema::<Layer, SliderFunction>")]
     #[validate]
     #[validate(custom = "contains_all_keys")]
     slider_assign: HashMap<Layer, SliderFunction>,
+    #[schemars(title = "Split (Internal)", description = "Whether the internal layers are limited to their key ranges, otherwise every layer plays the whole keyboard")]
     pub split_switch_internal: bool,
+    #[schemars(title = "Split (External)", description = "Whether the external layers are limited to their key ranges, otherwise every layer plays the whole keyboard")]
     pub split_switch_external: bool,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(deserialize_with = "serialize_map_keys_in_order::deserialize")]
     #[serde(serialize_with = "serialize_map_keys_in_order::serialize")]
     #[schemars(with = "serialize_map_keys_in_order::RequiredMapSchema::<Layer, StateMap<HarmonicBar>>")]
     #[validate(custom = "contains_all_keys")]
     unused_harmonic_bar_assign: HashMap<Layer, StateMap<HarmonicBar>>, // index=(LOWER2:ON, LOWER2:OFF, LOWER1:ON, LOWER1:OFF, UPPER2:ON, UPPER2:OFF, UPPER1:ON, UPPER1:OFF)
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     unused_mfx_control_destination: Layer,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<7>::zero")]
     unused: Bits<7>
 }
 
+impl Default for Common {
+    fn default() -> Self {
+        LiveSet::blank().common
+    }
+}
+
 impl Bytes<56> for Common {
     fn to_bytes(&self) -> Result<Box<[u8; Self::BYTE_SIZE]>, BytesError> {
         BitStream::write_fixed(|bits| {
@@ -207,7 +241,7 @@ impl Json for Common {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -216,7 +250,84 @@ impl Json for Common {
 }
 
 impl Common {
+    pub fn voice_reserve(&self) -> &HashMap<MidiChannel, VoiceReserve> {
+        &self.voice_reserve
+    }
+
+    pub fn live_set_tempo(&self) -> u16 {
+        self.live_set_tempo
+    }
+
+    pub fn sound_focus_switch(&self) -> bool {
+        self.sound_focus_switch
+    }
+
+    pub fn sound_focus_type(&self) -> SoundFocusType {
+        self.sound_focus_type
+    }
+
+    pub fn sound_focus_value(&self) -> u8 {
+        self.sound_focus_value
+    }
+
+    pub fn key_touch_velocity(&self) -> KeyTouchVelocity {
+        self.key_touch_velocity
+    }
+
+    pub fn key_touch_curve_type(&self) -> KeyTouchCurveType {
+        self.key_touch_curve_type
+    }
+
+    pub fn key_touch_curve_offset(&self) -> i8 {
+        self.key_touch_curve_offset.value()
+    }
+
+    pub fn key_touch_velocity_delay_sense(&self) -> i8 {
+        self.key_touch_velocity_delay_sense.value()
+    }
+
+    pub fn key_touch_velocity_key_follow(&self) -> i8 {
+        self.key_touch_velocity_key_follow.value()
+    }
+
+    pub fn key_off_position(&self) -> KeyOffPosition {
+        self.key_off_position
+    }
+
+    pub fn slider_select(&self) -> SliderSelect {
+        self.slider_select
+    }
+
     pub fn name_string(&self) -> String {
         self.name.iter().collect()
     }
+
+    // the name without the spaces which pad it to 16 characters
+    pub fn name(&self) -> String {
+        self.name_string().trim_end().to_string()
+    }
+
+    // names longer than 16 characters are truncated, and shorter names are padded with spaces
+    pub fn set_name(&mut self, name: &str) {
+        let mut chars = name.chars().chain(std::iter::repeat(' '));
+        for c in self.name.iter_mut() {
+            *c = chars.next().unwrap();
+        }
+    }
+
+    pub fn set_live_set_tempo(&mut self, tempo: u16) {
+        self.live_set_tempo = tempo;
+    }
+
+    pub fn set_key_touch_velocity(&mut self, velocity: KeyTouchVelocity) {
+        self.key_touch_velocity = velocity;
+    }
+
+    pub fn set_key_touch_curve_type(&mut self, curve: KeyTouchCurveType) {
+        self.key_touch_curve_type = curve;
+    }
+
+    pub fn slider_assign(&self, layer: &Layer) -> SliderFunction {
+        self.slider_assign.get(layer).copied().unwrap_or_default()
+    }
 }
\ No newline at end of file
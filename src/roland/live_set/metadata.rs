@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+
+use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
+
+// Metadata is not stored in the RDS file, it only exists in the JSON to document why a live set exists
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, Default)]
+pub struct LiveSetMetadata {
+    #[schemars(title = "Song", description = "The song this live set is used for")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub song: Option<String>,
+    #[schemars(title = "Notes", description = "Why this live set exists, or how to play it")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub notes: Option<String>,
+    #[schemars(title = "Tags", description = "Labels used to group live sets, eg. when filling favorites from a set list")]
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub tags: Vec<String>,
+    #[schemars(title = "Last Edited", description = "When this live set was last changed")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub last_edited: Option<String>
+}
+
+impl Json for LiveSetMetadata {
+    fn to_structured_json(&self) -> StructuredJson {
+        StructuredJson::SingleJson(self.to_json())
+    }
+
+    fn from_structured_json(structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        Self::from_json(structured_json.to_single_json()?).map_err(|e| e.into())
+    }
+
+    fn to_json(&self) -> String {
+        to_canonical_json(&self).unwrap()
+    }
+
+    fn from_json(json: String) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&json)
+    }
+}
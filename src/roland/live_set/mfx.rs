@@ -5,27 +5,39 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::warnings::ToneRemainIssue;
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::effects::mfx::MfxType;
+use crate::roland::types::enums::Layer;
 use crate::roland::types::numeric::Parameter;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Mfx {
+    #[schemars(title = "MFX Switch", description = "Whether the multi-effect is on")]
     pub enable: bool,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<8>::zero")]
     unused1: Bits<8>,
+    #[schemars(title = "MFX Type", description = "Type of multi-effect and its parameters")]
     #[validate]
     pub mfx_type: MfxType,
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<8>::unit")]
     padding1: Bits<8>,
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<14>::unit")]
     padding2: Bits<14>,
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<14>::unit")]
     padding3: Bits<14>,
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<14>::unit")]
     padding4: Bits<14>,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<26>::zero")]
     unused2: Bits<26>, // this contains the MFX control choice (which depends on type, so should probably be part of mfx_type)
     // [Parameter; 32]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<3>::zero")]
     unused3: Bits<3>
 }
@@ -88,7 +100,7 @@ impl Json for Mfx {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -103,19 +115,35 @@ impl Mfx {
 }
 
 impl Mfx {
-    pub fn tone_remain_warning(a: &Self, b: &Self, a_layer0_active: &bool) -> Option<String> {
+    pub fn tone_remain_issue(a: &Self, b: &Self, a_layer0_active: &bool) -> Option<ToneRemainIssue> {
         if !a_layer0_active {
             None // if layer0 wasn't on to begin with then mfx can't affect any tone which needs remaining
         } else if a.active() && !b.active() {
-            Some(format!("Mfx ({}) turns OFF", a.mfx_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Mfx ({}) turns OFF", a.mfx_type.name()),
+                format!("held notes on Layer[{}] lose the {} effect", Layer::Upper1, a.mfx_type.name()),
+                format!("enable Mfx with type {} and the same parameters", a.mfx_type.name())
+            ))
         } else if !a.active() && b.active() {
-            Some(format!("Mfx ({}) turns ON", b.mfx_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Mfx ({}) turns ON", b.mfx_type.name()),
+                format!("held notes on Layer[{}] suddenly gain the {} effect", Layer::Upper1, b.mfx_type.name()),
+                "disable Mfx".to_string()
+            ))
         } else if !a.active() && !b.active() {
             None // other changes to Mfx are irrelevant if Mfx is off 
         } else if a.mfx_type.number() != b.mfx_type.number() {
-            Some(format!("Mfx ({}) changes to {}", a.mfx_type.name(), b.mfx_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Mfx ({}) changes to {}", a.mfx_type.name(), b.mfx_type.name()),
+                format!("held notes on Layer[{}] switch from the {} effect to {}", Layer::Upper1, a.mfx_type.name(), b.mfx_type.name()),
+                format!("change Mfx type to {} with the same parameters", a.mfx_type.name())
+            ))
         } else if a.mfx_type.parameters() != b.mfx_type.parameters() {
-            Some(format!("Mfx ({}) parameters change", a.mfx_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Mfx ({}) parameters change", a.mfx_type.name()),
+                format!("the {} effect on held notes of Layer[{}] changes character", a.mfx_type.name(), Layer::Upper1),
+                "copy the Mfx parameters from the previous live set".to_string()
+            ))
         } else {
             None
         }
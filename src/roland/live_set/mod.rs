@@ -3,57 +3,135 @@ use schemars::JsonSchema;
 use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
-use crate::json::warnings::{Warnings, split_switch_warning};
-use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::warnings::{Warnings, Warning, split_switch_warning};
+use crate::json::{Json, StructuredJson, StructuredJsonError, SplitDepth};
 use crate::json::validation::valid_boxed_elements;
+use crate::json::canonical::to_canonical_json;
+use self::builder::LiveSetBuilder;
 use self::chorus::Chorus;
+use self::metadata::LiveSetMetadata;
 use self::common::Common;
 use self::mfx::Mfx;
 use self::resonance::Resonance;
 use self::reverb::Reverb;
 use self::song_rhythm::SongRhythm;
 
+use super::rd300nx::RD300NX;
+use super::types::effects::Effect;
 use super::layers::{LogicalLayer, ToneWheelLayer, EPianoLayer, InternalLayer, ExternalLayer, ToneLayer, PianoLayer};
 use super::sum_to_zero;
 
-mod common;
+pub mod common;
 pub mod chorus;
 pub mod reverb;
-mod song_rhythm;
+pub mod song_rhythm;
 pub mod mfx;
 mod resonance;
+pub mod metadata;
+pub mod builder;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct LiveSet {
+    #[schemars(title = "Metadata", description = "Notes about why this live set exists, which are not stored in the RDS file")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub metadata: Option<LiveSetMetadata>, // not stored in RDS
+    #[schemars(title = "Common", description = "Settings which apply to the whole live set, such as name, tempo, controllers and key touch")]
     #[validate]
     pub common: Common, // 56 bytes
+    #[schemars(title = "Song/Rhythm", description = "Levels and outputs for song playback and the rhythm function")]
     #[validate]
     song_rhythm: SongRhythm, // 6 bytes
+    #[schemars(title = "Chorus", description = "Chorus effect, which is applied according to each layer's chorus send")]
     #[validate]
     pub chorus: Chorus, // 42 bytes
+    #[schemars(title = "Reverb", description = "Reverb effect, which is applied according to each layer's reverb send")]
     #[validate]
     pub reverb: Reverb, // 42 bytes
+    #[schemars(title = "MFX", description = "Multi-effect which is applied to the internal layers")]
     #[validate]
     pub mfx: Mfx, // 76 bytes
     // don't validate because it can contain values unused by the RD300NX
     //#[validate(custom = "valid_boxed_elements")]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     unused_mfx: Box<[Mfx; 7]>, // 532 bytes
     //#[validate]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     unused_resonance: Resonance, // 76 bytes
+    #[schemars(title = "Layers", description = "The Upper 1, Upper 2 and Lower layers, each with an internal tone and an external MIDI part")]
     #[validate(custom = "valid_boxed_elements")]
     pub layers: Box<[LogicalLayer; 3]>, // 332*3=996 bytes
     // don't validate because it can contain values unused by the RD300NX
     //#[validate]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     unused_layer: LogicalLayer, // 332 bytes
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<8>::unit")]
     padding: Bits<8>, // 1 byte
     // checksum: 1 byte
 }
 
 impl LiveSet {
+    pub const DEFAULT_NAME: &'static str = "Concert Grand"; // the name given to every user set when the keyboard is initialised
+
+    // the live set given to every user set when the keyboard is initialised (which is also the first factory live set)
+    pub fn blank() -> Self {
+        let [live_set, ..] = *RD300NX::blank().user_sets;
+        live_set
+    }
+
+    pub fn builder() -> LiveSetBuilder {
+        LiveSetBuilder::new()
+    }
+
+    pub fn song_rhythm(&self) -> &SongRhythm {
+        &self.song_rhythm
+    }
+
     pub fn name_string(&self) -> String {
         self.common.name_string()
     }
+
+    pub fn has_blank_name(&self) -> bool {
+        self.name_string().trim().is_empty()
+    }
+
+    pub fn has_default_name(&self) -> bool {
+        self.name_string().trim() == Self::DEFAULT_NAME
+    }
+
+    // resets the parameters of an effect to the defaults for its current type, or switches to the named type with its
+    // defaults (as turning the type knob on the keyboard does), returning the name of the type
+    pub fn reset_effect(&mut self, effect: Effect, type_name: Option<&str>) -> Result<String, String> {
+        Ok(match effect {
+            Effect::Mfx => {
+                self.mfx.mfx_type = match type_name {
+                    Some(name) => name.parse()?,
+                    None => self.mfx.mfx_type.default()
+                };
+                self.mfx.mfx_type.name()
+            },
+            Effect::Reverb => {
+                self.reverb.reverb_type = match type_name {
+                    Some(name) => name.parse()?,
+                    None => self.reverb.reverb_type.default()
+                };
+                self.reverb.reverb_type.name().to_string()
+            },
+            Effect::Chorus => {
+                self.chorus.chorus_type = match type_name {
+                    Some(name) => name.parse()?,
+                    None => self.chorus.chorus_type.default()
+                };
+                self.chorus.chorus_type.name().to_string()
+            }
+        })
+    }
+}
+
+impl Default for LiveSet {
+    fn default() -> Self {
+        Self::blank()
+    }
 }
 
 impl Bytes<2160> for LiveSet {
@@ -97,6 +175,7 @@ impl Bytes<2160> for LiveSet {
                 });
             }
             Ok(Self {
+                metadata: None,
                 common,
                 song_rhythm,
                 chorus,
@@ -156,10 +235,21 @@ impl Bytes<2160> for LiveSet {
 
 impl Json for LiveSet {
     fn to_structured_json(&self) -> StructuredJson {
+        self.to_structured_json_at_depth(SplitDepth::default())
+    }
+
+    fn to_structured_json_at_depth(&self, depth: SplitDepth) -> StructuredJson {
+        if depth == SplitDepth::Set {
+            return StructuredJson::SingleJson(self.to_json());
+        }
         if !self.padding.is_unit() {
             panic!("Cannot split JSON with non-standard padding");
         }
-        StructuredJson::NestedCollection(vec![
+        let mut nodes = Vec::new();
+        if let Some(metadata) = &self.metadata {
+            nodes.push(("metadata".to_string(), metadata.to_structured_json()));
+        }
+        nodes.append(&mut vec![
             ("ls_common".to_string(), self.common.to_structured_json()),
             ("song_rhythm".to_string(), self.song_rhythm.to_structured_json()),
             ("chorus".to_string(), self.chorus.to_structured_json()),
@@ -167,23 +257,29 @@ impl Json for LiveSet {
             ("mfx".to_string(), self.mfx.to_structured_json()),
             ("unused_mfx".to_string(), StructuredJson::from_collection(self.unused_mfx.as_slice(), |_| None)),
             ("unused_resonance".to_string(), self.unused_resonance.to_structured_json()),
-            ("layers".to_string(), StructuredJson::from_collection(self.layers.as_slice(), |l| Some(l.tone.tone_name()))),
-            ("unused_layer".to_string(), self.unused_layer.to_structured_json())
-        ])
+            ("layers".to_string(), StructuredJson::from_collection_at_depth(self.layers.as_slice(), |l| Some(l.tone.tone_name()), depth)),
+            ("unused_layer".to_string(), self.unused_layer.to_structured_json_at_depth(depth))
+        ]);
+        StructuredJson::NestedCollection(nodes)
     }
 
     fn from_structured_json(mut structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
+        if let StructuredJson::SingleJson(json) = structured_json {
+            return Self::from_json(json).map_err(|e| e.into()); // split at Set depth
+        }
+        let metadata = structured_json.extract_optional("metadata")?.map(|m| m.to()).transpose()?;
         let common = structured_json.extract("ls_common")?.to()?;
         let song_rhythm = structured_json.extract("song_rhythm")?.to()?;
         let chorus = structured_json.extract("chorus")?.to()?;
         let reverb = structured_json.extract("reverb")?.to()?;
         let mfx = structured_json.extract("mfx")?.to()?;
-        let unused_mfx = structured_json.extract("unused_mfx")?.to_array()?;
+        let unused_mfx = structured_json.extract("unused_mfx")?.to_array().map_err(|e| e.in_node("unused_mfx"))?;
         let unused_resonance = structured_json.extract("unused_resonance")?.to()?;
-        let layers = structured_json.extract("layers")?.to_array()?;
+        let layers = structured_json.extract("layers")?.to_array().map_err(|e| e.in_node("layers"))?;
         let unused_layer = structured_json.extract("unused_layer")?.to()?;
         structured_json.done()?;
         Ok(Self {
+            metadata,
             common,
             song_rhythm,
             chorus,
@@ -198,7 +294,7 @@ impl Json for LiveSet {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -207,7 +303,7 @@ impl Json for LiveSet {
 }
 
 impl Warnings for LiveSet {
-    fn warnings(&self) -> Vec<String> {
+    fn warnings(&self) -> Vec<Warning> {
         let mut warnings = Vec::new();
         if let Some(warning) = split_switch_warning("Internal", self.common.split_switch_internal, self.layers.iter().map(|l| &l.internal)) {
             warnings.push(warning);
@@ -3,6 +3,7 @@ use schemars::JsonSchema;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct Resonance(Bits<608>);
@@ -31,7 +32,7 @@ impl Json for Resonance {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -5,18 +5,24 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::warnings::ToneRemainIssue;
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::effects::reverb::ReverbType;
 use crate::roland::types::numeric::Parameter;
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Reverb {
+    #[schemars(title = "Reverb Type", description = "Type of reverb effect and its parameters")]
     #[validate]
     pub reverb_type: ReverbType,
+    #[schemars(title = "Reverb Depth", description = "Overall amount of the reverb effect (0-127)")]
     #[validate(range(max = 127))]
     depth: u8,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<2>::zero")]
     unused1: Bits<2>,
     // [Parameter; 20]
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<3>::zero")]
     unused2: Bits<3>
 }
@@ -64,7 +70,7 @@ impl Json for Reverb {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -73,21 +79,45 @@ impl Json for Reverb {
 }
 
 impl Reverb {
-    pub fn tone_remain_warning(a: &Self, b: &Self, a_max_reverb_level: u8, b_max_reverb_level: u8) -> Option<String> {
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn tone_remain_issue(a: &Self, b: &Self, a_max_reverb_level: u8, b_max_reverb_level: u8) -> Option<ToneRemainIssue> {
         let a_off = a_max_reverb_level == 0 || a.reverb_type.is_off();
         let b_off = b_max_reverb_level == 0 || b.reverb_type.is_off();
         if a_off && !b_off {
-            Some(format!("Reverb ({}) turns ON", b.reverb_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Reverb ({}) turns ON", b.reverb_type.name()),
+                format!("held notes suddenly gain {} reverb", b.reverb_type.name()),
+                "set every layer's reverb level to 0, or turn Reverb off".to_string()
+            ))
         } else if !a_off && b_off {
-            Some(format!("Reverb ({}) turns OFF", a.reverb_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Reverb ({}) turns OFF", a.reverb_type.name()),
+                format!("held notes lose their {} reverb", a.reverb_type.name()),
+                format!("change Reverb type to {} and keep the reverb level of the held layers above 0", a.reverb_type.name())
+            ))
         } else if a_off && b_off {
             None // other changes to Reverb are irrelevant if Reverb is off 
         } else if a.reverb_type.number() != b.reverb_type.number() {
-            Some(format!("Reverb ({}) changes to {}", a.reverb_type.name(), b.reverb_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Reverb ({}) changes to {}", a.reverb_type.name(), b.reverb_type.name()),
+                format!("held notes switch from {} reverb to {}", a.reverb_type.name(), b.reverb_type.name()),
+                format!("change Reverb type to {} with the same parameters", a.reverb_type.name())
+            ))
         } else if a.depth != b.depth {
-            Some(format!("Reverb ({}) depth changes from {} to {}", a.reverb_type.name(), a.depth, b.depth))
+            Some(ToneRemainIssue::new(
+                format!("Reverb ({}) depth changes from {} to {}", a.reverb_type.name(), a.depth, b.depth),
+                "the reverb on held notes changes depth".to_string(),
+                format!("set Reverb depth to {}", a.depth)
+            ))
         } else if a.reverb_type.parameters() != b.reverb_type.parameters() {
-            Some(format!("Reverb ({}) parameters change", a.reverb_type.name()))
+            Some(ToneRemainIssue::new(
+                format!("Reverb ({}) parameters change", a.reverb_type.name()),
+                "the reverb on held notes changes character".to_string(),
+                "copy the Reverb parameters from the previous live set".to_string()
+            ))
         } else {
             None
         }
@@ -4,27 +4,39 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::enums::{OutputPort, OptionalMidiChannel};
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct SongRhythm {
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<1>::zero")]
     unused1: Bits<1>,
+    #[schemars(title = "Audio Level", description = "Volume of audio files played from USB memory (0-127)")]
     #[validate(range(max = 127))]
     audio_level: u8,
+    #[schemars(title = "Song Level", description = "Volume of SMF songs played from USB memory (0-127)")]
     #[validate(range(max = 127))]
     song_level: u8,
+    #[schemars(title = "Song Output Port", description = "Where song playback is sent")]
     song_output_port: OutputPort,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<1>::zero")]
     unused2: Bits<1>,
+    #[schemars(title = "Rhythm Set", description = "Drum kit used by the rhythm function (0-13)")]
     #[validate(range(max = 13))]
     rhythm_set: u8,
+    #[schemars(title = "Rhythm Level", description = "Volume of the rhythm function (0-127)")]
     #[validate(range(max = 127))]
     rhythm_level: u8,
+    #[schemars(title = "Rhythm Pattern", description = "Pattern played by the rhythm function (0-200)")]
     #[validate(range(max = 200))]
     rhythm_pattern: u8,
+    #[schemars(title = "Rhythm MIDI Out Channel", description = "MIDI channel on which the rhythm is transmitted, or Off")]
     rhythm_midi_out_channel: OptionalMidiChannel,
+    #[schemars(title = "Rhythm Output Port", description = "Where the rhythm is sent")]
     rhythm_output_port: OutputPort,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<2>::zero")]
     unused3: Bits<2>
 }
@@ -76,10 +88,44 @@ impl Json for SongRhythm {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
         serde_json::from_str(&json)
     }
-}
\ No newline at end of file
+}
+
+impl SongRhythm {
+    pub fn audio_level(&self) -> u8 {
+        self.audio_level
+    }
+
+    pub fn song_level(&self) -> u8 {
+        self.song_level
+    }
+
+    pub fn song_output_port(&self) -> OutputPort {
+        self.song_output_port
+    }
+
+    pub fn rhythm_set(&self) -> u8 {
+        self.rhythm_set
+    }
+
+    pub fn rhythm_level(&self) -> u8 {
+        self.rhythm_level
+    }
+
+    pub fn rhythm_pattern(&self) -> u8 {
+        self.rhythm_pattern
+    }
+
+    pub fn rhythm_midi_out_channel(&self) -> OptionalMidiChannel {
+        self.rhythm_midi_out_channel
+    }
+
+    pub fn rhythm_output_port(&self) -> OutputPort {
+        self.rhythm_output_port
+    }
+}
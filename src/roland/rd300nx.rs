@@ -1,23 +1,34 @@
 use crate::bytes::{Bytes, BytesError, BitStream};
 use crate::json::validation::{validate_boxed_array, merge_all_fixed};
-use crate::json::warnings::{Warnings, tone_remain_warnings, mfx_state_warnings};
-use crate::json::{StructuredJson, Json, StructuredJsonError, serialize_array_as_vec};
+use crate::json::warnings::{Warnings, Warning, ToneRemainTransition, tone_remain_transitions, mfx_state_warnings, duplicate_name_warnings, identical_live_set_warnings, favorite_warnings};
+use crate::json::migration::{FORMAT_VERSION, migrate_json};
+use crate::json::{StructuredJson, Json, StructuredJsonError, SplitDepth, serialize_array_as_vec};
+use crate::json::canonical::to_canonical_json;
 use super::live_set::LiveSet;
 use super::system::System;
-use super::types::enums::SettingMode;
+use super::tones::{ToneNumber, ToneUsage};
+use super::types::enums::{Layer, SettingMode};
 use super::types::metadata::{ToneRemain, BySet};
 use schemars::JsonSchema;
+use strum::IntoEnumIterator;
+use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
 #[derive(Serialize, Deserialize, JsonSchema)]
 pub struct RD300NX {
+    #[schemars(title = "Format Version", description = "The version of roland-rds JSON format this was written with, so that older files can be upgraded when read")]
+    format_version: u16,
+    #[schemars(title = "User Live Sets", description = "The 60 live sets which can be freely programmed, selected with the LIVE SET buttons")]
     #[serde(deserialize_with = "serialize_array_as_vec::deserialize")]
     #[serde(serialize_with = "serialize_array_as_vec::serialize")]
     #[schemars(with = "serialize_array_as_vec::ArraySchema::<LiveSet, {Self::USER_SETS}>")]
     pub user_sets: Box<[LiveSet; Self::USER_SETS]>,
+    #[schemars(title = "Piano Live Sets", description = "The 10 live sets selected with the ONE TOUCH PIANO button")]
     pub piano: Box<[LiveSet; Self::PIANO_SETS]>,
+    #[schemars(title = "E. Piano Live Sets", description = "The 15 live sets selected with the ONE TOUCH E. PIANO button")]
     pub e_piano: Box<[LiveSet; Self::E_PIANO_SETS]>,
-    system: System
+    #[schemars(title = "System", description = "Settings which apply to every live set (SYSTEM button)")]
+    pub system: System
     // checksum: 2 bytes
 }
 
@@ -33,7 +44,7 @@ impl Validate for RD300NX {
 }
 
 impl Warnings for RD300NX {
-    fn warnings(&self) -> Vec<String> {
+    fn warnings(&self) -> Vec<Warning> {
         let mut warnings = Vec::new();
         let s1 = match self.system.common.s1_s2_mode {
             SettingMode::LiveSet => None,
@@ -47,23 +58,138 @@ impl Warnings for RD300NX {
             let mut ls_warnings = live_set.warnings();
             ls_warnings.append(&mut mfx_state_warnings(live_set, &s1, &s2));
             for warning in ls_warnings {
-                warnings.push(format!("User #{}: {}", i+1, warning));
+                warnings.push(warning.prefixed(&format!("User #{}: ", i+1)));
             }
         }
         for (i, live_set) in self.piano.iter().enumerate() {
             let mut ls_warnings = live_set.warnings();
             ls_warnings.append(&mut mfx_state_warnings(live_set, &s1, &s2));
             for warning in ls_warnings {
-                warnings.push(format!("Piano #{}: {}", i+1, warning));
+                warnings.push(warning.prefixed(&format!("Piano #{}: ", i+1)));
             }
         }
         for (i, live_set) in self.e_piano.iter().enumerate() {
             let mut ls_warnings = live_set.warnings();
             ls_warnings.append(&mut mfx_state_warnings(live_set, &s1, &s2));
             for warning in ls_warnings {
-                warnings.push(format!("EPiano #{}: {}", i+1, warning));
+                warnings.push(warning.prefixed(&format!("EPiano #{}: ", i+1)));
             }
         }
+        warnings.append(&mut duplicate_name_warnings("User", self.user_sets.as_slice()));
+        warnings.append(&mut identical_live_set_warnings(self.user_sets.as_slice(), self.piano.as_slice(), self.e_piano.as_slice()));
+        warnings.append(&mut favorite_warnings(self.system.favorites(), self.user_sets.as_slice()));
+        for transition in self.tone_remain_report() {
+            warnings.append(&mut transition.warnings());
+        }
+        warnings
+    }
+}
+
+impl RD300NX {
+    pub fn format_version(&self) -> u16 {
+        self.format_version
+    }
+
+    pub const USER_SETS: usize = 60;
+    pub const PIANO_SETS: usize = 10;
+    pub const E_PIANO_SETS: usize = 15;
+
+    // the state of the keyboard when it is initialised, where every user set is identical
+    pub fn blank() -> Self {
+        let bytes = include_bytes!("../../examples/rd300nx/BLANK.RDS");
+        Self::from_bytes(bytes.to_vec().try_into().unwrap()).unwrap() // safe because BLANK.RDS is a valid RDS file
+    }
+
+    // the live sets which the keyboard is shipped with, as restored by a factory reset
+    pub fn factory_default() -> Self {
+        let bytes = include_bytes!("../../examples/rd300nx/DEFAULT.RDS");
+        Self::from_bytes(bytes.to_vec().try_into().unwrap()).unwrap() // safe because DEFAULT.RDS is a valid RDS file
+    }
+
+    pub fn all_live_sets(&self) -> Vec<&LiveSet> {
+        self.user_sets.iter().chain(self.piano.iter()).chain(self.e_piano.iter()).collect()
+    }
+
+    pub fn all_live_sets_mut(&mut self) -> Vec<&mut LiveSet> {
+        self.user_sets.iter_mut().chain(self.piano.iter_mut()).chain(self.e_piano.iter_mut()).collect()
+    }
+
+    pub fn live_set(&self, bank: &str, index: usize) -> Option<&LiveSet> {
+        match bank {
+            "user_sets" => self.user_sets.get(index),
+            "piano" => self.piano.get(index),
+            "e_piano" => self.e_piano.get(index),
+            _ => None
+        }
+    }
+
+    pub fn live_set_mut(&mut self, bank: &str, index: usize) -> Option<&mut LiveSet> {
+        match bank {
+            "user_sets" => self.user_sets.get_mut(index),
+            "piano" => self.piano.get_mut(index),
+            "e_piano" => self.e_piano.get_mut(index),
+            _ => None
+        }
+    }
+
+    // copy metadata from a previous version of these live sets, matching by content first and then by name
+    // (rather than position) so that metadata follows its live set when they have been reordered
+    pub fn reattach_metadata(&mut self, previous: &RD300NX) -> Result<usize, BytesError> {
+        let mut candidates = Vec::new();
+        for (i, ls) in previous.all_live_sets().into_iter().enumerate() {
+            if let Some(metadata) = &ls.metadata {
+                candidates.push((i, ls.name_string(), ls.to_bytes()?, metadata.clone()));
+            }
+        }
+        let mut live_sets = self.all_live_sets_mut();
+        let mut count = 0;
+        for by_content in [true, false] {
+            for (i, ls) in live_sets.iter_mut().enumerate() {
+                if ls.metadata.is_some() {
+                    continue;
+                }
+                let bytes = ls.to_bytes()?;
+                let matches: Vec<usize> = candidates.iter().enumerate()
+                    .filter(|(_, (_, name, b, _))| if by_content {
+                        *b == bytes
+                    } else {
+                        *name == ls.name_string() && !ls.has_blank_name() && !ls.has_default_name()
+                    })
+                    .map(|(c, _)| c)
+                    .collect();
+                let best = matches.iter().find(|c| candidates[**c].0 == i).or(matches.first());
+                if let Some(c) = best {
+                    ls.metadata = Some(candidates.remove(*c).3);
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    // every layer (of every live set) which has this tone selected, excluding the unused 4th layer
+    pub fn tone_usage(&self, tone: ToneNumber) -> Vec<ToneUsage> {
+        let mut usage = Vec::new();
+        for (bank, live_sets) in [("user_sets", self.user_sets.as_slice()), ("piano", self.piano.as_slice()), ("e_piano", self.e_piano.as_slice())] {
+            for (index, ls) in live_sets.iter().enumerate() {
+                for (layer, l) in Layer::iter().zip(ls.layers.iter()) {
+                    if layer != Layer::UnusedLower2 && l.tone.tone_number == tone {
+                        usage.push(ToneUsage {
+                            bank,
+                            index,
+                            name: ls.name_string().trim().to_string(),
+                            layer,
+                            enabled: l.internal.key_range(ls.common.split_switch_internal).is_some()
+                        });
+                    }
+                }
+            }
+        }
+        usage
+    }
+
+    pub fn tone_remain_report(&self) -> Vec<ToneRemainTransition> {
+        let mut transitions = Vec::new();
         if self.system.common.tone_remain.any() {
             let fc1 = match self.system.common.pedal_mode {
                 SettingMode::LiveSet => None,
@@ -78,21 +204,11 @@ impl Warnings for RD300NX {
                 ToneRemain::Always(false) => panic!("ToneRemain::Bool(false).any() == true"),
                 ToneRemain::BySet(s) => (&s.user_sets, &s.piano, &s.e_piano)
             };
-            warnings.append(&mut tone_remain_warnings(user, "User", &self.user_sets, fc1, fc2));
-            warnings.append(&mut tone_remain_warnings(piano, "Piano", &self.piano, fc1, fc2));
-            warnings.append(&mut tone_remain_warnings(e_piano, "EPiano", &self.e_piano, fc1, fc2));
+            transitions.append(&mut tone_remain_transitions(user, "User", &self.user_sets, fc1, fc2));
+            transitions.append(&mut tone_remain_transitions(piano, "Piano", &self.piano, fc1, fc2));
+            transitions.append(&mut tone_remain_transitions(e_piano, "EPiano", &self.e_piano, fc1, fc2));
         }
-        warnings
-    }
-}
-
-impl RD300NX {
-    pub const USER_SETS: usize = 60;
-    pub const PIANO_SETS: usize = 10;
-    pub const E_PIANO_SETS: usize = 15;
-
-    pub fn all_live_sets(&self) -> Vec<&LiveSet> {
-        self.user_sets.iter().chain(self.piano.iter()).chain(self.e_piano.iter()).collect()
+        transitions
     }
 }
 
@@ -112,6 +228,7 @@ impl Bytes<183762> for RD300NX {
                 });
             }
             Ok(Self {
+                format_version: FORMAT_VERSION,
                 user_sets,
                 piano,
                 e_piano,
@@ -135,21 +252,26 @@ impl Bytes<183762> for RD300NX {
 
 impl Json for RD300NX {
     fn to_structured_json(&self) -> StructuredJson {
+        self.to_structured_json_at_depth(SplitDepth::default())
+    }
+
+    fn to_structured_json_at_depth(&self, depth: SplitDepth) -> StructuredJson {
         StructuredJson::NestedCollection(vec![
-            ("user_sets".to_string(), StructuredJson::from_collection(self.user_sets.as_slice(), |ls| Some(ls.name_string()))),
-            ("piano".to_string(), StructuredJson::from_collection(self.piano.as_slice(), |ls| Some(ls.name_string()))),
-            ("e_piano".to_string(), StructuredJson::from_collection(self.e_piano.as_slice(), |ls| Some(ls.name_string()))),
+            ("user_sets".to_string(), StructuredJson::from_collection_at_depth(self.user_sets.as_slice(), |ls| Some(ls.name_string()), depth)),
+            ("piano".to_string(), StructuredJson::from_collection_at_depth(self.piano.as_slice(), |ls| Some(ls.name_string()), depth)),
+            ("e_piano".to_string(), StructuredJson::from_collection_at_depth(self.e_piano.as_slice(), |ls| Some(ls.name_string()), depth)),
             ("system".to_string(), self.system.to_structured_json())
         ])
     }
 
     fn from_structured_json(mut structured_json: StructuredJson) -> Result<Self, StructuredJsonError> {
-        let user_sets = structured_json.extract("user_sets")?.to_array()?;
-        let piano = structured_json.extract("piano")?.to_array()?;
-        let e_piano = structured_json.extract("e_piano")?.to_array()?;
+        let user_sets = structured_json.extract("user_sets")?.to_array().map_err(|e| e.in_node("user_sets"))?;
+        let piano = structured_json.extract("piano")?.to_array().map_err(|e| e.in_node("piano"))?;
+        let e_piano = structured_json.extract("e_piano")?.to_array().map_err(|e| e.in_node("e_piano"))?;
         let system = structured_json.extract("system")?.to()?;
         structured_json.done()?;
         Ok(Self {
+            format_version: FORMAT_VERSION, // split folders are upgraded before being read
             user_sets,
             piano,
             e_piano,
@@ -158,10 +280,20 @@ impl Json for RD300NX {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
-        serde_json::from_str(&json)
+        #[derive(Deserialize)]
+        struct Version {
+            #[serde(default)]
+            format_version: u16
+        }
+        if serde_json::from_str::<Version>(&json)?.format_version == FORMAT_VERSION {
+            return serde_json::from_str(&json);
+        }
+        let mut value: Value = serde_json::from_str(&json)?;
+        migrate_json(&mut value).map_err(serde::de::Error::custom)?;
+        serde_json::from_str(&value.to_string())
     }
 }
@@ -4,6 +4,7 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{StructuredJson, Json, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::enums::{Polarity, SettingMode, OptionalMidiChannel, PartMode, ButtonFunction, PedalFunction, Temperament};
 use crate::roland::types::metadata::ToneRemain;
 use crate::roland::types::notes::KeyNote;
@@ -12,31 +13,52 @@ use crate::roland::types::numeric::Offset1Dp;
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 #[schemars(rename = "SystemCommon")]
 pub struct Common {
+    #[schemars(title = "Master Tune", description = "Tuning of the whole keyboard, in cents from A4=440Hz (-100.0 to +100.0, ie. 415.3-466.2Hz)")]
     #[validate]
     master_tune_percent: Offset1Dp<1024, 24, 2024>, // 24-2024 (-100.0 - +100.0)
+    #[schemars(title = "Master Level", description = "Overall volume level (0-127)")]
     #[validate(range(max = 127))]
     master_level: u8, // max 127
+    #[schemars(title = "Live Set Ctrl Channel", description = "MIDI channel on which program changes select live sets, or Off")]
     live_set_control_channel: OptionalMidiChannel,
+    #[schemars(title = "Damper Polarity", description = "Polarity of the damper pedal, which depends on the pedal model")]
     damper_polarity: Polarity,
+    #[schemars(title = "FC1 Polarity", description = "Polarity of the pedal connected to the FC1 jack")]
     fc1_polarity: Polarity,
+    #[schemars(title = "FC2 Polarity", description = "Polarity of the pedal connected to the FC2 jack")]
     fc2_polarity: Polarity,
+    #[schemars(title = "EQ Mode", description = "Whether the EQ settings come from each live set or from the system")]
     eq_mode: SettingMode,
+    #[schemars(title = "Pedal Mode", description = "Whether the FC1/FC2 pedal assignments come from each live set or from the system")]
     pub pedal_mode: SettingMode,
+    #[schemars(title = "S1/S2 Mode", description = "Whether the S1/S2 switch assignments come from each live set or from the system")]
     pub s1_s2_mode: SettingMode,
+    #[schemars(title = "FC1 Assign", description = "Function of the FC1 pedal, when Pedal Mode is System")]
     #[validate]
     pub fc1_assign: PedalFunction, // 0-146
+    #[schemars(title = "FC2 Assign", description = "Function of the FC2 pedal, when Pedal Mode is System")]
     #[validate]
     pub fc2_assign: PedalFunction, // 0-146
+    #[schemars(title = "S1 Assign", description = "Function of the S1 switch, when S1/S2 Mode is System")]
     pub s1_assign: ButtonFunction, // 0-20
+    #[schemars(title = "S2 Assign", description = "Function of the S2 switch, when S1/S2 Mode is System")]
     pub s2_assign: ButtonFunction, // 0-20
+    #[schemars(title = "Tone Remain", description = "Whether held notes keep sounding when changing live sets, either for all live sets or only for particular changes")]
     #[validate]
     pub tone_remain: ToneRemain,
+    #[schemars(title = "Rx GM/GM2 System On", description = "Whether GM/GM2 System On messages are received")]
     receive_gm_gm2_system_on: bool,
+    #[schemars(title = "Rx GS Reset", description = "Whether GS Reset messages are received")]
     receive_gs_reset: bool,
+    #[schemars(title = "Part Mode", description = "How the internal parts respond to MIDI messages")]
     part_mode: PartMode,
+    #[schemars(title = "Unknown", description = "Not yet decoded")]
     unsure: Bits<2>,
+    #[schemars(title = "Temperament", description = "Tuning system used by the piano tones")]
     temperament: Temperament,
+    #[schemars(title = "Temperament Key", description = "Tonic note of the temperament")]
     temperament_key: KeyNote,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<7>::zero")]
     unused: Bits<7>
 }
@@ -108,10 +130,60 @@ impl Json for Common {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
         serde_json::from_str(&json)
     }
-}
\ No newline at end of file
+}
+
+impl Common {
+    pub fn master_tune_percent(&self) -> f64 {
+        self.master_tune_percent.value()
+    }
+
+    pub fn master_level(&self) -> u8 {
+        self.master_level
+    }
+
+    pub fn live_set_control_channel(&self) -> OptionalMidiChannel {
+        self.live_set_control_channel
+    }
+
+    pub fn damper_polarity(&self) -> Polarity {
+        self.damper_polarity
+    }
+
+    pub fn fc1_polarity(&self) -> Polarity {
+        self.fc1_polarity
+    }
+
+    pub fn fc2_polarity(&self) -> Polarity {
+        self.fc2_polarity
+    }
+
+    pub fn eq_mode(&self) -> SettingMode {
+        self.eq_mode
+    }
+
+    pub fn receive_gm_gm2_system_on(&self) -> bool {
+        self.receive_gm_gm2_system_on
+    }
+
+    pub fn receive_gs_reset(&self) -> bool {
+        self.receive_gs_reset
+    }
+
+    pub fn part_mode(&self) -> PartMode {
+        self.part_mode
+    }
+
+    pub fn temperament(&self) -> Temperament {
+        self.temperament
+    }
+
+    pub fn temperament_key(&self) -> KeyNote {
+        self.temperament_key
+    }
+}
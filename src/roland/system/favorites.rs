@@ -4,32 +4,44 @@ use validator::Validate;
 
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{StructuredJson, Json, StructuredJsonError};
+use crate::json::canonical::to_canonical_json;
 use crate::roland::types::enums::PatchCategory;
 use crate::roland::types::numeric::{OneIndexedU16, OneIndexedU8};
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Favorites {
+    #[schemars(title = "One Touch Piano Number", description = "The piano live set which is selected when the ONE TOUCH PIANO button is pressed (1-10)")]
     #[validate]
     one_touch_piano_current_number: OneIndexedU8<128>,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[validate]
     unused_one_touch_piano_current_number: [OneIndexedU8<128>; 2],
+    #[schemars(title = "One Touch E. Piano Number", description = "The e-piano live set which is selected when the ONE TOUCH E. PIANO button is pressed (1-15)")]
     #[validate]
     one_touch_e_piano_current_number: OneIndexedU8<128>,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[validate]
     unused_one_touch_e_piano_current_number: [OneIndexedU8<128>; 2],
+    #[schemars(title = "Favorite Bank A", description = "The favorites of BANK A, in the order of the FAVORITE buttons")]
     #[validate]
     bank_a: Bank,
+    #[schemars(title = "Favorite Bank B", description = "The favorites of BANK B, in the order of the FAVORITE buttons")]
     #[validate]
     bank_b: Bank,
+    #[schemars(title = "Favorite Bank C", description = "The favorites of BANK C, in the order of the FAVORITE buttons")]
     #[validate]
     bank_c: Bank,
+    #[schemars(title = "Favorite Bank D", description = "The favorites of BANK D, in the order of the FAVORITE buttons")]
     #[validate]
     bank_d: Bank,
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[serde(skip_serializing_if="Bits::is_zero", default="Bits::<6>::zero")]
     unused: Bits<6>
 }
 
 impl Favorites {
+    pub const BANK_LETTERS: [char; 4] = ['A', 'B', 'C', 'D'];
+
     pub fn all_banks(&self) -> Vec<&Bank> {
         vec![
             &self.bank_a,
@@ -38,6 +50,16 @@ impl Favorites {
             &self.bank_d
         ]
     }
+
+    pub fn bank_mut(&mut self, letter: char) -> Option<&mut Bank> {
+        match letter.to_ascii_uppercase() {
+            'A' => Some(&mut self.bank_a),
+            'B' => Some(&mut self.bank_b),
+            'C' => Some(&mut self.bank_c),
+            'D' => Some(&mut self.bank_d),
+            _ => None
+        }
+    }
 }
 
 impl Bytes<76> for Favorites {
@@ -99,7 +121,7 @@ impl Json for Favorites {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
@@ -109,17 +131,27 @@ impl Json for Favorites {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Bank {
+    #[schemars(title = "Favorites", description = "The live set registered to each FAVORITE button (1-10) of this bank")]
     #[validate]
     favorites: [Favorite; Self::USED_FAVORITES],
+    #[schemars(title = "Unused", description = "Not used by the RD300NX, but kept so that the RDS file is unchanged")]
     #[validate]
     unused_favorites: [Favorite; Self::FAVORITES_PER_BANK - Self::USED_FAVORITES]
 }
 
 impl Bank {
-    const USED_FAVORITES: usize = 6;
+    pub const USED_FAVORITES: usize = 6;
     const FAVORITES_PER_BANK: usize = 10;
     const BITS_SIZE: usize = Favorite::BITS_SIZE * Self::FAVORITES_PER_BANK;
 
+    pub fn used_favorites(&self) -> &[Favorite; Self::USED_FAVORITES] {
+        &self.favorites
+    }
+
+    pub fn used_favorites_mut(&mut self) -> &mut [Favorite; Self::USED_FAVORITES] {
+        &mut self.favorites
+    }
+
     fn to_bits(&self) -> Result<Bits<{Self::BITS_SIZE}>, BytesError> {
         BitStream::write_fixed_bits(|bits| {
             for favorite in &self.favorites {
@@ -152,7 +184,9 @@ impl Bank {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct Favorite {
+    #[schemars(title = "Category", description = "Which group of live sets the favorite refers to (User, Piano or E. Piano)")]
     category: PatchCategory,
+    #[schemars(title = "Live Set Number", description = "The number of the live set within its category")]
     #[validate]
     live_set_number: OneIndexedU16
 }
@@ -160,6 +194,22 @@ pub struct Favorite {
 impl Favorite {
     const BITS_SIZE: usize = 14;
 
+    pub fn user_set(index: usize) -> Self {
+        Self {
+            category: PatchCategory::User,
+            live_set_number: (index as u16).into()
+        }
+    }
+
+    pub fn category(&self) -> PatchCategory {
+        self.category
+    }
+
+    pub fn live_set_index(&self) -> usize {
+        let number: u16 = self.live_set_number.into();
+        number as usize
+    }
+
     fn to_bits(&self) -> Result<Bits<{Self::BITS_SIZE}>, BytesError> {
         BitStream::write_fixed_bits(|bits| {
             bits.set_u8::<2>(self.category.into(), 0, 3)?;
@@ -4,6 +4,7 @@ use validator::Validate;
 use crate::bytes::{Bytes, BytesError, Bits, BitStream};
 use crate::json::{Json, StructuredJson, StructuredJsonError, serialize_chars_as_string};
 use crate::json::validation::valid_chars;
+use crate::json::canonical::to_canonical_json;
 use super::sum_to_zero;
 use self::common::Common;
 use self::compressor::Compressor;
@@ -11,40 +12,56 @@ use self::favorites::Favorites;
 use self::switch_assign::SwitchAssign;
 use self::v_link::VLink;
 
-mod favorites;
-mod common;
+pub mod favorites;
+pub mod common;
 mod v_link;
 mod switch_assign;
 mod compressor;
 
 #[derive(Serialize, Deserialize, JsonSchema, Validate)]
 pub struct System {
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<16>::unit")]
     padding1: Bits<16>, // 2 bytes padding
+    #[schemars(title = "System Common", description = "General system settings, such as tuning, pedals and tone remain")]
     #[validate]
     pub common: Common, // 10 bytes
+    #[schemars(title = "Checksum", description = "Checksum of the preceding system section, which is recalculated when encoding")]
     checksum1: SystemCheckSum, // 2 bytes checksum
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<16>::unit")]
     padding2: Bits<16>, // 2 bytes padding
     //#[validate]
+    #[schemars(title = "Compressor", description = "System compressor settings (not yet decoded)")]
     compressor: Compressor, // 14 bytes
+    #[schemars(title = "Checksum", description = "Checksum of the preceding system section, which is recalculated when encoding")]
     checksum2: SystemCheckSum, // 2 bytes checksum
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<16>::unit")]
     padding3: Bits<16>, // 2 bytes padding
     //#[validate]
+    #[schemars(title = "V-LINK", description = "V-LINK video control settings (not yet decoded)")]
     v_link: VLink, // 4 bytes
+    #[schemars(title = "Checksum", description = "Checksum of the preceding system section, which is recalculated when encoding")]
     checksum3: SystemCheckSum, // 2 bytes checksum
     // 2 bytes VLink checksum?
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<16>::unit")]
     padding4: Bits<16>, // 2 bytes padding
+    #[schemars(title = "Favorites", description = "The live sets registered to each bank of the FAVORITE buttons")]
     #[validate]
     favorites: Favorites, // 76 bytes
+    #[schemars(title = "Checksum", description = "Checksum of the preceding system section, which is recalculated when encoding")]
     checksum4: SystemCheckSum, // 2 bytes checksum
+    #[schemars(title = "Padding", description = "Fixed padding in the RDS file")]
     #[serde(skip_serializing_if="Bits::is_unit", default="Bits::<16>::unit")]
     padding5: Bits<16>, // 2 bytes padding
     //#[validate]
+    #[schemars(title = "Switch Assign", description = "Assignable switch settings (not yet decoded)")]
     switch_assign: SwitchAssign, // 20 bytes
+    #[schemars(title = "Checksum", description = "Checksum of the preceding system section, which is recalculated when encoding")]
     checksum5: SystemCheckSum, // 2 bytes checksum
+    #[schemars(title = "Hardware Version", description = "The firmware version of the keyboard which saved this file")]
     #[serde(deserialize_with = "serialize_chars_as_string::deserialize")]
     #[serde(serialize_with = "serialize_chars_as_string::serialize")]
     #[schemars(with = "serialize_chars_as_string::StringSchema::<16>")]
@@ -52,6 +69,20 @@ pub struct System {
     hardware_version: [char; 16] // 16 bytes
 }
 
+impl System {
+    pub fn favorites(&self) -> &Favorites {
+        &self.favorites
+    }
+
+    pub fn favorites_mut(&mut self) -> &mut Favorites {
+        &mut self.favorites
+    }
+
+    pub fn hardware_version(&self) -> String {
+        self.hardware_version.iter().collect::<String>().trim_end().to_string()
+    }
+}
+
 impl Bytes<160> for System {
     fn from_bytes(bytes: Box<[u8; Self::BYTE_SIZE]>) -> Result<Self, BytesError> {
         BitStream::read_fixed(bytes, |data| {
@@ -130,7 +161,7 @@ impl Json for System {
     }
 
     fn to_json(&self) -> String {
-        serde_json::to_string_pretty(&self).unwrap()
+        to_canonical_json(&self).unwrap()
     }
 
     fn from_json(json: String) -> Result<Self, serde_json::Error> {
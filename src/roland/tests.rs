@@ -1,6 +1,6 @@
 use std::{error::Error, fs, io::Read};
-use crate::{bytes::Bytes, json::Json};
-use super::{*, rd300nx::RD300NX};
+use crate::{bytes::Bytes, json::{Json, SplitDepth, SplitManifest, StructuredJson, UpdateSummary, lint::LintConfig, warnings::{self, Warning, Warnings}, keyboard::keyboard_map, tuning::{KeyboardMapping, ScalaScale, TuningSource, import_tuning, export_tuning, temperament_offsets}, canonical::to_canonical_json, format::FileFormat, migration::{bits_to_fields, migrate_structure, structure_to_value, upgrade_json, upgrade_structure, Migration, FORMAT_VERSION, VERSION_FIELD}, patch::LiveSetPatch, presets::{EffectPreset, PresetLibrary}, selection::SetSelection, set_list::SetList, sheet::{CueSheet, SheetFormat}, templates::{Templated, Templates}, transform::{transform, Edit, LiveSetFilter, TransformError}}};
+use super::{*, rd300nx::RD300NX, types::{effects::Effect, enums::{Layer, Temperament}, notes::{KeyNote, PianoKey}}};
 use schemars::schema_for;
 use test_case::test_case;
 use validator::Validate;
@@ -220,6 +220,921 @@ fn chorus_default_values(rds_filename: &str, user_set_count: usize) -> Result<()
     Ok(())
 }
 
+#[test_case("examples/rd300nx/MFX0-59.RDS", 23, "mfx", "Rate: 0.50 Hz", "0.05 Hz to 10.00 Hz")]
+#[test_case("examples/rd300nx/MFX0-59.RDS", 23, "mfx", "Balance: D50:50W", "D0:100W to D100:0W")]
+#[test_case("examples/rd300nx/MFX0-59.RDS", 23, "mfx", "Filter Type: HighPassFilter", "Off, LowPassFilter, HighPassFilter")]
+#[test_case("examples/rd300nx/MFX0-59.RDS", 43, "mfx", "Feedback: 20%", "-98% to 98%")]
+#[test_case("examples/rd300nx/MFX0-59.RDS", 43, "mfx", "Hf Damp: ByPass", "200 Hz to 8000 Hz, ByPass")]
+#[test_case("examples/rd300nx/CHO-REV-DFLTS.RDS", 1, "reverb", "Pre Delay: 5.0 ms", "0.0 ms to 100.0 ms")]
+#[test_case("examples/rd300nx/CHO-REV-DFLTS.RDS", 1, "chorus", "Phase: 180 deg", "0 deg to 180 deg")]
+#[test_case("examples/rd300nx/CHO-REV-DFLTS.RDS", 1, "chorus", "Depth: 40", "0 to 127")]
+fn describe_effect_parameters(rds_filename: &str, user_set: usize, effect: &str, expected: &str, range: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let ls = &rds.user_sets[user_set];
+    let parameters = match effect {
+        "mfx" => ls.mfx.mfx_type.describe(),
+        "reverb" => ls.reverb.reverb_type.describe(),
+        _ => ls.chorus.chorus_type.describe()
+    };
+    let found = parameters.iter().find(|p| p.to_string() == expected).ok_or(format!("{} not found in {:?}", expected, parameters))?;
+    assert_eq!(found.range, range);
+    Ok(())
+}
+
+#[test_case(Effect::Mfx, None, "SympatheticResonance")]
+#[test_case(Effect::Mfx, Some("tapeecho"), "TapeEcho")]
+#[test_case(Effect::Reverb, None, "Hall")]
+#[test_case(Effect::Reverb, Some("Gm2Reverb"), "Gm2Reverb")]
+#[test_case(Effect::Chorus, Some("delay"), "Delay")]
+fn reset_effect_to_defaults(effect: Effect, type_name: Option<&str>, expected_type: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let ls = &mut rds.user_sets[1];
+    assert_eq!(ls.reset_effect(effect, type_name)?, expected_type);
+    let (found, expected) = match effect {
+        Effect::Mfx => (ls.mfx.mfx_type.parameters().to_vec(), ls.mfx.mfx_type.default().parameters().to_vec()),
+        Effect::Reverb => (ls.reverb.reverb_type.parameters().to_vec(), ls.reverb.reverb_type.default().parameters().to_vec()),
+        Effect::Chorus => (ls.chorus.chorus_type.parameters().to_vec(), ls.chorus.chorus_type.default().parameters().to_vec())
+    };
+    assert_eq!(found, expected);
+    assert!(rds.validate().is_ok());
+    assert!(rds.user_sets[1].reset_effect(effect, Some("UnusedVrChorus")).is_err());
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", SplitDepth::Set)]
+#[test_case("examples/rd300nx/SB.RDS", SplitDepth::Section)]
+#[test_case("examples/rd300nx/SB.RDS", SplitDepth::Full)]
+fn split_merge_at_depth(rds_filename: &str, depth: SplitDepth) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.clone().try_into().unwrap())?;
+    let merged = rd300nx::RD300NX::from_structured_json(rds.to_structured_json_at_depth(depth))?;
+    assert_eq!(rds_bytes, merged.to_bytes()?.to_vec());
+    Ok(())
+}
+
+#[test]
+fn split_edit_then_update() -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let folder = std::env::temp_dir().join("roland-rds-split-update");
+    let _ = fs::remove_dir_all(&folder);
+    rds.to_structured_json_at_depth(SplitDepth::Section).save(folder.clone(), FileFormat::Json)?;
+    SplitManifest::new(SplitDepth::Section, FileFormat::Json).save(&folder)?;
+    fs::write(folder.join(LintConfig::FILE_NAME), "[rules]\n")?;
+    let old_set = folder.join("user_sets").join("01-HarpinG");
+    fs::write(old_set.join("notes.txt"), "not part of the structure")?;
+    rds.user_sets[0].common.set_name("Renamed");
+    let volume = rds.user_sets[1].layers[0].internal.volume();
+    rds.user_sets[1].layers[0].internal.set_volume(if volume == 100 { 90 } else { 100 });
+    let mut summary = UpdateSummary::default();
+    rds.to_structured_json_at_depth(SplitDepth::Section).update(folder.clone(), FileFormat::Json, &mut summary)?;
+    let new_set = folder.join("user_sets").join("01-Renamed");
+    assert!(!summary.added.is_empty() && summary.added.iter().all(|p| p.starts_with(&new_set)), "{:?}", summary.added);
+    assert!(!summary.removed.is_empty() && summary.removed.iter().all(|p| p.starts_with(&old_set)), "{:?}", summary.removed);
+    assert_eq!(summary.changed.len(), 1, "{:?}", summary.changed);
+    assert!(summary.changed[0].starts_with(folder.join("user_sets").join("02-ConcertGrand")));
+    assert!(folder.join(".split.json").is_file());
+    assert!(folder.join(LintConfig::FILE_NAME).is_file());
+    assert!(old_set.join("notes.txt").is_file());
+    let merged = RD300NX::from_structured_json(StructuredJson::load(folder.clone(), FileFormat::Json)?)?;
+    assert_eq!(merged.to_bytes()?, rds.to_bytes()?);
+    let mut again = UpdateSummary::default();
+    rds.to_structured_json_at_depth(SplitDepth::Section).update(folder.clone(), FileFormat::Json, &mut again)?;
+    assert_eq!(again.added.len() + again.changed.len() + again.removed.len(), 0);
+    fs::remove_dir_all(&folder)?;
+    Ok(())
+}
+
+#[test_case("slots-ok", &["01-First", "2", "3-Third"], None ; "ok")]
+#[test_case("slots-missing", &["1", "3"], Some("MissingSlots([2])") ; "missing")]
+#[test_case("slots-extra", &["1", "2", "3", "4-Extra"], Some(r#"ExtraSlots(["4-Extra"])"#) ; "extra")]
+#[test_case("slots-duplicate", &["1", "2-First", "2-Second", "3"], Some("DuplicateSlot(2)") ; "duplicate")]
+#[test_case("slots-invalid", &["1", "2", "3", "Extra"], Some(r#"InvalidSlotName("Extra")"#) ; "invalid")]
+fn load_numbered_slots(folder_name: &str, names: &[&str], expected_error: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let folder = std::env::temp_dir().join(format!("roland-rds-{}", folder_name));
+    fs::create_dir_all(&folder)?;
+    for name in names {
+        fs::write(folder.join(format!("{}.json", name)), live_set::LiveSet::blank().to_json())?;
+    }
+    let result = StructuredJson::load(folder.clone(), FileFormat::Json)?.to_array::<live_set::LiveSet, 3>();
+    fs::remove_dir_all(&folder)?;
+    match (result, expected_error) {
+        (Ok(_), None) => {},
+        (Err(e), Some(expected)) => assert_eq!(format!("{:?}", e), expected),
+        (Ok(_), Some(expected)) => panic!("Expected {} but loaded successfully", expected),
+        (Err(e), None) => return Err(e.into())
+    }
+    Ok(())
+}
+
+#[test]
+fn renumber_folder() -> Result<(), Box<dyn Error>> {
+    let folder = std::env::temp_dir().join("roland-rds-renumber");
+    fs::create_dir_all(folder.join("5-Set"))?;
+    fs::write(folder.join(".split.json"), "{}")?;
+    fs::write(folder.join("01.json"), "\"first\"")?;
+    fs::write(folder.join("1.json"), "\"second\"")?; // renamed to 2.json, after 01.json is renamed to 1.json
+    fs::write(folder.join("notes.json"), "\"not numbered\"")?;
+    fs::write(folder.join("5-Set").join("2.json"), "\"inner\"")?;
+    let mut unnumbered = Vec::new();
+    let count = StructuredJson::renumber(folder.clone(), FileFormat::Json, &mut unnumbered)?;
+    let mut files: Vec<String> = fs::read_dir(&folder)?.map(|e| e.unwrap().file_name().to_string_lossy().to_string()).collect();
+    files.sort();
+    assert_eq!(files, vec![".split.json", "1.json", "2.json", "3-Set", "notes.json"]);
+    assert_eq!(fs::read_to_string(folder.join("1.json"))?, "\"first\"");
+    assert_eq!(fs::read_to_string(folder.join("2.json"))?, "\"second\"");
+    assert_eq!(fs::read_to_string(folder.join("3-Set").join("1.json"))?, "\"inner\"");
+    assert_eq!(count, 4);
+    assert_eq!(unnumbered, vec![folder.join("notes.json")]);
+    fs::remove_dir_all(&folder)?;
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", "1-60")]
+#[test_case("examples/rd300nx/SOUL-V3.RDS", "5-12,piano/1-3")]
+fn extract_and_resolve_template(rds_filename: &str, sets: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.clone().try_into().unwrap())?;
+    let flat = serde_json::to_value(&rds)?;
+    let mut templated = Templated::default();
+    let sets: SetSelection = sets.parse()?;
+    templated.extract_template(&flat, &sets, "test".to_string());
+    let mut value = flat.clone();
+    templated.unresolve(&mut value);
+    assert_ne!(flat, value);
+    let resolved = Templated::resolve(&mut value, templated.templates)?;
+    assert_eq!(resolved.sets.len(), sets.sets().len());
+    assert_eq!(flat, value);
+    let merged = RD300NX::from_json(value.to_string())?;
+    assert_eq!(rds_bytes, merged.to_bytes()?.to_vec());
+    Ok(())
+}
+
+#[test_case(r#"{"common":{"key_touch_curve_type":"Heavy"}}"#, true)]
+#[test_case(r#"[{"op":"replace","path":"/common/key_touch_curve_offset","value":3}]"#, true)]
+#[test_case(r#"[{"op":"replace","path":"/common/unknown/field","value":3}]"#, false)]
+#[test_case(r#"{"common":{"key_touch_curve_offset":"Heavy"}}"#, false)]
+#[test_case(r#"{"common":{"key_touch_curve_offset":99}}"#, false)]
+fn apply_live_set_patch(patch_json: &str, expect_ok: bool) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let patch = LiveSetPatch::from_json(patch_json)?;
+    let result = patch.apply(&rds.user_sets[0]);
+    assert_eq!(result.is_ok(), expect_ok);
+    if let Ok(patched) = result {
+        assert!(patched.validate().is_ok());
+        assert_ne!(patched.to_bytes()?, rds.user_sets[0].to_bytes()?);
+    }
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/BLANK.RDS", RD300NX::blank())]
+#[test_case("examples/rd300nx/DEFAULT.RDS", RD300NX::factory_default())]
+fn factory_defaults_match_files(rds_filename: &str, rds: RD300NX) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    assert_eq!(rds.to_bytes()?.to_vec(), rds_bytes);
+    assert_eq!(live_set::LiveSet::blank().to_bytes()?, rds.user_sets[0].to_bytes()?);
+    assert_eq!(live_set::LiveSet::default().common.to_bytes()?, rds.user_sets[0].common.to_bytes()?);
+    assert_eq!(layers::InternalLayer::default().to_bytes()?, rds.user_sets[0].layers[0].internal.to_bytes()?);
+    Ok(())
+}
+
+#[test]
+fn build_live_set() -> Result<(), Box<dyn Error>> {
+    let strings = tones::ToneQuery { name: Some("Strings".to_string()), ..Default::default() }.search()[0].0;
+    let built = live_set::LiveSet::builder()
+        .name("BALLAD")
+        .tempo(72)
+        .split(true)
+        .layer(Layer::Upper1, |l| l.tone(tones::ToneNumber::from(2)).range(PianoKey::C4, PianoKey::C8))
+        .layer(Layer::Lower1, |l| l.tone(strings).range(PianoKey::A0, PianoKey::B3).volume(90).transpose(-12))
+        .build()?;
+    assert!(built.validate().is_ok());
+    assert_eq!(built.common.name(), "BALLAD");
+    assert_eq!(built.common.live_set_tempo(), 72);
+    assert_eq!(built.layers[0].tone.tone_number, tones::ToneNumber::from(2));
+    assert_eq!(built.layers[0].internal.key_range(true), Some((PianoKey::C4, PianoKey::C8)));
+    assert!(!built.layers[1].internal.enable());
+    assert_eq!(built.layers[2].tone.tone_number, strings);
+    assert_eq!(built.layers[2].internal.key_range(true), Some((PianoKey::A0, PianoKey::B3)));
+    assert_eq!(built.layers[2].internal.volume(), 90);
+    assert_eq!(built.layers[2].internal.transpose_semitones(), -12);
+    // round trips through bytes unchanged
+    assert_eq!(live_set::LiveSet::from_bytes(built.to_bytes()?)?.to_bytes()?, built.to_bytes()?);
+    Ok(())
+}
+
+#[test]
+fn build_invalid_live_set() {
+    let errors = live_set::LiveSet::builder()
+        .name("A NAME LONGER THAN 16")
+        .layer(Layer::Upper2, |l| l.volume(200).velocity_range(100, 10))
+        .layer(Layer::UnusedLower2, |l| l.volume(1))
+        .build().unwrap_err();
+    assert!(errors.contains("longer than 16 characters"));
+    assert!(errors.contains("layers: Boxed array element(s) failed validation (ArrayIndicies=[1]"));
+    assert!(errors.contains(r#""volume":[{"code":"range""#));
+    assert!(errors.contains("UnusedLower2"));
+}
+
+#[test_case(&["volume-=6"], "", 1, "layers.Upper1.internal.volume: 127 -> 121")]
+#[test_case(&["volume+=100", "tempo=90"], "--sets 1-5", 5, "common.live_set_tempo: 120 -> 90")]
+#[test_case(&["tone=1:Honky-tonk1"], "--bank piano", 1, "layers.Upper1.piano.tone_number: \"1_ConcertGrand\" -> \"2_Honky-tonk1\"")]
+#[test_case(&["key_touch_velocity=64"], "--name ^Concert", 2, "common.key_touch_velocity: \"Real\" -> {\"Fixed\":64}")]
+fn transform_live_sets(edits: &[&str], filter_arg: &str, min_changed: usize, expected_change: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds = RD300NX::factory_default();
+    let mut filter = LiveSetFilter::default();
+    match filter_arg.split_once(' ') {
+        Some(("--sets", sets)) => filter.sets = Some(sets.parse()?),
+        Some(("--bank", bank)) => filter.set_bank(bank)?,
+        Some(("--name", pattern)) => filter.set_name(pattern)?,
+        _ => {}
+    }
+    let edits = edits.iter().map(|e| e.parse()).collect::<Result<Vec<Edit>, _>>()?;
+    let changed = transform(&mut rds, &filter, &edits)?;
+    assert!(changed.len() >= min_changed);
+    assert!(changed.iter().all(|c| filter.matches(c.bank, c.index, rds.live_set(c.bank, c.index).unwrap())));
+    assert!(changed.iter().any(|c| c.changes.iter().any(|change| change == expected_change)));
+    assert!(rds.validate().is_ok());
+    Ok(())
+}
+
+#[test]
+fn transform_invalid_edits() -> Result<(), Box<dyn Error>> {
+    assert!("volume".parse::<Edit>().is_err());
+    assert!("volume+=loud".parse::<Edit>().is_err());
+    assert!("tone+=1".parse::<Edit>().is_err());
+    assert!("tone=Piano:1".parse::<Edit>().is_err()); // matches too many tones
+    assert!("pitch=1".parse::<Edit>().is_err());
+    let mut rds = RD300NX::blank();
+    let filter = LiveSetFilter { sets: Some("1-3".parse()?), ..Default::default() };
+    match transform(&mut rds, &filter, &["key_touch_velocity=0".parse()?]) {
+        Err(TransformError::FailedValidation(sets)) => assert_eq!(sets.len(), 3),
+        _ => panic!("Expected validation to fail")
+    }
+    Ok(())
+}
+
+#[test_case("1", 1)]
+#[test_case("concertgrand", 1)]
+#[test_case("Honky-tonk1", 2)]
+fn find_tone_number(name: &str, expected: u16) -> Result<(), Box<dyn Error>> {
+    assert_eq!(name.parse::<tones::ToneNumber>()?, tones::ToneNumber::from(expected));
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS")]
+#[test_case("examples/rd300nx/DEFAULT.RDS")]
+fn accessors_match_json(rds_filename: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    for live_set in rds.all_live_sets() {
+        let json = serde_json::to_value(live_set)?;
+        assert_eq!(live_set.common.name(), json["common"]["name"].as_str().unwrap().trim_end());
+        assert_eq!(live_set.common.live_set_tempo() as u64, json["common"]["live_set_tempo"].as_u64().unwrap());
+        assert_eq!(live_set.reverb.depth() as u64, json["reverb"]["depth"].as_u64().unwrap());
+        for (i, layer) in live_set.layers.iter().enumerate() {
+            let internal = &json["layers"][i]["internal"];
+            assert_eq!(layer.internal.volume() as u64, internal["volume"].as_u64().unwrap());
+            assert_eq!(layer.internal.enable(), internal["enable"].as_bool().unwrap());
+            assert_eq!(layer.internal.velocity_sensitivity() as i64, internal["velocity_sensitivity"].as_i64().unwrap());
+            assert_eq!(layer.tone.cutoff() as i64, json["layers"][i]["tone"]["cutoff"].as_i64().unwrap());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn reattach_metadata_after_reordering() -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut previous = rd300nx::RD300NX::from_bytes(rds_bytes.clone().try_into().unwrap())?;
+    let metadata = |song: &str| Some(live_set::metadata::LiveSetMetadata { song: Some(song.to_string()), ..Default::default() });
+    previous.user_sets[0].metadata = metadata("Harp");
+    previous.user_sets[4].metadata = metadata("Bells");
+    previous.user_sets[7].metadata = metadata("Keys");
+    // re-decode after moving set 1 to 6 on the keyboard, and changing the volume of set 8 (which keeps its name)
+    let mut current = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    current.user_sets[0..6].rotate_left(1);
+    current.user_sets[7].layers[0].internal.set_volume(1);
+    let previous = RD300NX::from_json(previous.to_json())?;
+    assert_eq!(current.reattach_metadata(&previous)?, 3);
+    let song = |ls: &live_set::LiveSet| ls.metadata.as_ref().and_then(|m| m.song.clone());
+    assert_eq!(song(&current.user_sets[5]).as_deref(), Some("Harp")); // by content
+    assert_eq!(song(&current.user_sets[3]).as_deref(), Some("Bells")); // by content
+    assert_eq!(song(&current.user_sets[7]).as_deref(), Some("Keys")); // by name
+    assert_eq!(current.user_sets.iter().filter(|ls| ls.metadata.is_some()).count(), 3);
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS")]
+#[test_case("examples/rd300nx/MFX0-59_MAX.RDS")]
+#[test_case("examples/rd300nx/PIANO.RDS")]
+fn canonical_json_is_stable(rds_filename: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let json = rds.to_json();
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&json)?, serde_json::to_value(&rds)?);
+    assert_eq!(RD300NX::from_json(json.clone())?.to_json(), json);
+    assert!(json.lines().count() < serde_json::to_string_pretty(&rds)?.lines().count());
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", FileFormat::Yaml)]
+#[test_case("examples/rd300nx/SB.RDS", FileFormat::Toml)]
+#[test_case("examples/rd300nx/MFX60-78_MAX.RDS", FileFormat::Yaml)]
+#[test_case("examples/rd300nx/MFX60-78_MAX.RDS", FileFormat::Toml)]
+fn other_file_formats(rds_filename: &str, format: FileFormat) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.clone().try_into().unwrap())?;
+    let text = format.from_json(&rds.to_json())?;
+    let decoded = RD300NX::from_json(format.to_json(&text)?)?;
+    assert_eq!(rds_bytes, decoded.to_bytes()?.to_vec());
+    // split files are not always objects (eg. unused bits)
+    let structure = rds.to_structured_json_at_depth(SplitDepth::Full);
+    let converted = structured_in_format(structure, format)?;
+    assert_eq!(rds_bytes, RD300NX::from_structured_json(converted)?.to_bytes()?.to_vec());
+    Ok(())
+}
+
+fn structured_in_format(structured_json: StructuredJson, format: FileFormat) -> Result<StructuredJson, Box<dyn Error>> {
+    Ok(match structured_json {
+        StructuredJson::SingleJson(json) => StructuredJson::SingleJson(format.to_json(&format.from_json(&json)?)?),
+        StructuredJson::NestedCollection(vec) => StructuredJson::NestedCollection(vec.into_iter()
+            .map(|(name, s)| Ok((name, structured_in_format(s, format)?)))
+            .collect::<Result<_, Box<dyn Error>>>()?)
+    })
+}
+
+#[test_case(FileFormat::Yaml)]
+#[test_case(FileFormat::Toml)]
+fn templates_in_other_formats(format: FileFormat) -> Result<(), Box<dyn Error>> {
+    let folder = std::env::temp_dir().join(format!("roland-rds-templates-{}", format.extension()));
+    let output = Some(folder.join(format!("rds.{}", format.extension())).to_string_lossy().to_string());
+    let template = serde_json::to_value(live_set::LiveSet::blank())?;
+    let mut templates = Templates::default();
+    templates.insert("piano".to_string(), template.clone());
+    assert_eq!(templates.save(&output)?, 1);
+    assert!(folder.join("templates").join(format!("piano.{}", format.extension())).is_file());
+    assert_eq!(templates.save(&output)?, 0); // already exists
+    let found = Templates::find(&output)?;
+    fs::remove_dir_all(&folder)?;
+    assert_eq!(found.get("piano"), Some(&template));
+    Ok(())
+}
+
+#[test]
+fn yaml_comments_are_ignored() -> Result<(), Box<dyn Error>> {
+    let yaml = "# the tempo of the song\nlive_set_tempo: 96 # slower than the recording\nsplit_switch_internal: true\n";
+    let json: serde_json::Value = serde_json::from_str(&FileFormat::Yaml.to_json(yaml)?)?;
+    assert_eq!(json, serde_json::json!({ "live_set_tempo": 96, "split_switch_internal": true }));
+    assert_eq!(FileFormat::for_file(&Some("show.YML".into())), FileFormat::Yaml);
+    assert_eq!(FileFormat::for_file(&Some("show.rds".into())), FileFormat::Json);
+    Ok(())
+}
+
+#[test_case(r#""Café — Für Elise""# ; "string")]
+#[test_case(r#"[0, 1, 1, 0]"# ; "array")]
+#[test_case(r#"{"value": [0, 1]}"# ; "table with a value key")]
+#[test_case(r#"{"value": {"depth": 64}}"# ; "table with a value table")]
+fn toml_values_round_trip(json: &str) -> Result<(), Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    let toml = FileFormat::Toml.from_json(json)?;
+    assert_eq!(FileFormat::Toml.to_value(&toml)?, value, "{}", toml);
+    Ok(())
+}
+
+#[test_case(r#"{"a": [1, 2, 3], "b": {"Fixed": 64}, "c": []}"#, "{\n  \"a\": [1, 2, 3],\n  \"b\": { \"Fixed\": 64 },\n  \"c\": []\n}" ; "short values inline")]
+#[test_case(r#"{"a": {"b": {"c": 1}}}"#, "{\n  \"a\": {\n    \"b\": { \"c\": 1 }\n  }\n}" ; "nested objects")]
+#[test_case(r#"[-0.0, 0.1, 12.5]"#, "[0.0, 0.1, 12.5]" ; "floats")]
+#[test_case(&format!("[{}]", vec!["100"; 40].join(",")), &format!("[\n  {},\n  {}\n]", vec!["100"; 23].join(", "), vec!["100"; 17].join(", ")) ; "long numeric array wrapped")]
+fn canonical_json_format(json: &str, expected: &str) -> Result<(), Box<dyn Error>> {
+    let value: serde_json::Value = serde_json::from_str(json)?;
+    assert_eq!(to_canonical_json(&value)?, expected);
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", "build_set_list.yaml", "sets:\n  1: patch.json\n  2: { patch: patch.json, tone_remain: true, tags: [fav] }\n  3: { patch: patch.json, tone_remain: true, name: Third }\n  5: { patch: patch.json, tags: [fav] }\nfavorites:\n  C: fav\n" ; "yaml")]
+#[test_case("examples/rd300nx/SB.RDS", "build_set_list.json", r#"{"sets": {"1": "patch.json", "2": {"patch": "patch.json", "tone_remain": true, "tags": ["fav"]}, "3": {"patch": "patch.json", "tone_remain": true, "name": "Third"}, "5": {"patch": "patch.json", "tags": ["fav"]}}, "favorites": {"C": "fav"}}"# ; "json")]
+fn build_from_set_list(rds_filename: &str, set_list_filename: &str, set_list: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let folder = std::env::temp_dir().join(format!("roland-rds-{}", set_list_filename));
+    fs::create_dir_all(&folder)?;
+    fs::write(folder.join("patch.json"), rds.user_sets[0].to_json())?;
+    fs::write(folder.join(set_list_filename), set_list)?;
+    let built = SetList::load(&folder.join(set_list_filename))?.build(RD300NX::blank(), &folder)?;
+    assert!(built.validate().is_ok());
+    assert_eq!(built.user_sets[1].to_bytes()?, rds.user_sets[0].to_bytes()?);
+    assert_eq!(built.user_sets[2].name_string(), "Third           ");
+    assert!(built.user_sets[3].has_default_name());
+    let transitions: Vec<usize> = built.tone_remain_report().iter().filter(|t| t.bank == "User").map(|t| t.from_number).collect();
+    assert_eq!(transitions, vec![1, 2]);
+    let favorites: Vec<usize> = built.system.favorites().all_banks()[2].used_favorites().iter().take(2).map(|f| f.live_set_index()).collect();
+    assert_eq!(favorites, vec![1, 4]);
+    fs::remove_dir_all(&folder)?;
+    Ok(())
+}
+
+#[test_case(&["Same", "Same", "Other", "Same"], &["User #1, #2, #4: Live sets share the name 'Same'"] ; "duplicates")]
+#[test_case(&["Same", "Same  ", "Other"], &["User #1, #2: Live sets share the name 'Same'"] ; "trailing spaces ignored")]
+#[test_case(&["", "", live_set::LiveSet::DEFAULT_NAME, live_set::LiveSet::DEFAULT_NAME], &[] ; "blank and default names excluded")]
+fn lint_duplicate_names(names: &[&str], expected: &[&str]) {
+    let mut rds = RD300NX::blank();
+    for (ls, name) in rds.user_sets.iter_mut().zip(names) {
+        ls.common.set_name(name);
+    }
+    let warnings: Vec<String> = warnings::duplicate_name_warnings("User", &rds.user_sets[..names.len()]).iter().map(|w| w.to_string()).collect();
+    assert_eq!(warnings, expected);
+}
+
+#[test]
+fn lint_identical_live_sets() {
+    let mut rds = RD300NX::blank();
+    rds.user_sets[0].common.set_name("Same");
+    rds.user_sets[1].common.set_name("Same");
+    rds.user_sets[2].common.set_name("Other");
+    rds.user_sets[3] = live_set::LiveSet::from_bytes(rds.piano[1].to_bytes().unwrap()).unwrap();
+    let warnings: Vec<String> = warnings::identical_live_set_warnings(&rds.user_sets[..4], &rds.piano[..], &rds.e_piano[..]).iter().map(|w| w.to_string()).collect();
+    assert_eq!(warnings, vec![
+        "User #1, #2: Live sets are identical".to_string(),
+        "User #4: Live set is identical to Piano #2".to_string()
+    ]);
+    // unused live sets are expected to be identical (to each other, and to Piano #1)
+    assert!(warnings::identical_live_set_warnings(&RD300NX::blank().user_sets[..], &[], &[]).is_empty());
+}
+
+#[test]
+fn lint_favorites_pointing_at_unused_sets() {
+    let mut rds = RD300NX::blank();
+    rds.user_sets[0].common.set_name("Named");
+    rds.user_sets[1].common.set_name("");
+    let favorites = rds.system.favorites_mut().bank_mut('B').unwrap().used_favorites_mut();
+    favorites[0] = system::favorites::Favorite::user_set(0);
+    favorites[1] = system::favorites::Favorite::user_set(1);
+    favorites[2] = system::favorites::Favorite::user_set(2);
+    favorites[3] = system::favorites::Favorite::user_set(3);
+    let user_sets = &rds.user_sets[..3]; // so that User #4 does not exist
+    let warnings: Vec<String> = warnings::favorite_warnings(rds.system.favorites(), user_sets).iter()
+        .map(|w| w.to_string())
+        .filter(|w| w.starts_with("Favorite B"))
+        .collect();
+    assert_eq!(warnings, vec![
+        "Favorite B2: Points to User #2 which has a blank name",
+        "Favorite B3: Points to User #3 which still has the default name",
+        "Favorite B4: Points to User #4 which does not exist"
+    ]);
+}
+
+#[test_case("", &[], 3 ; "default config")]
+#[test_case("[rules]\nduplicate_name = \"off\"\n", &[], 2 ; "rule turned off")]
+#[test_case("[rules]\nduplicate_name = \"error\"\nfavorite_unused = \"error\"\n", &["User #1, #2: Live sets share the name 'Same'", "Favorite B1: Points to User #3 which still has the default name"], 1 ; "rules promoted to errors")]
+fn lint_config_severity(config: &str, expected_errors: &[&str], expected_warnings: usize) -> Result<(), Box<dyn Error>> {
+    let mut rds = RD300NX::blank();
+    rds.user_sets[0].common.set_name("Same");
+    rds.user_sets[1].common.set_name("Same");
+    *rds.system.favorites_mut().bank_mut('B').unwrap().used_favorites_mut().first_mut().unwrap() = system::favorites::Favorite::user_set(2);
+    let warnings: Vec<String> = rds.warnings().iter().map(|w| w.to_string()).collect();
+    for expected in ["User #1, #2: Live sets share the name 'Same'", "User #1, #2: Live sets are identical", "Favorite B1: Points to User #3 which still has the default name"] {
+        assert!(warnings.iter().any(|w| w == expected), "{} not found in {:?}", expected, warnings);
+    }
+    let folder = std::env::temp_dir().join(format!("roland-rds-lint-{}", expected_warnings));
+    fs::create_dir_all(&folder)?;
+    let json_file = folder.join("lint.json");
+    fs::write(&json_file, rds.to_json())?;
+    if !config.is_empty() {
+        fs::write(folder.join(LintConfig::FILE_NAME), config)?;
+    }
+    let lint = LintConfig::find(&Some(json_file.to_string_lossy().to_string()))?.apply(rds.warnings());
+    fs::remove_dir_all(&folder)?;
+    // only the warnings set up here are counted, as RD300NX::blank() has others (eg. Favorite A2 points to User #1)
+    let covered = |w: &&Warning| w.to_string().starts_with("User #1, #2") || w.to_string().starts_with("Favorite B1:");
+    assert_eq!(lint.warnings.iter().filter(covered).count(), expected_warnings);
+    match lint.check() {
+        Ok(_) => assert!(expected_errors.is_empty()),
+        Err(failed) => {
+            let errors: Vec<String> = failed.errors.iter().filter(covered).map(|e| e.to_string()).collect();
+            assert_eq!(errors, expected_errors);
+            assert_eq!(failed.to_string(), format!("Lint failed with {} errors.", failed.errors.len()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn build_from_templated_patches_in_other_formats() -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let folder = std::env::temp_dir().join("roland-rds-build-templated");
+    fs::create_dir_all(folder.join("templates"))?;
+    fs::write(folder.join("templates").join("harp.json"), rds.user_sets[0].to_json())?;
+    fs::write(folder.join("harp.yaml"), "base: template:harp\ncommon:\n  name: \"Templated Harp  \"\n")?;
+    fs::write(folder.join("copy.toml"), format!("{} = {}\nbase = \"user_sets/1\"\n", VERSION_FIELD, FORMAT_VERSION))?;
+    fs::write(folder.join("set_list.yaml"), "sets:\n  1: harp.yaml\n  2: copy.toml\n")?;
+    let built = SetList::load(&folder.join("set_list.yaml"))?.build(RD300NX::blank(), &folder)?;
+    fs::remove_dir_all(&folder)?;
+    assert_eq!(built.user_sets[0].name_string(), "Templated Harp  ");
+    assert_eq!(built.user_sets[1].to_bytes()?, built.user_sets[0].to_bytes()?);
+    assert_eq!(built.user_sets[0].layers[0].internal.volume(), rds.user_sets[0].layers[0].internal.volume());
+    Ok(())
+}
+
+#[test]
+fn build_non_ascii_song_then_validate() -> Result<(), Box<dyn Error>> {
+    let folder = std::env::temp_dir().join("roland-rds-non-ascii");
+    fs::create_dir_all(&folder)?;
+    fs::write(folder.join("patch.json"), live_set::LiveSet::blank().to_json())?;
+    fs::write(folder.join("set_list.yaml"), "sets:\n  1: { patch: patch.json, song: Café — Für Elise, tags: [naïve] }\n")?;
+    let built = SetList::load(&folder.join("set_list.yaml"))?.build(RD300NX::blank(), &folder)?;
+    fs::write(folder.join("built.json"), built.to_json().into_bytes())?;
+    let text = String::from_utf8(fs::read(folder.join("built.json"))?)?;
+    let read = RD300NX::from_json(text)?;
+    assert!(read.validate().is_ok());
+    let metadata = read.user_sets[0].metadata.as_ref().unwrap();
+    assert_eq!(metadata.song.as_deref(), Some("Café — Für Elise"));
+    assert_eq!(metadata.tags, vec!["naïve".to_string()]);
+    fs::remove_dir_all(&folder)?;
+    Ok(())
+}
+
+// reverb sends only matter to held notes while the reverb (of the set being left) is on
+#[test_case(1, &["Layer[Upper1] volume changes from 100 to 80", "Layer[Upper1] pan moves", "Layer[Upper1] reverb level changes from 40 to 60"] ; "reverb on")]
+#[test_case(0, &["Layer[Upper1] volume changes from 100 to 80", "Layer[Upper1] pan moves"] ; "reverb off")]
+fn tone_remain_report_lists_every_issue(reverb_type: u8, expected: &[&str]) -> Result<(), Box<dyn Error>> {
+    let mut rds = RD300NX::blank();
+    rds.system.common.tone_remain = types::metadata::ToneRemain::Always(true);
+    for (i, (volume, pan, reverb)) in [(100, types::enums::Pan::Centre, 40), (80, types::enums::Pan::Left(20), 60)].into_iter().enumerate() {
+        let live_set = &mut rds.user_sets[i];
+        live_set.reverb.reverb_type = types::effects::reverb::ReverbType::from(reverb_type, [Default::default(); 20]);
+        let layer = &mut live_set.layers[0].internal;
+        layer.set_enable(true);
+        layer.set_key_range(PianoKey::A0, PianoKey::C8);
+        layer.set_volume(volume);
+        layer.set_pan(pan);
+        layer.reverb = reverb;
+    }
+    let report = rds.tone_remain_report();
+    let transition = report.iter().find(|t| t.bank == "User" && t.from_number == 1).unwrap();
+    assert!(!transition.is_seamless());
+    let reasons: Vec<&str> = transition.issues.iter().map(|i| i.reason.as_str()).collect();
+    assert_eq!(reasons.len(), expected.len(), "{:?}", reasons);
+    for (reason, expected) in reasons.iter().zip(expected) {
+        assert!(reason.starts_with(expected), "{} should start with {}", reason, expected);
+    }
+    assert_eq!(transition.warnings().len(), expected.len());
+    assert!(report.iter().find(|t| t.bank == "User" && t.from_number == 3).unwrap().is_seamless());
+    Ok(())
+}
+
+#[test]
+fn tone_remain_report_layer_turning_off() {
+    let mut rds = RD300NX::blank();
+    rds.system.common.tone_remain = types::metadata::ToneRemain::Always(true);
+    for (i, enable) in [true, false].into_iter().enumerate() {
+        let layer = &mut rds.user_sets[i].layers[0].internal;
+        layer.set_enable(enable);
+        layer.set_key_range(PianoKey::A0, PianoKey::C8);
+    }
+    let report = rds.tone_remain_report();
+    let transition = report.iter().find(|t| t.bank == "User" && t.from_number == 1).unwrap();
+    assert_eq!(transition.issues.len(), 1);
+    assert_eq!(transition.issues[0].reason, "Layer[Upper1] turns OFF");
+    assert!(transition.issues[0].suggestion.starts_with("this cannot be made seamless"), "{}", transition.issues[0].suggestion);
+    assert!(!transition.issues[0].suggestion.contains("C8"));
+}
+
+#[test_case(Effect::Mfx, 1, "SympatheticResonance")]
+#[test_case(Effect::Reverb, 0, "Room")]
+#[test_case(Effect::Chorus, 0, "Off")]
+fn save_and_apply_effect_preset(effect: Effect, from_set: usize, expected_type: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/SB.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let folder = std::env::temp_dir().join(format!("roland-rds-presets-{}", effect));
+    let library = PresetLibrary::new(&folder);
+    assert!(!library.save(&EffectPreset::from_live_set("Signature".to_string(), &rds.user_sets[from_set], effect))?);
+    let preset = library.get("signature")?;
+    assert_eq!(preset.effect(), effect);
+    assert_eq!(preset.type_name(), expected_type);
+    preset.validate()?;
+    for ls in rds.user_sets.iter_mut().skip(10).take(5) {
+        preset.apply(ls);
+    }
+    let expected = match effect {
+        Effect::Mfx => rds.user_sets[from_set].mfx.to_bytes()?.to_vec(),
+        Effect::Reverb => rds.user_sets[from_set].reverb.to_bytes()?.to_vec(),
+        Effect::Chorus => rds.user_sets[from_set].chorus.to_bytes()?.to_vec()
+    };
+    for ls in rds.user_sets.iter().skip(10).take(5) {
+        let found = match effect {
+            Effect::Mfx => ls.mfx.to_bytes()?.to_vec(),
+            Effect::Reverb => ls.reverb.to_bytes()?.to_vec(),
+            Effect::Chorus => ls.chorus.to_bytes()?.to_vec()
+        };
+        assert_eq!(found, expected);
+    }
+    assert_eq!(library.list()?.len(), 1);
+    fs::remove_dir_all(&folder)?;
+    Ok(())
+}
+
+#[test]
+fn effect_preset_out_of_range() -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/CHO-REV-DFLTS.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let preset = EffectPreset::from_live_set("Too Long".to_string(), &rds.user_sets[1], Effect::Reverb);
+    preset.validate()?;
+    let mut json = serde_json::to_value(&preset)?;
+    json["reverb"]["reverb_type"]["Room"]["time"] = 200.into();
+    let edited: EffectPreset = serde_json::from_value(json)?;
+    assert!(edited.validate().is_err());
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", SheetFormat::Markdown)]
+#[test_case("examples/rd300nx/SB.RDS", SheetFormat::Html)]
+fn export_sheet_resolves_system_mode(rds_filename: &str, format: SheetFormat) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let sets = CueSheet::named_user_sets(&rds);
+    assert!(!sets.is_empty());
+    for mode in [types::enums::SettingMode::System, types::enums::SettingMode::LiveSet] {
+        rds.system.common.pedal_mode = mode;
+        rds.system.common.s1_s2_mode = mode;
+        let sheet = CueSheet::new(&rds, &sets, None);
+        assert_eq!(sheet.sets.len(), sets.len());
+        let rendered = sheet.render(format);
+        for (set, (_, index)) in sheet.sets.iter().zip(&sets) {
+            assert!(rendered.contains(&set.name));
+            assert!(!set.layers.is_empty());
+            let is_system = set.controls.iter().filter(|(_, assignment)| assignment.ends_with("(system)")).count();
+            if matches!(mode, types::enums::SettingMode::System) {
+                assert_eq!(is_system, 4);
+            } else {
+                assert_eq!(is_system, 0);
+                assert_eq!(set.label, (index + 1).to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+// the MFX switch starts as the MFX is enabled, whatever the live set's S1 state is
+#[test_case(types::enums::SettingMode::LiveSet, true, "Mfx1Switch (on)")]
+#[test_case(types::enums::SettingMode::LiveSet, false, "Mfx1Switch (off)")]
+#[test_case(types::enums::SettingMode::System, true, "Mfx1Switch (on) (system)")]
+#[test_case(types::enums::SettingMode::System, false, "Mfx1Switch (off) (system)")]
+fn export_sheet_mfx_switch_state(mode: types::enums::SettingMode, mfx_enable: bool, expected: &str) -> Result<(), Box<dyn Error>> {
+    let mut rds = RD300NX::blank();
+    rds.system.common.s1_s2_mode = mode;
+    rds.system.common.s1_assign = types::enums::ButtonFunction::Mfx1Switch;
+    rds.user_sets[0].common.s1_assign = types::enums::ButtonFunction::Mfx1Switch;
+    rds.user_sets[0].common.s1_state = !mfx_enable;
+    rds.user_sets[0].mfx.enable = mfx_enable;
+    let sheet = CueSheet::new(&rds, &[("user_sets", 0)], None);
+    let s1 = sheet.sets[0].controls.iter().find(|(control, _)| control == "S1").unwrap();
+    assert_eq!(s1.1, expected);
+    Ok(())
+}
+
+#[test]
+fn export_sheet_includes_external_layers() -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open("examples/rd300nx/EXTERNAL.RDS")?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let sheet = CueSheet::new(&rds, &[("user_sets", 0)], None);
+    let names: Vec<String> = sheet.sets[0].layers.iter().map(|l| l.name()).collect();
+    assert_eq!(names, vec!["Upper1", "Upper1 (external)"]);
+    assert_eq!(sheet.sets[0].layers[1].tone, "MIDI Channel1 (All)");
+    assert_eq!(sheet.sets[0].controls.iter().filter(|(control, _)| control.starts_with("Slider")).count(), 1);
+    assert!(sheet.render(SheetFormat::Markdown).contains("| Upper1 (external) | MIDI Channel1 (All) | A0-C8 |"));
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", 7, false, &["Upper1 and Upper2 overlap at A0-C8", "Upper1 and Lower1 overlap at A0-C8", "are ignored ('~')"], &["no layer plays"] ; "split off")]
+#[test_case("examples/rd300nx/SB.RDS", 7, true, &["Upper1 and Upper2 overlap at G3-C8", "would make Upper1, Upper2, Lower1 play every key"], &["Upper1 and Lower1", "no layer plays"] ; "split on")]
+fn keyboard_map_of_layers(rds_filename: &str, index: usize, split_switch: bool, expected: &[&str], unexpected: &[&str]) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let mut rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    rds.user_sets[index].common.split_switch_internal = split_switch;
+    let map = keyboard_map(&rds.user_sets[index]);
+    for line in map.lines().skip(1).take(2) {
+        assert_eq!(line.len(), 98);
+    }
+    for text in expected {
+        assert!(map.contains(text), "missing '{}' in:\n{}", text, map);
+    }
+    for text in unexpected {
+        assert!(!map.contains(text), "unexpected '{}' in:\n{}", text, map);
+    }
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", None, true ; "before format version")]
+#[test_case("examples/rd300nx/SB.RDS", Some(0), true ; "version 0")]
+#[test_case("examples/rd300nx/SB.RDS", Some(FORMAT_VERSION), true ; "current version")]
+#[test_case("examples/rd300nx/SB.RDS", Some(FORMAT_VERSION + 1), false ; "newer version")]
+fn migrate_older_json(rds_filename: &str, version: Option<u16>, expect_ok: bool) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.clone().try_into().unwrap())?;
+    let mut value = serde_json::to_value(&rds)?;
+    match version {
+        Some(v) => value[VERSION_FIELD] = v.into(),
+        None => { value.as_object_mut().unwrap().remove(VERSION_FIELD); }
+    }
+    let result = RD300NX::from_json(value.to_string());
+    assert_eq!(result.is_ok(), expect_ok);
+    if let Ok(migrated) = result {
+        assert_eq!(migrated.to_json(), rds.to_json());
+        assert_eq!(rds_bytes, migrated.to_bytes()?.to_vec());
+    }
+    let mut structure = rds.to_structured_json_at_depth(SplitDepth::Set);
+    assert_eq!(migrate_structure(&mut structure, version.unwrap_or_default()).is_ok(), expect_ok);
+    Ok(())
+}
+
+#[test_case("10110010", &[("switch", 1), ("level", 7)], Some(&[("switch", 1), ("level", 50)]))]
+#[test_case("0000 0011 1111", &[("a", 4), ("b", 4), ("c", 4)], Some(&[("a", 0), ("b", 3), ("c", 15)]))]
+#[test_case("1011", &[("switch", 1), ("level", 7)], None)]
+#[test_case("10x1", &[("a", 4)], None)]
+fn bits_to_typed_fields(bits: &str, fields: &[(&str, usize)], expected: Option<&[(&str, u64)]>) -> Result<(), Box<dyn Error>> {
+    let mut value = serde_json::Value::from(bits);
+    let result = bits_to_fields(&mut value, fields);
+    match expected {
+        Some(expected) => {
+            assert!(result.is_ok());
+            for (name, number) in expected {
+                assert_eq!(value[name], *number);
+            }
+        },
+        None => assert!(result.is_err())
+    }
+    Ok(())
+}
+
+// migrations which change the shape of files (and folders) within a split live set, as future migrations will
+const TEST_MIGRATIONS: [Migration; 3] = [
+    Migration {
+        description: "decode the EQ settings",
+        paths: &["user_sets/*/common/unused_eq_settings"],
+        migrate: |eq| bits_to_fields(eq, &[("low", 34), ("high", 34)])
+    },
+    Migration {
+        description: "nest the internal layer volume",
+        paths: &["user_sets/*/layers/*/internal/volume"],
+        migrate: |volume| {
+            *volume = serde_json::json!({ "level": volume.take() });
+            Ok(())
+        }
+    },
+    Migration {
+        description: "rename the second piano set",
+        paths: &["piano/1"],
+        migrate: |live_set| {
+            live_set["common"]["name"] = "Migrated".into();
+            Ok(())
+        }
+    }
+];
+
+#[test_case(SplitDepth::Set)]
+#[test_case(SplitDepth::Section)]
+#[test_case(SplitDepth::Full)]
+fn migrate_paths_at_every_split_depth(depth: SplitDepth) -> Result<(), Box<dyn Error>> {
+    let rds = RD300NX::blank();
+    let mut expected = serde_json::to_value(&rds)?;
+    expected.as_object_mut().unwrap().remove(VERSION_FIELD);
+    upgrade_json(&mut expected, 0, &TEST_MIGRATIONS)?;
+    assert!(expected["user_sets"][59]["common"]["unused_eq_settings"]["high"].is_u64());
+    assert!(expected["user_sets"][0]["layers"][2]["internal"]["volume"]["level"].is_u64());
+    assert_eq!(expected["piano"][1]["common"]["name"], "Migrated");
+    let mut structure = rds.to_structured_json_at_depth(depth);
+    upgrade_structure(&mut structure, 0, &TEST_MIGRATIONS)?;
+    assert_eq!(structure_to_value(&structure, "")?, expected);
+    Ok(())
+}
+
+#[test_case(Some("stage ep"), None, 3, &["Stage EP 1", "Stage EP 2", "Stage EP Trm"])]
+#[test_case(Some("26"), None, 1, &["Stage EP 1"])]
+#[test_case(None, Some("piano"), 23, &["ConcertGrand", "Honky-tonk w"])]
+#[test_case(Some("piano"), Some("gm2"), 8, &["Piano 1", "E.Piano 2"])]
+#[test_case(None, Some("drums"), 23, &["Standard 1", "GM2 SFX"])]
+fn search_tones(name: Option<&str>, category: Option<&str>, expected_count: usize, expected_names: &[&str]) -> Result<(), Box<dyn Error>> {
+    let mut query = tones::ToneQuery { name: name.map(|n| n.to_string()), ..Default::default() };
+    if let Some(c) = category {
+        query.set_category(c)?;
+    }
+    let found: Vec<&str> = query.search().into_iter().map(|(_, t)| t.name).collect();
+    assert_eq!(found.len(), expected_count, "found: {:?}", found);
+    for name in expected_names {
+        assert!(found.contains(name), "missing '{}' in {:?}", name, found);
+    }
+    Ok(())
+}
+
+#[test_case("examples/rd300nx/SB.RDS", "EP Belle", &[("user_sets", 7, true)])]
+#[test_case("examples/rd300nx/SB.RDS", "Hit EP", &[("piano", 1, false)])]
+#[test_case("examples/rd300nx/SB.RDS", "Stage EP 1", &[])]
+fn live_sets_using_tone(rds_filename: &str, tone_name: &str, expected: &[(&str, usize, bool)]) -> Result<(), Box<dyn Error>> {
+    let mut rds_bytes = Vec::new();
+    let mut f = fs::File::options().read(true).open(&rds_filename)?;
+    f.read_to_end(&mut rds_bytes)?;
+    let rds = rd300nx::RD300NX::from_bytes(rds_bytes.try_into().unwrap())?;
+    let query = tones::ToneQuery { name: Some(tone_name.to_string()), ..Default::default() };
+    let (number, _) = query.search().into_iter().find(|(_, t)| t.name == tone_name).unwrap();
+    let found: Vec<(&str, usize, bool)> = rds.tone_usage(number).into_iter().map(|u| (u.bank, u.index, u.enabled)).collect();
+    assert_eq!(found, expected);
+    Ok(())
+}
+
+const MEANTONE_SCL: &str = "! meanquar.scl\n!\n1/4-comma meantone\n 12\n!\n 76.04900\n 193.15686\n 310.26471\n 5/4\n 503.42157\n 579.47057\n 696.57843\n 25/16\n 889.73529\n 1006.84314\n 1082.89214\n 2/1\n";
+const SHIFTED_KBM: &str = "! Map size\n12\n! First note\n0\n! Last note\n127\n! Middle note\n60\n! Reference note\n60\n! Reference frequency\n261.625565\n! Octave degree\n12\n! Mapping\n0\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\nx\n";
+
+#[test_case(MEANTONE_SCL, None, &[(60, 10.3), (64, -3.4), (67, 6.8), (69, 0.0), (81, 0.0)], 0 ; "meantone from A")]
+#[test_case(MEANTONE_SCL, Some(SHIFTED_KBM), &[(60, 0.0), (64, -13.7), (69, -10.3), (71, 0.0), (72, 0.0)], 0 ; "meantone from C with B unmapped")]
+#[test_case("!\nsharp C#\n12\n170.0\n200.0\n300.0\n400.0\n500.0\n600.0\n700.0\n800.0\n900.0\n1000.0\n1100.0\n2/1\n", None, &[(60, 0.0), (61, 50.0), (62, 0.0), (69, 0.0)], 1 ; "clamped to 50 cents")]
+fn import_scala_tuning(scl: &str, kbm: Option<&str>, expected: &[(usize, f64)], expected_warnings: usize) -> Result<(), Box<dyn Error>> {
+    let mut rds = RD300NX::blank();
+    let piano = &mut rds.user_sets[0].layers[0].piano;
+    let scale = ScalaScale::parse(scl)?;
+    let mapping = match kbm {
+        Some(kbm) => KeyboardMapping::parse(kbm)?,
+        None => KeyboardMapping::default()
+    };
+    let warnings = import_tuning(piano, &TuningSource::Scala(scale, mapping))?;
+    assert_eq!(warnings.len(), expected_warnings);
+    let cents = piano.micro_tune_cents();
+    for (note, offset) in expected {
+        assert!((cents[*note] - offset).abs() < 0.01, "note {} expected {} but found {}", note, offset, cents[*note]);
+    }
+    assert!(rds.validate().is_ok());
+    let (scale, mapping) = export_tuning(&rds.user_sets[0].layers[0].piano, "exported".into());
+    let mut reimported = RD300NX::blank();
+    import_tuning(&mut reimported.user_sets[0].layers[0].piano, &TuningSource::Scala(ScalaScale::parse(&scale.to_scl())?, KeyboardMapping::parse(&mapping.to_kbm())?))?;
+    assert_eq!(reimported.user_sets[0].layers[0].piano.micro_tune_cents(), rds.user_sets[0].layers[0].piano.micro_tune_cents());
+    Ok(())
+}
+
+#[test_case(Temperament::Equal, KeyNote::C, [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])]
+#[test_case(Temperament::JustMajor, KeyNote::C, [0.0, 11.7, 3.9, 15.6, -13.7, -2.0, -9.8, 2.0, 13.7, -15.6, -3.9, -11.7])]
+#[test_case(Temperament::Pythagorean, KeyNote::C, [0.0, 13.7, 3.9, -5.9, 7.8, -2.0, 11.7, 2.0, 15.6, 5.9, -3.9, 9.8])]
+#[test_case(Temperament::MeanTone, KeyNote::C, [0.0, -24.0, -6.8, 10.3, -13.7, 3.4, -20.5, -3.4, -27.4, -10.3, 6.8, -17.1])]
+#[test_case(Temperament::Kirnberger, KeyNote::C, [0.0, -9.8, -6.8, -5.9, -13.7, -2.0, -9.8, -3.4, -7.8, -10.3, -3.9, -11.7])]
+#[test_case(Temperament::Werckmeister, KeyNote::C, [0.0, -9.8, -7.8, -5.9, -9.8, -2.0, -11.7, -3.9, -7.8, -11.7, -3.9, -7.8])]
+#[test_case(Temperament::Kirnberger, KeyNote::D, [-3.9, -11.7, 0.0, -9.8, -6.8, -5.9, -13.7, -2.0, -9.8, -3.4, -7.8, -10.3])]
+fn temperament_micro_tune(temperament: Temperament, key: KeyNote, expected: [f64; 12]) -> Result<(), Box<dyn Error>> {
+    let offsets = temperament_offsets(temperament, key);
+    for (note, (found, expected)) in offsets.iter().zip(expected).enumerate() {
+        assert!((found - expected).abs() < 0.05, "note {} expected {} but found {}", note, expected, found);
+    }
+    let mut rds = RD300NX::blank();
+    let piano = &mut rds.user_sets[0].layers[0].piano;
+    assert!(import_tuning(piano, &TuningSource::Temperament(temperament, key))?.is_empty());
+    let cents = piano.micro_tune_cents();
+    for (note, cent) in cents.iter().enumerate() {
+        assert!((cent - expected[note % 12]).abs() < 0.051, "note {} expected {} but found {}", note, expected[note % 12], cent);
+    }
+    assert!(rds.validate().is_ok());
+    Ok(())
+}
+
 #[test_case("schema/rd300nx.json")]
 fn no_changes_to_schema(schema_filename: &str) -> Result<(), Box<dyn Error>> {
     let mut bytes = Vec::new();
@@ -229,4 +1144,29 @@ fn no_changes_to_schema(schema_filename: &str) -> Result<(), Box<dyn Error>> {
     let found_schema = serde_json::to_string_pretty(&schema_for!(RD300NX)).unwrap();
     assert_eq!(expected_schema, found_schema);
     Ok(())
+}
+
+#[test]
+fn every_schema_property_is_described() {
+    fn undescribed_properties(value: &serde_json::Value, location: String, found: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(map) => {
+                if let Some(serde_json::Value::Object(properties)) = map.get("properties") {
+                    for (name, property) in properties {
+                        if property.get("title").is_none() || property.get("description").is_none() {
+                            found.push(format!("{}/{}", location, name));
+                        }
+                    }
+                }
+                for (key, item) in map {
+                    undescribed_properties(item, format!("{}/{}", location, key), found);
+                }
+            },
+            serde_json::Value::Array(items) => items.iter().for_each(|item| undescribed_properties(item, location.clone(), found)),
+            _ => {}
+        }
+    }
+    let mut found = Vec::new();
+    undescribed_properties(&serde_json::to_value(schema_for!(RD300NX)).unwrap(), String::new(), &mut found);
+    assert!(found.is_empty(), "{} properties have no title or description, eg. {:?}", found.len(), found.iter().take(10).collect::<Vec<_>>());
 }
\ No newline at end of file
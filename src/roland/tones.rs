@@ -1,8 +1,13 @@
+use std::str::FromStr;
+
 use schemars::{JsonSchema, schema::Schema};
 use serde_json::Value;
 use serde::{de, Serialize, Deserialize};
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, Display};
 use validator::Validate;
 
+use crate::roland::types::enums::Layer;
 use crate::json::{type_name_pretty, schema::{u16_schema, one_of_schema, enum_schema}, validation::out_of_range_err};
 
 pub struct Tone {
@@ -17,9 +22,129 @@ impl Tone {
     fn numbered_string(&self) -> String {
         format!("{}_{}", self._number, self.name)
     }
+
+    // the number shown on the RD300NX (or 0 for tones which only exist on the RD700NX)
+    pub fn number(&self) -> u16 {
+        self._number
+    }
+
+    pub fn category(&self) -> Option<ToneCategory> {
+        if self._number == 0 {
+            return None;
+        }
+        ToneCategory::iter().rev().find(|c| c.first_number() <= self._number)
+    }
+
+    // GM2 tones (and drum kits) use the MSB reserved for them by the General MIDI 2 standard
+    pub fn is_gm2(&self) -> bool {
+        self.msb == 120 || self.msb == 121
+    }
 }
 
-#[derive(Debug)]
+// The tone list is arranged in groups of similar sounding tones, which begin at these tone numbers
+#[derive(Debug, Copy, Clone, PartialEq, EnumIter, Display)]
+pub enum ToneCategory {
+    Piano,
+    EPiano,
+    Keys,
+    Strings,
+    Pads,
+    Organ,
+    Guitar,
+    Bass,
+    Choir,
+    Brass,
+    Wind,
+    Synth,
+    Sfx,
+    Drums
+}
+
+impl ToneCategory {
+    fn first_number(&self) -> u16 {
+        match self {
+            Self::Piano => 1,
+            Self::EPiano => 24,
+            Self::Keys => 68,
+            Self::Strings => 132,
+            Self::Pads => 173,
+            Self::Organ => 286,
+            Self::Guitar => 336,
+            Self::Bass => 395,
+            Self::Choir => 499,
+            Self::Brass => 533,
+            Self::Wind => 591,
+            Self::Synth => 619,
+            Self::Sfx => 865,
+            Self::Drums => 917
+        }
+    }
+}
+
+impl FromStr for ToneCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let simplified: String = s.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        Self::iter().find(|c| c.to_string().eq_ignore_ascii_case(&simplified))
+            .ok_or_else(|| format!("Tone category should be one of {} (or gm2), but found '{}'", Self::iter().map(|c| c.to_string().to_lowercase()).collect::<Vec<_>>().join(", "), s))
+    }
+}
+
+// A search of the tone list, where every criteria which is set must match
+#[derive(Default)]
+pub struct ToneQuery {
+    pub name: Option<String>,
+    pub category: Option<ToneCategory>,
+    pub gm2: bool
+}
+
+impl ToneQuery {
+    // as well as the tone categories, 'gm2' selects the GM2 tones
+    pub fn set_category(&mut self, category: &str) -> Result<(), String> {
+        if category.eq_ignore_ascii_case("gm2") {
+            self.gm2 = true;
+        } else {
+            self.category = Some(category.parse()?);
+        }
+        Ok(())
+    }
+
+    // a name matches by tone number, or case insensitive part of the tone name
+    pub fn matches(&self, tone: &Tone) -> bool {
+        if let Some(name) = &self.name {
+            let matched = match name.parse::<u16>() {
+                Ok(number) => number == tone._number,
+                Err(_) => tone.name.to_lowercase().contains(&name.to_lowercase())
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if self.category.is_some() && tone.category() != self.category {
+            return false;
+        }
+        !self.gm2 || tone.is_gm2()
+    }
+
+    pub fn search(&self) -> Vec<(ToneNumber, &'static Tone)> {
+        TONE_LIST.iter().enumerate()
+            .filter(|(_, tone)| tone._number != 0 && self.matches(tone))
+            .map(|(i, tone)| (ToneNumber(i as u16 + 1), tone))
+            .collect()
+    }
+}
+
+// A layer of a live set which has a particular tone selected
+pub struct ToneUsage {
+    pub bank: &'static str,
+    pub index: usize,
+    pub name: String,
+    pub layer: Layer,
+    pub enabled: bool
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct ToneNumber(u16);
 
 impl ToneNumber {
@@ -50,6 +175,23 @@ impl ToneNumber {
     }
 }
 
+// a tone is found by its number on the RD300NX or its name, where part of a name is enough if only one tone matches
+impl FromStr for ToneNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found = ToneQuery { name: Some(s.to_string()), ..Default::default() }.search();
+        if let Some((number, _)) = found.iter().find(|(_, tone)| tone.name.eq_ignore_ascii_case(s)) {
+            return Ok(*number);
+        }
+        match found.as_slice() {
+            [(number, _)] => Ok(*number),
+            [] => Err(format!("No tone matches '{}'", s)),
+            _ => Err(format!("Tone '{}' matches {} tones (eg. {}), use a tone number instead", s, found.len(), found.iter().take(3).map(|(_, t)| t.name).collect::<Vec<_>>().join(", ")))
+        }
+    }
+}
+
 impl From<u16> for ToneNumber {
     fn from(value: u16) -> Self {
         if value == 0 {
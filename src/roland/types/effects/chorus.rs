@@ -1,8 +1,9 @@
-use super::{UnusedParameters, Parameters};
+use std::str::FromStr;
+use super::{UnusedParameters, Parameters, ParameterInfo, ParameterField, Effect, parameters_schema_of};
 use super::parameters::{FilterType, RateMode, NoteLength, DelayMode, Level, LinearMilliseconds, PreLpf, UInt};
 use super::super::numeric::Parameter;
 use super::discrete::{LogFrequency, LogMilliseconds, LinearFrequency, LogFrequencyOrByPass, EvenPercent, Feedback, Phase};
-use crate::json::schema::{one_of_schema, single_property_schema_of};
+use crate::json::schema::one_of_schema;
 use crate::json::{serialize_default_terminated_array, type_name_pretty};
 use schemars::JsonSchema;
 use validator::Validate;
@@ -26,6 +27,17 @@ impl ChorusType {
         }
     }
 
+    // the default parameters of a type, as loaded by the keyboard when the type is changed
+    pub fn default_for(number: u8) -> Self {
+        match number {
+            0 => Self::Off(Default::default()),
+            1 => Self::Chorus(Default::default()),
+            2 => Self::Delay(Default::default()),
+            3 => Self::Gm2Chorus(Default::default()),
+            _ => panic!("Invalid chorus type: {}", number)
+        }
+    }
+
     pub fn number(&self) -> u8 {
         match self {
             Self::Off(_) => 0,
@@ -53,7 +65,15 @@ impl ChorusType {
         }
     }
 
-    #[allow(dead_code)] // used by tests, potentially useful if using this as a library
+    pub fn describe(&self) -> Vec<ParameterInfo> {
+        match self {
+            Self::Off(u) => u.describe(),
+            Self::Chorus(c) => c.describe(),
+            Self::Delay(d) => d.describe(),
+            Self::Gm2Chorus(g) => g.describe()
+        }
+    }
+
     pub fn default(&self) -> Self {
         match self {
             Self::Off(_) => Self::Off(Default::default()),
@@ -77,6 +97,17 @@ impl Default for ChorusType {
     }
 }
 
+impl FromStr for ChorusType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let types: Vec<Self> = (0..=3).map(Self::default_for).collect();
+        let names: Vec<String> = types.iter().map(|t| t.name().to_string()).collect();
+        types.into_iter().find(|t| t.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Chorus type should be one of {}, but found '{}'", names.join(", "), s))
+    }
+}
+
 impl Validate for ChorusType {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -95,14 +126,18 @@ impl JsonSchema for ChorusType {
 
     fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
-            single_property_schema_of::<UnusedParameters<20>>("Off", gen),
-            single_property_schema_of::<ChorusParameters>("Chorus", gen),
-            single_property_schema_of::<DelayParameters>("Delay", gen),
-            single_property_schema_of::<Gm2ChorusParameters>("Gm2Chorus", gen)
+            schema_of::<UnusedParameters<20>>("Off", gen),
+            schema_of::<ChorusParameters>("Chorus", gen),
+            schema_of::<DelayParameters>("Delay", gen),
+            schema_of::<Gm2ChorusParameters>("Gm2Chorus", gen)
         ])
     }
 }
 
+fn schema_of<T: Parameters<20> + JsonSchema + serde::Serialize>(type_name: &str, gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    parameters_schema_of::<T, 20>(Effect::Chorus, type_name, gen)
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ChorusParameters {
     filter_type: FilterType,
@@ -111,8 +146,10 @@ pub struct ChorusParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
+    #[validate]
     feedback: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -140,18 +177,24 @@ impl Default for ChorusParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DelayParameters {
     delay_left_mode: DelayMode,
+    #[validate]
     delay_left_ms: LinearMilliseconds<1000>,
     delay_left_note: NoteLength,
     delay_right_mode: DelayMode,
+    #[validate]
     delay_right_ms: LinearMilliseconds<1000>,
     delay_right_note: NoteLength,
     delay_centre_mode: DelayMode,
+    #[validate]
     delay_centre_ms: LinearMilliseconds<1000>,
     delay_centre_note: NoteLength,
     centre_feedback_percent: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     left_level: Level,
+    #[validate]
     right_level: Level,
+    #[validate]
     centre_level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -184,12 +227,19 @@ impl Default for DelayParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct Gm2ChorusParameters {
+    #[validate]
     pre_lpf: PreLpf,
+    #[validate]
     level: Level,
+    #[validate]
     feedback: Level,
+    #[validate]
     delay: Level,
+    #[validate]
     rate: Level,
+    #[validate]
     depth: Level,
+    #[validate]
     send_to_reverb: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
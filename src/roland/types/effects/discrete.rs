@@ -1,18 +1,30 @@
 use std::{fmt::Display, ops::AddAssign};
 use schemars::JsonSchema;
 use serde_json::Value;
-use crate::json::{schema::enum_schema, type_name_pretty};
+use schemars::schema::Schema;
+use crate::json::{schema::{enum_schema, one_of_schema, single_property_schema, described_schema}, type_name_pretty};
 
 use super::super::numeric::Parameter;
+use super::DisplayParameter;
 use serde::{Serialize, Deserialize, de};
 
 pub trait DiscreteValues<T: PartialEq + Display, const OFFSET: i16> {
     const OFFSET: i16 = OFFSET;
+    const UNIT: &'static str = "";
 
     fn values() -> Vec<T>;
 
     fn format(value: T) -> String;
 
+    // format is used for serialization, so cannot change, but readable puts a space before the unit (eg. "12.5 ms")
+    fn readable(value: T) -> String {
+        let formatted = Self::format(value);
+        match formatted.strip_suffix(Self::UNIT) {
+            Some(number) if Self::UNIT.starts_with(char::is_alphabetic) => format!("{} {}", number, Self::UNIT),
+            _ => formatted
+        }
+    }
+
     fn equal(a: &T, b: &T) -> bool {
         a == b
     }
@@ -57,6 +69,8 @@ fn flatten<T>(vectors: Vec<Vec<T>>) -> Vec<T> {
 pub struct FineFrequency(pub u8);
 
 impl DiscreteValues<u8, 0> for FineFrequency {
+    const UNIT: &'static str = "Hz";
+
     fn values() -> Vec<u8> {
         vec![50, 56, 63, 71, 80, 90, 100, 112, 125]
     }
@@ -71,6 +85,8 @@ impl DiscreteValues<u8, 0> for FineFrequency {
 pub struct FilterSlope(pub i8);
 
 impl DiscreteValues<i8, 0> for FilterSlope {
+    const UNIT: &'static str = "dB";
+
     fn values() -> Vec<i8> {
         vec![-12, -24, -36]
     }
@@ -99,6 +115,8 @@ impl DiscreteValues<u8, 0> for Balance {
 pub struct LinearFrequency(pub f64);
 
 impl DiscreteValues<f64, 1> for LinearFrequency {
+    const UNIT: &'static str = "Hz";
+
     fn values() -> Vec<f64> {
         enumerate_f64(0.05, 10.0, 0.05)
     }
@@ -107,6 +125,11 @@ impl DiscreteValues<f64, 1> for LinearFrequency {
         format!("{:.2}ms", value)
     }
 
+    // the unit is wrong in format, but fixing it would break existing JSON
+    fn readable(value: f64) -> String {
+        format!("{:.2} {}", value, Self::UNIT)
+    }
+
     fn equal(a: &f64, b: &f64) -> bool {
         (a - b).abs() < EPSILON
     }
@@ -117,6 +140,8 @@ impl DiscreteValues<f64, 1> for LinearFrequency {
 pub struct StepLinearFrequency(pub f64);
 
 impl DiscreteValues<f64, 1> for StepLinearFrequency {
+    const UNIT: &'static str = "Hz";
+
     fn values() -> Vec<f64> {
         enumerate_f64(0.1, 20.0, 0.1)
     }
@@ -125,6 +150,11 @@ impl DiscreteValues<f64, 1> for StepLinearFrequency {
         format!("{:.1}ms", value)
     }
 
+    // the unit is wrong in format, but fixing it would break existing JSON
+    fn readable(value: f64) -> String {
+        format!("{:.1} {}", value, Self::UNIT)
+    }
+
     fn equal(a: &f64, b: &f64) -> bool {
         (a - b).abs() < EPSILON
     }
@@ -135,6 +165,8 @@ impl DiscreteValues<f64, 1> for StepLinearFrequency {
 pub struct LogMilliseconds(pub f64);
 
 impl DiscreteValues<f64, 0> for LogMilliseconds {
+    const UNIT: &'static str = "ms";
+
     fn values() -> Vec<f64> {
         flatten(vec![
             enumerate_f64(0.0, 4.9, 0.1),
@@ -158,6 +190,8 @@ impl DiscreteValues<f64, 0> for LogMilliseconds {
 pub struct EvenPercent<const LIMIT: u8>(pub i8);
 
 impl<const LIMIT: u8> DiscreteValues<i8, 0> for EvenPercent<LIMIT> {
+    const UNIT: &'static str = "%";
+
     fn values() -> Vec<i8> {
         if LIMIT > i8::MAX as u8 {
             panic!("Invalid EvenPercent limit: {}", LIMIT);
@@ -191,6 +225,8 @@ impl DiscreteValues<f64, 0> for QFactor {
 pub struct HumFrequency(pub u8);
 
 impl DiscreteValues<u8, 0> for HumFrequency {
+    const UNIT: &'static str = "Hz";
+
     fn values() -> Vec<u8> {
         vec![50, 60]
     }
@@ -205,6 +241,8 @@ impl DiscreteValues<u8, 0> for HumFrequency {
 pub struct Phase(pub u8);
 
 impl DiscreteValues<u8, 0> for Phase {
+    const UNIT: &'static str = "deg";
+
     fn values() -> Vec<u8> {
         enumerate(0, 180, 2)
     }
@@ -219,6 +257,8 @@ impl DiscreteValues<u8, 0> for Phase {
 pub struct GateTime(pub u16);
 
 impl DiscreteValues<u16, 0> for GateTime {
+    const UNIT: &'static str = "ms";
+
     fn values() -> Vec<u16> {
         enumerate(5, 500, 5)
     }
@@ -238,6 +278,8 @@ impl<const MIN: u16, const MAX: u16> LogFrequency<MIN, MAX> {
 }
 
 impl<const MIN: u16, const MAX: u16> DiscreteValues<u16, 0> for LogFrequency<MIN, MAX> {
+    const UNIT: &'static str = "Hz";
+
     fn values() -> Vec<u16> {
         let mut factor = 1;
         let mut v = Vec::new();
@@ -270,13 +312,32 @@ impl<const MIN: u16, const MAX: u16> DiscreteValues<u16, 0> for LogFrequency<MIN
     }
 }
 
+fn frequency_or_bypass_schema<const MIN: u16, const MAX: u16>(description: &str, gen: &mut schemars::gen::SchemaGenerator) -> Schema {
+    let mut schema = one_of_schema(vec![
+        enum_schema(vec!["ByPass".into()]),
+        single_property_schema("Frequency", described_schema(gen.subschema_for::<LogFrequency<MIN, MAX>>(), "Frequency", "The frequency, when it is not bypassed"))
+    ]).into_object();
+    schema.metadata().description = Some(description.into());
+    schema.into()
+}
+
 /// Parameter(0-17) === LogFrequencyOrByPass(200-8000Hz, BYPASS)
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub enum LogFrequencyOrByPass<const MIN: u16, const MAX: u16> {
     Frequency(LogFrequency<MIN, MAX>),
     ByPass
 }
 
+impl<const MIN: u16, const MAX: u16> JsonSchema for LogFrequencyOrByPass<MIN, MAX> {
+    fn schema_name() -> String {
+        format!("LogFrequencyOrByPass_for_{}_and_{}", MIN, MAX)
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        frequency_or_bypass_schema::<MIN, MAX>("Parameter(0-17) === LogFrequencyOrByPass(200-8000Hz, BYPASS)", gen)
+    }
+}
+
 impl<const L: u16, const H: u16> From<Parameter> for LogFrequencyOrByPass<L, H> {
     fn from(value: Parameter) -> Self {
         let values = LogFrequency::<L, H>::values();
@@ -299,13 +360,40 @@ impl<const L: u16, const H: u16> Into<Parameter> for LogFrequencyOrByPass<L, H>
     }
 }
 
+impl<const L: u16, const H: u16> DisplayParameter for LogFrequencyOrByPass<L, H> {
+    fn unit() -> &'static str {
+        LogFrequency::<L, H>::UNIT
+    }
+
+    fn range() -> String {
+        format!("{}, ByPass", LogFrequency::<L, H>::range())
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Self::Frequency(f) => f.display(),
+            Self::ByPass => "ByPass".into()
+        }
+    }
+}
+
 /// Parameter(1-18) === LogFrequencyOrByPassOffByOne(200-8000Hz, BYPASS)
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub enum LogFrequencyOrByPassOffByOne<const MIN: u16, const MAX: u16> {
     Frequency(LogFrequency<MIN, MAX>),
     ByPass
 }
 
+impl<const MIN: u16, const MAX: u16> JsonSchema for LogFrequencyOrByPassOffByOne<MIN, MAX> {
+    fn schema_name() -> String {
+        format!("LogFrequencyOrByPassOffByOne_for_{}_and_{}", MIN, MAX)
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        frequency_or_bypass_schema::<MIN, MAX>("Parameter(1-18) === LogFrequencyOrByPassOffByOne(200-8000Hz, BYPASS)", gen)
+    }
+}
+
 impl<const L: u16, const H: u16> From<Parameter> for LogFrequencyOrByPassOffByOne<L, H> {
     fn from(value: Parameter) -> Self {
         let values = LogFrequency::<L, H>::values();
@@ -328,13 +416,40 @@ impl<const L: u16, const H: u16> Into<Parameter> for LogFrequencyOrByPassOffByOn
     }
 }
 
+impl<const L: u16, const H: u16> DisplayParameter for LogFrequencyOrByPassOffByOne<L, H> {
+    fn unit() -> &'static str {
+        LogFrequency::<L, H>::UNIT
+    }
+
+    fn range() -> String {
+        format!("{}, ByPass", LogFrequency::<L, H>::range())
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Self::Frequency(f) => f.display(),
+            Self::ByPass => "ByPass".into()
+        }
+    }
+}
+
 /// Parameter(0-17) === ByPassOrLogFrequency(BYPASS, 200-8000Hz)
-#[derive(Serialize, Deserialize, Debug, JsonSchema, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub enum ByPassOrLogFrequency<const MIN: u16, const MAX: u16> {
     ByPass,
     Frequency(LogFrequency<MIN, MAX>)
 }
 
+impl<const MIN: u16, const MAX: u16> JsonSchema for ByPassOrLogFrequency<MIN, MAX> {
+    fn schema_name() -> String {
+        format!("ByPassOrLogFrequency_for_{}_and_{}", MIN, MAX)
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        frequency_or_bypass_schema::<MIN, MAX>("Parameter(0-17) === ByPassOrLogFrequency(BYPASS, 200-8000Hz)", gen)
+    }
+}
+
 impl<const L: u16, const H: u16> From<Parameter> for ByPassOrLogFrequency<L, H> {
     fn from(value: Parameter) -> Self {
         let values = LogFrequency::<L, H>::values();
@@ -355,4 +470,21 @@ impl<const L: u16, const H: u16> Into<Parameter> for ByPassOrLogFrequency<L, H>
             Self::Frequency(f) => Parameter(Into::<Parameter>::into(f).0 + 1),
         }
     }
+}
+
+impl<const L: u16, const H: u16> DisplayParameter for ByPassOrLogFrequency<L, H> {
+    fn unit() -> &'static str {
+        LogFrequency::<L, H>::UNIT
+    }
+
+    fn range() -> String {
+        format!("ByPass, {}", LogFrequency::<L, H>::range())
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Self::Frequency(f) => f.display(),
+            Self::ByPass => "ByPass".into()
+        }
+    }
 }
\ No newline at end of file
@@ -1,13 +1,14 @@
+use std::str::FromStr;
 use schemars::JsonSchema;
 use validator::Validate;
 
-use crate::json::schema::{single_property_schema_of, one_of_schema};
+use crate::json::schema::one_of_schema;
 use crate::json::{serialize_default_terminated_array, type_name_pretty};
 use crate::json::validation::unused_by_rd300nx_err;
 
 use crate::roland::types::enums::Pan;
 use crate::roland::types::numeric::Parameter;
-use super::{UnusedParameters, Parameters};
+use super::{UnusedParameters, Parameters, ParameterInfo, ParameterField, Effect, parameters_schema_of, effect_type_schema_of};
 use super::discrete::{LogFrequency, QFactor, FineFrequency, LinearFrequency, FilterSlope, EvenPercent, StepLinearFrequency, Balance, LogMilliseconds, LogFrequencyOrByPass, HumFrequency, Feedback, ByPassOrLogFrequency, LogFrequencyOrByPassOffByOne, Phase, GateTime};
 use super::parameters::{Level, Switch, Gain, UInt, Int, BoostGain, BoostWidth, RateMode, SuperFilterType, Wave, NoteLength, SimpleFilterType, Direction, Vowel, SpeakerType, PhaserMode, PhaserPolarity, MultiPhaserMode, ModWave, SlicerMode, Speed, FilterType, OutputMode, AmpType, MicSetting, PreAmpType, PreAmpGain, CompressionRatio, PostGain, GateMode, DelayMode, LinearMilliseconds, PhaseType, FeedbackMode, TapeHeads, LofiType, NoiseType, DiscType, DiscTypeWithRandom, Semitones, ReverbOnlyCharacter, GateType};
 
@@ -195,6 +196,99 @@ impl MfxType {
         }
     }
 
+    // the default parameters of a type, as loaded by the keyboard when the type is changed
+    pub fn default_for(number: u8) -> Self {
+        match number {
+            0 => Self::Thru(Default::default()),
+            1 => Self::Equalizer(Default::default()),
+            2 => Self::Spectrum(Default::default()),
+            3 => Self::Isolator(Default::default()),
+            4 => Self::LowBoost(Default::default()),
+            5 => Self::SuperFilter(Default::default()),
+            6 => Self::StepFilter(Default::default()),
+            7 => Self::Enhancer(Default::default()),
+            8 => Self::AutoWah(Default::default()),
+            9 => Self::Humanizer(Default::default()),
+            10 => Self::SpeakerSimulator(Default::default()),
+            11 => Self::Phaser(Default::default()),
+            12 => Self::StepPhaser(Default::default()),
+            13 => Self::MultiStagePhaser(Default::default()),
+            14 => Self::InfinitePhaser(Default::default()),
+            15 => Self::RingModulator(Default::default()),
+            16 => Self::StepRingModulator(Default::default()),
+            17 => Self::Tremolo(Default::default()),
+            18 => Self::AutoPan(Default::default()),
+            19 => Self::StepPan(Default::default()),
+            20 => Self::Slicer(Default::default()),
+            21 => Self::Rotary(Default::default()),
+            22 => Self::VkRotary(Default::default()),
+            23 => Self::Chorus(Default::default()),
+            24 => Self::Flanger(Default::default()),
+            25 => Self::StepFlanger(Default::default()),
+            26 => Self::HexaChorus(Default::default()),
+            27 => Self::TremoloChorus(Default::default()),
+            28 => Self::SpaceD(Default::default()),
+            29 => Self::Chorus3D(Default::default()),
+            30 => Self::Flanger3D(Default::default()),
+            31 => Self::StepFlanger3D(Default::default()),
+            32 => Self::TwoBandChorus(Default::default()),
+            33 => Self::TwoBandFlanger(Default::default()),
+            34 => Self::TwoBandStepFlanger(Default::default()),
+            35 => Self::Overdrive(Default::default()),
+            36 => Self::Distortion(Default::default()),
+            37 => Self::VsOverdrive(Default::default()),
+            38 => Self::VsDistortion(Default::default()),
+            39 => Self::GuitarAmpSimulator(Default::default()),
+            40 => Self::Compressor(Default::default()),
+            41 => Self::Limiter(Default::default()),
+            42 => Self::Gate(Default::default()),
+            43 => Self::Delay(Default::default()),
+            44 => Self::LongDelay(Default::default()),
+            45 => Self::SerialDelay(Default::default()),
+            46 => Self::ModulationDelay(Default::default()),
+            47 => Self::ThreeTapPanDelay(Default::default()),
+            48 => Self::FourTapPanDelay(Default::default()),
+            49 => Self::MultiTapDelay(Default::default()),
+            50 => Self::ReverseDelay(Default::default()),
+            51 => Self::ShuffleDelay(Default::default()),
+            52 => Self::Delay3D(Default::default()),
+            53 => Self::TimeCtrlDelay(Default::default()),
+            54 => Self::LongTimeCtrlDelay(Default::default()),
+            55 => Self::TapeEcho(Default::default()),
+            56 => Self::LofiNoise(Default::default()),
+            57 => Self::LofiCompress(Default::default()),
+            58 => Self::LofiRadio(Default::default()),
+            59 => Self::Telephone(Default::default()),
+            60 => Self::Phonograph(Default::default()),
+            61 => Self::PitchShifter(Default::default()),
+            62 => Self::TwoVoicePitchShifter(Default::default()),
+            63 => Self::StepPitchShifter(Default::default()),
+            64 => Self::Reverb(Default::default()),
+            65 => Self::GatedReverb(Default::default()),
+            66 => Self::OverdriveChorus(Default::default()),
+            67 => Self::OverdriveFlanger(Default::default()),
+            68 => Self::OverdriveDelay(Default::default()),
+            69 => Self::DistortionChorus(Default::default()),
+            70 => Self::DistortionFlanger(Default::default()),
+            71 => Self::DistortionDelay(Default::default()),
+            72 => Self::EnhancerChorus(Default::default()),
+            73 => Self::EnhancerFlanger(Default::default()),
+            74 => Self::EnhancerDelay(Default::default()),
+            75 => Self::ChorusDelay(Default::default()),
+            76 => Self::FlangerDelay(Default::default()),
+            77 => Self::ChorusFlanger(Default::default()),
+            78 => Self::UnusedVrChorus(Default::default()),
+            79 => Self::UnusedVrTremolo(Default::default()),
+            80 => Self::UnusedVrAutoWah(Default::default()),
+            81 => Self::UnusedVrPhaser(Default::default()),
+            82 => Self::UnusedOrganMulti(Default::default()),
+            83 => Self::UnusedLinedrive(Default::default()),
+            84 => Self::UnusedSmallPhaser(Default::default()),
+            85 => Self::SympatheticResonance(Default::default()),
+            mfx_number => Self::Other(OtherMfxParameters { mfx_number, unknown: Default::default() })
+        }
+    }
+
     pub fn number(&self) -> u8 {
         match self {
             Self::Thru(_) => 0,
@@ -471,7 +565,98 @@ impl MfxType {
         }
     }
 
-    #[allow(dead_code)] // used by tests, potentially useful if using this as a library
+    pub fn describe(&self) -> Vec<ParameterInfo> {
+        match self {
+            Self::Thru(p) => p.describe(),
+            Self::Equalizer(p) => p.describe(),
+            Self::Spectrum(p) => p.describe(),
+            Self::Isolator(p) => p.describe(),
+            Self::LowBoost(p) => p.describe(),
+            Self::SuperFilter(p) => p.describe(),
+            Self::StepFilter(p) => p.describe(),
+            Self::Enhancer(p) => p.describe(),
+            Self::AutoWah(p) => p.describe(),
+            Self::Humanizer(p) => p.describe(),
+            Self::SpeakerSimulator(p) => p.describe(),
+            Self::Phaser(p) => p.describe(),
+            Self::StepPhaser(p) => p.describe(),
+            Self::MultiStagePhaser(p) => p.describe(),
+            Self::InfinitePhaser(p) => p.describe(),
+            Self::RingModulator(p) => p.describe(),
+            Self::StepRingModulator(p) => p.describe(),
+            Self::Tremolo(p) => p.describe(),
+            Self::AutoPan(p) => p.describe(),
+            Self::StepPan(p) => p.describe(),
+            Self::Slicer(p) => p.describe(),
+            Self::Rotary(p) => p.describe(),
+            Self::VkRotary(p) => p.describe(),
+            Self::Chorus(p) => p.describe(),
+            Self::Flanger(p) => p.describe(),
+            Self::StepFlanger(p) => p.describe(),
+            Self::HexaChorus(p) => p.describe(),
+            Self::TremoloChorus(p) => p.describe(),
+            Self::SpaceD(p) => p.describe(),
+            Self::Chorus3D(p) => p.describe(),
+            Self::Flanger3D(p) => p.describe(),
+            Self::StepFlanger3D(p) => p.describe(),
+            Self::TwoBandChorus(p) => p.describe(),
+            Self::TwoBandFlanger(p) => p.describe(),
+            Self::TwoBandStepFlanger(p) => p.describe(),
+            Self::Overdrive(p) => p.describe(),
+            Self::Distortion(p) => p.describe(),
+            Self::VsOverdrive(p) => p.describe(),
+            Self::VsDistortion(p) => p.describe(),
+            Self::GuitarAmpSimulator(p) => p.describe(),
+            Self::Compressor(p) => p.describe(),
+            Self::Limiter(p) => p.describe(),
+            Self::Gate(p) => p.describe(),
+            Self::Delay(p) => p.describe(),
+            Self::LongDelay(p) => p.describe(),
+            Self::SerialDelay(p) => p.describe(),
+            Self::ModulationDelay(p) => p.describe(),
+            Self::ThreeTapPanDelay(p) => p.describe(),
+            Self::FourTapPanDelay(p) => p.describe(),
+            Self::MultiTapDelay(p) => p.describe(),
+            Self::ReverseDelay(p) => p.describe(),
+            Self::ShuffleDelay(p) => p.describe(),
+            Self::Delay3D(p) => p.describe(),
+            Self::TimeCtrlDelay(p) => p.describe(),
+            Self::LongTimeCtrlDelay(p) => p.describe(),
+            Self::TapeEcho(p) => p.describe(),
+            Self::LofiNoise(p) => p.describe(),
+            Self::LofiCompress(p) => p.describe(),
+            Self::LofiRadio(p) => p.describe(),
+            Self::Telephone(p) => p.describe(),
+            Self::Phonograph(p) => p.describe(),
+            Self::PitchShifter(p) => p.describe(),
+            Self::TwoVoicePitchShifter(p) => p.describe(),
+            Self::StepPitchShifter(p) => p.describe(),
+            Self::Reverb(p) => p.describe(),
+            Self::GatedReverb(p) => p.describe(),
+            Self::OverdriveChorus(p) => p.describe(),
+            Self::OverdriveFlanger(p) => p.describe(),
+            Self::OverdriveDelay(p) => p.describe(),
+            Self::DistortionChorus(p) => p.describe(),
+            Self::DistortionFlanger(p) => p.describe(),
+            Self::DistortionDelay(p) => p.describe(),
+            Self::EnhancerChorus(p) => p.describe(),
+            Self::EnhancerFlanger(p) => p.describe(),
+            Self::EnhancerDelay(p) => p.describe(),
+            Self::ChorusDelay(p) => p.describe(),
+            Self::FlangerDelay(p) => p.describe(),
+            Self::ChorusFlanger(p) => p.describe(),
+            Self::UnusedVrChorus(p) => p.describe(),
+            Self::UnusedVrTremolo(p) => p.describe(),
+            Self::UnusedVrAutoWah(p) => p.describe(),
+            Self::UnusedVrPhaser(p) => p.describe(),
+            Self::UnusedOrganMulti(p) => p.describe(),
+            Self::UnusedLinedrive(p) => p.describe(),
+            Self::UnusedSmallPhaser(p) => p.describe(),
+            Self::SympatheticResonance(p) => p.describe(),
+            Self::Other(p) => p.describe()
+        }
+    }
+
     pub fn default(&self) -> Self {
         match self {
             Self::Thru(_) => Self::Thru(Default::default()),
@@ -578,6 +763,17 @@ impl Default for MfxType {
     }
 }
 
+impl FromStr for MfxType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let types: Vec<Self> = (0..=u8::MAX).map(Self::default_for).take_while(|t| !matches!(t, Self::Other(_))).filter(|t| !t.name().starts_with("Unused")).collect();
+        let names: Vec<String> = types.iter().map(|t| t.name().to_string()).collect();
+        types.into_iter().find(|t| t.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("MFX type should be one of {}, but found '{}'", names.join(", "), s))
+    }
+}
+
 impl Validate for MfxType {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -679,101 +875,107 @@ impl JsonSchema for MfxType {
 
     fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
-            single_property_schema_of::<UnusedParameters<32>>("Thru", gen),
-            single_property_schema_of::<EqualizerParameters>("Equalizer", gen),
-            single_property_schema_of::<SpectrumParameters>("Spectrum", gen),
-            single_property_schema_of::<IsolatorParameters>("Isolator", gen),
-            single_property_schema_of::<LowBoostParameters>("LowBoost", gen),
-            single_property_schema_of::<SuperFilterParameters>("SuperFilter", gen),
-            single_property_schema_of::<StepFilterParameters>("StepFilter", gen),
-            single_property_schema_of::<EnhancerParameters>("Enhancer", gen),
-            single_property_schema_of::<AutoWahParameters>("AutoWah", gen),
-            single_property_schema_of::<HumanizerParameters>("Humanizer", gen),
-            single_property_schema_of::<SpeakerSimulatorParameters>("SpeakerSimulator", gen),
-            single_property_schema_of::<PhaserParameters>("Phaser", gen),
-            single_property_schema_of::<StepPhaserParameters>("StepPhaser", gen),
-            single_property_schema_of::<MultiStagePhaserParameters>("MultiStagePhaser", gen),
-            single_property_schema_of::<InfinitePhaserParameters>("InfinitePhaser", gen),
-            single_property_schema_of::<RingModulatorParameters>("RingModulator", gen),
-            single_property_schema_of::<StepRingModulatorParameters>("StepRingModulator", gen),
-            single_property_schema_of::<CyclicalParameters>("Tremolo", gen),
-            single_property_schema_of::<CyclicalParameters>("AutoPan", gen),
-            single_property_schema_of::<StepPanParameters>("StepPan", gen),
-            single_property_schema_of::<SlicerParameters>("Slicer", gen),
-            single_property_schema_of::<RotaryParameters>("Rotary", gen),
-            single_property_schema_of::<VkRotaryParameters>("VkRotary", gen),
-            single_property_schema_of::<ChorusParameters>("Chorus", gen),
-            single_property_schema_of::<FlangerParameters>("Flanger", gen),
-            single_property_schema_of::<StepFlangerParameters>("StepFlanger", gen),
-            single_property_schema_of::<HexaChorusParameters>("HexaChorus", gen),
-            single_property_schema_of::<TremoloChorusParameters>("TremoloChorus", gen),
-            single_property_schema_of::<SpaceDParameters>("SpaceD", gen),
-            single_property_schema_of::<Chorus3DParameters>("Chorus3D", gen),
-            single_property_schema_of::<Flanger3DParameters>("Flanger3D", gen),
-            single_property_schema_of::<StepFlanger3DParameters>("StepFlanger3D", gen),
-            single_property_schema_of::<TwoBandChorusParameters>("TwoBandChorus", gen),
-            single_property_schema_of::<TwoBandFlangerParameters>("TwoBandFlanger", gen),
-            single_property_schema_of::<TwoBandStepFlangerParameters>("TwoBandStepFlanger", gen),
-            single_property_schema_of::<DriveParameters<0, 70>>("Overdrive", gen),
-            single_property_schema_of::<DriveParameters<3, 50>>("Distortion", gen),
-            single_property_schema_of::<VsDriveParameters<0>>("VsOverdrive", gen),
-            single_property_schema_of::<VsDriveParameters<3>>("VsDistortion", gen),
-            single_property_schema_of::<GuitarAmpSimulatorParameters>("GuitarAmpSimulator", gen),
-            single_property_schema_of::<CompressorParameters>("Compressor", gen),
-            single_property_schema_of::<LimiterParameters>("Limiter", gen),
-            single_property_schema_of::<GateParameters>("Gate", gen),
-            single_property_schema_of::<DelayParameters>("Delay", gen),
-            single_property_schema_of::<LongDelayParameters>("LongDelay", gen),
-            single_property_schema_of::<SerialDelayParameters>("SerialDelay", gen),
-            single_property_schema_of::<ModulationDelayParameters>("ModulationDelay", gen),
-            single_property_schema_of::<ThreeTapPanDelayParameters>("ThreeTapPanDelay", gen),
-            single_property_schema_of::<FourTapPanDelayParameters>("FourTapPanDelay", gen),
-            single_property_schema_of::<MultiTapDelayParameters>("MultiTapDelay", gen),
-            single_property_schema_of::<ReverseDelayParameters>("ReverseDelay", gen),
-            single_property_schema_of::<ShuffleDelayParameters>("ShuffleDelay", gen),
-            single_property_schema_of::<Delay3DParameters>("Delay3D", gen),
-            single_property_schema_of::<TimeCtrlDelayParameters>("TimeCtrlDelay", gen),
-            single_property_schema_of::<LongTimeCtrlDelayParameters>("LongTimeCtrlDelay", gen),
-            single_property_schema_of::<TapeEchoParameters>("TapeEcho", gen),
-            single_property_schema_of::<LofiNoiseParameters>("LofiNoise", gen),
-            single_property_schema_of::<LofiCompressParameters>("LofiCompress", gen),
-            single_property_schema_of::<LofiRadioParameters>("LofiRadio", gen),
-            single_property_schema_of::<TelephoneParameters>("Telephone", gen),
-            single_property_schema_of::<PhonographParameters>("Phonograph", gen),
-            single_property_schema_of::<PitchShifterParameters>("PitchShifter", gen),
-            single_property_schema_of::<TwoVoicePitchShifterParameters>("TwoVoicePitchShifter", gen),
-            single_property_schema_of::<StepPitchShifterParameters>("StepPitchShifter", gen),
-            single_property_schema_of::<ReverbParameters>("Reverb", gen),
-            single_property_schema_of::<GatedReverbParameters>("GatedReverb", gen),
-            single_property_schema_of::<DriveChorusParameters<64, 80>>("OverdriveChorus", gen),
-            single_property_schema_of::<DriveFlangerParameters<64>>("OverdriveFlanger", gen),
-            single_property_schema_of::<DriveDelayParameters<64>>("OverdriveDelay", gen),
-            single_property_schema_of::<DriveChorusParameters<127, 70>>("DistortionChorus", gen),
-            single_property_schema_of::<DriveFlangerParameters<127>>("DistortionFlanger", gen),
-            single_property_schema_of::<DriveDelayParameters<127>>("DistortionDelay", gen),
-            single_property_schema_of::<EnhancerChorusParameters>("EnhancerChorus", gen),
-            single_property_schema_of::<EnhancerFlangerParameters>("EnhancerFlanger", gen),
-            single_property_schema_of::<EnhancerDelayParameters>("EnhancerDelay", gen),
-            single_property_schema_of::<ChorusDelayParameters>("ChorusDelay", gen),
-            single_property_schema_of::<FlangerDelayParameters>("FlangerDelay", gen),
-            single_property_schema_of::<ChorusFlangerParameters>("ChorusFlanger", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedVrChorus", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedVrTremolo", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedVrAutoWah", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedVrPhaser", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedOrganMulti", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedLinedrive", gen),
-            single_property_schema_of::<UnusedParameters<32>>("UnusedSmallPhaser", gen),
-            single_property_schema_of::<SympatheticResonanceParameters>("SympatheticResonance", gen),
-            single_property_schema_of::<OtherMfxParameters>("Other", gen)
+            schema_of::<UnusedParameters<32>>("Thru", gen),
+            schema_of::<EqualizerParameters>("Equalizer", gen),
+            schema_of::<SpectrumParameters>("Spectrum", gen),
+            schema_of::<IsolatorParameters>("Isolator", gen),
+            schema_of::<LowBoostParameters>("LowBoost", gen),
+            schema_of::<SuperFilterParameters>("SuperFilter", gen),
+            schema_of::<StepFilterParameters>("StepFilter", gen),
+            schema_of::<EnhancerParameters>("Enhancer", gen),
+            schema_of::<AutoWahParameters>("AutoWah", gen),
+            schema_of::<HumanizerParameters>("Humanizer", gen),
+            schema_of::<SpeakerSimulatorParameters>("SpeakerSimulator", gen),
+            schema_of::<PhaserParameters>("Phaser", gen),
+            schema_of::<StepPhaserParameters>("StepPhaser", gen),
+            schema_of::<MultiStagePhaserParameters>("MultiStagePhaser", gen),
+            schema_of::<InfinitePhaserParameters>("InfinitePhaser", gen),
+            schema_of::<RingModulatorParameters>("RingModulator", gen),
+            schema_of::<StepRingModulatorParameters>("StepRingModulator", gen),
+            schema_of::<CyclicalParameters>("Tremolo", gen),
+            schema_of::<CyclicalParameters>("AutoPan", gen),
+            schema_of::<StepPanParameters>("StepPan", gen),
+            schema_of::<SlicerParameters>("Slicer", gen),
+            schema_of::<RotaryParameters>("Rotary", gen),
+            schema_of::<VkRotaryParameters>("VkRotary", gen),
+            schema_of::<ChorusParameters>("Chorus", gen),
+            schema_of::<FlangerParameters>("Flanger", gen),
+            schema_of::<StepFlangerParameters>("StepFlanger", gen),
+            schema_of::<HexaChorusParameters>("HexaChorus", gen),
+            schema_of::<TremoloChorusParameters>("TremoloChorus", gen),
+            schema_of::<SpaceDParameters>("SpaceD", gen),
+            schema_of::<Chorus3DParameters>("Chorus3D", gen),
+            schema_of::<Flanger3DParameters>("Flanger3D", gen),
+            schema_of::<StepFlanger3DParameters>("StepFlanger3D", gen),
+            schema_of::<TwoBandChorusParameters>("TwoBandChorus", gen),
+            schema_of::<TwoBandFlangerParameters>("TwoBandFlanger", gen),
+            schema_of::<TwoBandStepFlangerParameters>("TwoBandStepFlanger", gen),
+            schema_of::<DriveParameters<0, 70>>("Overdrive", gen),
+            schema_of::<DriveParameters<3, 50>>("Distortion", gen),
+            schema_of::<VsDriveParameters<0>>("VsOverdrive", gen),
+            schema_of::<VsDriveParameters<3>>("VsDistortion", gen),
+            schema_of::<GuitarAmpSimulatorParameters>("GuitarAmpSimulator", gen),
+            schema_of::<CompressorParameters>("Compressor", gen),
+            schema_of::<LimiterParameters>("Limiter", gen),
+            schema_of::<GateParameters>("Gate", gen),
+            schema_of::<DelayParameters>("Delay", gen),
+            schema_of::<LongDelayParameters>("LongDelay", gen),
+            schema_of::<SerialDelayParameters>("SerialDelay", gen),
+            schema_of::<ModulationDelayParameters>("ModulationDelay", gen),
+            schema_of::<ThreeTapPanDelayParameters>("ThreeTapPanDelay", gen),
+            schema_of::<FourTapPanDelayParameters>("FourTapPanDelay", gen),
+            schema_of::<MultiTapDelayParameters>("MultiTapDelay", gen),
+            schema_of::<ReverseDelayParameters>("ReverseDelay", gen),
+            schema_of::<ShuffleDelayParameters>("ShuffleDelay", gen),
+            schema_of::<Delay3DParameters>("Delay3D", gen),
+            schema_of::<TimeCtrlDelayParameters>("TimeCtrlDelay", gen),
+            schema_of::<LongTimeCtrlDelayParameters>("LongTimeCtrlDelay", gen),
+            schema_of::<TapeEchoParameters>("TapeEcho", gen),
+            schema_of::<LofiNoiseParameters>("LofiNoise", gen),
+            schema_of::<LofiCompressParameters>("LofiCompress", gen),
+            schema_of::<LofiRadioParameters>("LofiRadio", gen),
+            schema_of::<TelephoneParameters>("Telephone", gen),
+            schema_of::<PhonographParameters>("Phonograph", gen),
+            schema_of::<PitchShifterParameters>("PitchShifter", gen),
+            schema_of::<TwoVoicePitchShifterParameters>("TwoVoicePitchShifter", gen),
+            schema_of::<StepPitchShifterParameters>("StepPitchShifter", gen),
+            schema_of::<ReverbParameters>("Reverb", gen),
+            schema_of::<GatedReverbParameters>("GatedReverb", gen),
+            schema_of::<DriveChorusParameters<64, 80>>("OverdriveChorus", gen),
+            schema_of::<DriveFlangerParameters<64>>("OverdriveFlanger", gen),
+            schema_of::<DriveDelayParameters<64>>("OverdriveDelay", gen),
+            schema_of::<DriveChorusParameters<127, 70>>("DistortionChorus", gen),
+            schema_of::<DriveFlangerParameters<127>>("DistortionFlanger", gen),
+            schema_of::<DriveDelayParameters<127>>("DistortionDelay", gen),
+            schema_of::<EnhancerChorusParameters>("EnhancerChorus", gen),
+            schema_of::<EnhancerFlangerParameters>("EnhancerFlanger", gen),
+            schema_of::<EnhancerDelayParameters>("EnhancerDelay", gen),
+            schema_of::<ChorusDelayParameters>("ChorusDelay", gen),
+            schema_of::<FlangerDelayParameters>("FlangerDelay", gen),
+            schema_of::<ChorusFlangerParameters>("ChorusFlanger", gen),
+            schema_of::<UnusedParameters<32>>("UnusedVrChorus", gen),
+            schema_of::<UnusedParameters<32>>("UnusedVrTremolo", gen),
+            schema_of::<UnusedParameters<32>>("UnusedVrAutoWah", gen),
+            schema_of::<UnusedParameters<32>>("UnusedVrPhaser", gen),
+            schema_of::<UnusedParameters<32>>("UnusedOrganMulti", gen),
+            schema_of::<UnusedParameters<32>>("UnusedLinedrive", gen),
+            schema_of::<UnusedParameters<32>>("UnusedSmallPhaser", gen),
+            schema_of::<SympatheticResonanceParameters>("SympatheticResonance", gen),
+            effect_type_schema_of::<OtherMfxParameters>(Effect::Mfx, "Other", gen)
         ])
     }
 }
 
+fn schema_of<T: Parameters<32> + JsonSchema + serde::Serialize>(type_name: &str, gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    parameters_schema_of::<T, 32>(Effect::Mfx, type_name, gen)
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
 pub struct OtherMfxParameters {
+    #[schemars(title = "MFX Number", description = "Number of an MFX type which is not used by the RD300NX (86-255)")]
     #[validate(range(min = 86))]
     mfx_number: u8,
+    #[schemars(title = "Unknown", description = "Parameters of an MFX type which is not used by the RD300NX, kept so that the RDS file is unchanged")]
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
     #[schemars(with = "serialize_default_terminated_array::DefaultTerminatedArraySchema::<Parameter, 32>")]
@@ -786,6 +988,10 @@ impl OtherMfxParameters {
     fn parameters(&self) -> [Parameter; 32] {
         self.unknown
     }
+
+    fn describe(&self) -> Vec<ParameterInfo> {
+        Vec::new()
+    }
 }
 
 impl Default for OtherMfxParameters {
@@ -800,15 +1006,20 @@ impl Default for OtherMfxParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct EqualizerParameters {
     low_freq: LogFrequency<20, 400>,
+    #[validate]
     low_gain: Gain,
     mid1_freq: LogFrequency<200, 8000>,
+    #[validate]
     mid1_gain: Gain,
     mid1_q: QFactor,
     mid2_freq: LogFrequency<200, 8000>,
+    #[validate]
     mid2_gain: Gain,
     mid2_q: QFactor,
     high_freq: LogFrequency<2000, 16000>,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -838,15 +1049,24 @@ impl Default for EqualizerParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct SpectrumParameters {
+    #[validate]
     band1_250hz: Gain,
+    #[validate]
     band2_500hz: Gain,
+    #[validate]
     band3_1000hz: Gain,
+    #[validate]
     band4_1250hz: Gain,
+    #[validate]
     band5_2000hz: Gain,
+    #[validate]
     band6_3150hz: Gain,
+    #[validate]
     band7_4000hz: Gain,
+    #[validate]
     band8_8000hz: Gain,
     q: QFactor,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -875,15 +1095,22 @@ impl Default for SpectrumParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct IsolatorParameters {
+    #[validate]
     boost_cut_low: BoostGain,
+    #[validate]
     boost_cut_mid: BoostGain,
+    #[validate]
     boost_cut_high: BoostGain,
     a_phase_low_sw: Switch,
+    #[validate]
     a_phase_low_level: Level,
     a_phase_mid_sw: Switch,
+    #[validate]
     a_phase_mid_level: Level,
     low_boost_sw: Switch,
+    #[validate]
     low_boost_level: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -913,10 +1140,14 @@ impl Default for IsolatorParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LowBoostParameters {
     boost_freq: FineFrequency,
+    #[validate]
     boost_gain: Int<0, 12>,
     boost_width: BoostWidth,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -943,16 +1174,22 @@ impl Default for LowBoostParameters {
 pub struct SuperFilterParameters {
     filter_type: SuperFilterType,
     filter_slope: FilterSlope,
+    #[validate]
     filter_cutoff: Level,
+    #[validate]
     filter_resonance: Level,
+    #[validate]
     filter_gain: Int<0, 12>,
     modulation_sw: Switch,
     modulation_wave: Wave,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
+    #[validate]
     attack: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -984,30 +1221,50 @@ impl Default for SuperFilterParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct StepFilterParameters {
+    #[validate]
     step1: Level,
+    #[validate]
     step2: Level,
+    #[validate]
     step3: Level,
+    #[validate]
     step4: Level,
+    #[validate]
     step5: Level,
+    #[validate]
     step6: Level,
+    #[validate]
     step7: Level,
+    #[validate]
     step8: Level,
+    #[validate]
     step9: Level,
+    #[validate]
     step10: Level,
+    #[validate]
     step11: Level,
+    #[validate]
     step12: Level,
+    #[validate]
     step13: Level,
+    #[validate]
     step14: Level,
+    #[validate]
     step15: Level,
+    #[validate]
     step16: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     attack: Level,
     filter_type: SuperFilterType,
     filter_slope: FilterSlope,
+    #[validate]
     filter_resonance: Level,
+    #[validate]
     filter_gain: Int<0, 12>,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1051,10 +1308,15 @@ impl Default for StepFilterParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct EnhancerParameters {
+    #[validate]
     sensitivity: Level,
+    #[validate]
     mix: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1079,17 +1341,24 @@ impl Default for EnhancerParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct AutoWahParameters {
     filter_type: SimpleFilterType,
+    #[validate]
     manual: Level,
+    #[validate]
     peak: Level,
+    #[validate]
     sensitivity: Level,
     polarity: Direction,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1122,19 +1391,26 @@ impl Default for AutoWahParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct HumanizerParameters {
     drive_sw: Switch,
+    #[validate]
     drive: Level,
     vowel1: Vowel,
     vowel2: Vowel,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     input_sync_sw: Switch,
+    #[validate]
     input_sync_threshold: Level,
+    #[validate]
     manual: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     pan: Pan,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1169,9 +1445,13 @@ impl Default for HumanizerParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct SpeakerSimulatorParameters {
     speaker: SpeakerType,
+    #[validate]
     mic_setting: MicSetting,
+    #[validate]
     mic_level: Level,
+    #[validate]
     direct_level: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1196,17 +1476,24 @@ impl Default for SpeakerSimulatorParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct PhaserParameters {
     mode: PhaserMode,
+    #[validate]
     manual: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     polarity: PhaserPolarity,
+    #[validate]
     resonance: Level,
     cross_feedback: Feedback,
+    #[validate]
     mix: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1239,20 +1526,27 @@ impl Default for PhaserParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct StepPhaserParameters {
     mode: PhaserMode,
+    #[validate]
     manual: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     polarity: PhaserPolarity,
+    #[validate]
     resonance: Level,
     cross_feedback: Feedback,
     step_rate_mode: RateMode,
     step_rate_hz: StepLinearFrequency,
     step_rate_note: NoteLength,
+    #[validate]
     mix: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1288,16 +1582,23 @@ impl Default for StepPhaserParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct MultiStagePhaserParameters {
     mode: MultiPhaserMode,
+    #[validate]
     manual: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
+    #[validate]
     resonance: Level,
+    #[validate]
     mix: Level,
     pan: Pan,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1328,13 +1629,19 @@ impl Default for MultiStagePhaserParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct InfinitePhaserParameters {
+    #[validate]
     mode: Int<1, 4>,
     speed: Int<-100, 100>,
+    #[validate]
     resonance: Level,
+    #[validate]
     mix: Level,
     pan: Pan,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1361,12 +1668,17 @@ impl Default for InfinitePhaserParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct RingModulatorParameters {
+    #[validate]
     frequency: Level,
+    #[validate]
     sensitivity: Level,
     polarity: Direction,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1392,29 +1704,49 @@ impl Default for RingModulatorParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct StepRingModulatorParameters {
+    #[validate]
     step1: Level,
+    #[validate]
     step2: Level,
+    #[validate]
     step3: Level,
+    #[validate]
     step4: Level,
+    #[validate]
     step5: Level,
+    #[validate]
     step6: Level,
+    #[validate]
     step7: Level,
+    #[validate]
     step8: Level,
+    #[validate]
     step9: Level,
+    #[validate]
     step10: Level,
+    #[validate]
     step11: Level,
+    #[validate]
     step12: Level,
+    #[validate]
     step13: Level,
+    #[validate]
     step14: Level,
+    #[validate]
     step15: Level,
+    #[validate]
     step16: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     attack: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1461,9 +1793,13 @@ pub struct CyclicalParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1509,9 +1845,12 @@ pub struct StepPanParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     attack: Level,
     input_sync_sw: Switch,
+    #[validate]
     input_sync_threshold: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1553,30 +1892,50 @@ impl Default for StepPanParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct SlicerParameters {
+    #[validate]
     step1: Level,
+    #[validate]
     step2: Level,
+    #[validate]
     step3: Level,
+    #[validate]
     step4: Level,
+    #[validate]
     step5: Level,
+    #[validate]
     step6: Level,
+    #[validate]
     step7: Level,
+    #[validate]
     step8: Level,
+    #[validate]
     step9: Level,
+    #[validate]
     step10: Level,
+    #[validate]
     step11: Level,
+    #[validate]
     step12: Level,
+    #[validate]
     step13: Level,
+    #[validate]
     step14: Level,
+    #[validate]
     step15: Level,
+    #[validate]
     step16: Level,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     attack: Level,
     input_sync_sw: Switch,
+    #[validate]
     input_sync_threshold: Level,
     mode: SlicerMode,
+    #[validate]
     shuffle: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1623,13 +1982,19 @@ pub struct RotaryParameters {
     speed: Speed,
     woofer_slow_rate: LinearFrequency,
     woofer_fast_rate: LinearFrequency,
+    #[validate]
     woofer_accel: UInt<0, 15>,
+    #[validate]
     woofer_level: Level,
     tweeter_slow_rate: LinearFrequency,
     tweeter_fast_rate: LinearFrequency,
+    #[validate]
     tweeter_accel: UInt<0, 15>,
+    #[validate]
     tweeter_level: Level,
+    #[validate]
     separation: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1663,17 +2028,27 @@ pub struct VkRotaryParameters {
     brake: Switch,
     woofer_slow_rate: LinearFrequency,
     woofer_fast_rate: LinearFrequency,
+    #[validate]
     woofer_trans_up: Level,
+    #[validate]
     woofer_trans_down: Level,
+    #[validate]
     woofer_level: Level,
     tweeter_slow_rate: LinearFrequency,
     tweeter_fast_rate: LinearFrequency,
+    #[validate]
     tweeter_trans_up: Level,
+    #[validate]
     tweeter_trans_down: Level,
+    #[validate]
     tweeter_level: Level,
+    #[validate]
     spread: UInt<0, 10>,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1714,11 +2089,15 @@ pub struct ChorusParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1755,12 +2134,16 @@ pub struct FlangerParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
     feedback: Feedback,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1798,15 +2181,19 @@ pub struct StepFlangerParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
     feedback: Feedback,
     step_rate_mode: RateMode,
     step_rate_hz: StepLinearFrequency,
     step_rate_note: NoteLength,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1845,11 +2232,15 @@ pub struct HexaChorusParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
+    #[validate]
     pre_delay_deviation: UInt<0, 20>,
     depth_deviation: Int<-20, 20>,
+    #[validate]
     pan_deviation: UInt<0, 20>,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1882,13 +2273,16 @@ pub struct TremoloChorusParameters {
     cho_rate_mode: RateMode,
     cho_rate_hz: LinearFrequency,
     cho_rate_note: NoteLength,
+    #[validate]
     cho_depth: Level,
     trem_rate_mode: RateMode,
     trem_rate_hz: LinearFrequency,
     trem_rate_note: NoteLength,
+    #[validate]
     trem_separation: Level,
     trem_phase: Phase,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1923,11 +2317,15 @@ pub struct SpaceDParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1962,12 +2360,16 @@ pub struct Chorus3DParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
     output_mode: OutputMode,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2005,13 +2407,17 @@ pub struct Flanger3DParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
     feedback: Feedback,
     output_mode: OutputMode,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2050,6 +2456,7 @@ pub struct StepFlanger3DParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
     feedback: Feedback,
@@ -2057,9 +2464,12 @@ pub struct StepFlanger3DParameters {
     step_rate_hz: StepLinearFrequency,
     step_rate_note: NoteLength,
     output_mode: OutputMode,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2100,15 +2510,18 @@ pub struct TwoBandChorusParameters {
     low_rate_mode: RateMode,
     low_rate_hz: LinearFrequency,
     low_rate_note: NoteLength,
+    #[validate]
     low_depth: Level,
     low_phase: Phase,
     high_pre_delay: LogMilliseconds,
     high_rate_mode: RateMode,
     high_rate_hz: LinearFrequency,
     high_rate_note: NoteLength,
+    #[validate]
     high_depth: Level,
     high_phase: Phase,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2147,6 +2560,7 @@ pub struct TwoBandFlangerParameters {
     low_rate_mode: RateMode,
     low_rate_hz: LinearFrequency,
     low_rate_note: NoteLength,
+    #[validate]
     low_depth: Level,
     low_phase: Phase,
     low_feedback: Feedback,
@@ -2154,10 +2568,12 @@ pub struct TwoBandFlangerParameters {
     high_rate_mode: RateMode,
     high_rate_hz: LinearFrequency,
     high_rate_note: NoteLength,
+    #[validate]
     high_depth: Level,
     high_phase: Phase,
     high_feedback: Feedback,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2198,6 +2614,7 @@ pub struct TwoBandStepFlangerParameters {
     low_rate_mode: RateMode,
     low_rate_hz: LinearFrequency,
     low_rate_note: NoteLength,
+    #[validate]
     low_depth: Level,
     low_phase: Phase,
     low_feedback: Feedback,
@@ -2208,6 +2625,7 @@ pub struct TwoBandStepFlangerParameters {
     high_rate_mode: RateMode,
     high_rate_hz: LinearFrequency,
     high_rate_note: NoteLength,
+    #[validate]
     high_depth: Level,
     high_phase: Phase,
     high_feedback: Feedback,
@@ -2215,6 +2633,7 @@ pub struct TwoBandStepFlangerParameters {
     high_step_rate_hz: StepLinearFrequency,
     high_step_rate_note: NoteLength,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2256,11 +2675,15 @@ impl Default for TwoBandStepFlangerParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DriveParameters<const DEFAULT_AMP_INDEX: i16, const DEFAULT_LEVEL: u16> {
+    #[validate]
     drive: Level,
     amp_type: AmpType,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     pan: Pan,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2285,13 +2708,18 @@ impl<const DAI: i16, const DL: u16> Default for DriveParameters<DAI, DL> {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct VsDriveParameters<const DEFAULT_AMP_INDEX: i16> {
+    #[validate]
     drive: Level,
+    #[validate]
     tone: Level,
     amp_sw: Switch,
     amp_type: AmpType,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     pan: Pan,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2320,20 +2748,30 @@ impl<const DAI: i16> Default for VsDriveParameters<DAI> {
 pub struct GuitarAmpSimulatorParameters {
     pre_amp_sw: Switch,
     pre_amp_type: PreAmpType,
+    #[validate]
     pre_amp_volume: Level,
+    #[validate]
     pre_amp_master: Level,
     pre_amp_gain: PreAmpGain,
+    #[validate]
     pre_amp_bass: Level,
+    #[validate]
     pre_amp_middle: Level,
+    #[validate]
     pre_amp_treble: Level,
+    #[validate]
     pre_amp_presence: Level,
     pre_amp_bright: Switch,
     speaker_sw: Switch,
     speaker_type: SpeakerType,
+    #[validate]
     mic_setting: MicSetting,
+    #[validate]
     mic_level: Level,
+    #[validate]
     direct_level: Level,
     pan: Pan,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2369,11 +2807,17 @@ impl Default for GuitarAmpSimulatorParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct CompressorParameters {
+    #[validate]
     attack: Level,
+    #[validate]
     threshold: Level,
+    #[validate]
     post_gain: PostGain,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2398,12 +2842,18 @@ impl Default for CompressorParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LimiterParameters {
+    #[validate]
     release: Level,
+    #[validate]
     threshold: Level,
     ratio: CompressionRatio,
+    #[validate]
     post_gain: PostGain,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2429,12 +2879,17 @@ impl Default for LimiterParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct GateParameters {
+    #[validate]
     threshold: Level,
     mode: GateMode,
+    #[validate]
     attack: Level,
+    #[validate]
     hold: Level,
+    #[validate]
     release: Level,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2461,9 +2916,11 @@ impl Default for GateParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DelayParameters {
     delay_left_mode: DelayMode,
+    #[validate]
     delay_left_ms: LinearMilliseconds<1300>,
     delay_left_note: NoteLength,
     delay_right_mode: DelayMode,
+    #[validate]
     delay_right_ms: LinearMilliseconds<1300>,
     delay_right_note: NoteLength,
     phase_left: PhaseType,
@@ -2471,9 +2928,12 @@ pub struct DelayParameters {
     feedback_mode: FeedbackMode,
     feedback_percent: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2508,15 +2968,19 @@ impl Default for DelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LongDelayParameters {
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
     phase_type: PhaseType,
     feedback_percent: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
     pan: Pan,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2547,19 +3011,24 @@ impl Default for LongDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct SerialDelayParameters {
     delay_1_mode: DelayMode,
+    #[validate]
     delay_1_ms: LinearMilliseconds<1300>,
     delay_1_note: NoteLength,
     delay_1_feedback: Feedback,
     delay_1_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_2_mode: DelayMode,
+    #[validate]
     delay_2_ms: LinearMilliseconds<1300>,
     delay_2_note: NoteLength,
     delay_2_feedback: Feedback,
     delay_2_hf_damp: LogFrequencyOrByPass<200, 8000>,
     pan: Pan,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2594,9 +3063,11 @@ impl Default for SerialDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ModulationDelayParameters {
     delay_left_mode: DelayMode,
+    #[validate]
     delay_left_ms: LinearMilliseconds<1300>,
     delay_left_note: NoteLength,
     delay_right_mode: DelayMode,
+    #[validate]
     delay_right_ms: LinearMilliseconds<1300>,
     delay_right_note: NoteLength,
     feedback_mode: FeedbackMode,
@@ -2605,11 +3076,15 @@ pub struct ModulationDelayParameters {
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     depth: Level,
     phase: Phase,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2647,22 +3122,31 @@ impl Default for ModulationDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ThreeTapPanDelayParameters {
     delay_left_mode: DelayMode,
+    #[validate]
     delay_left_ms: LinearMilliseconds<2600>,
     delay_left_note: NoteLength,
     delay_right_mode: DelayMode,
+    #[validate]
     delay_right_ms: LinearMilliseconds<2600>,
     delay_right_note: NoteLength,
     delay_centre_mode: DelayMode,
+    #[validate]
     delay_centre_ms: LinearMilliseconds<2600>,
     delay_centre_note: NoteLength,
     centre_feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     left_level: Level,
+    #[validate]
     right_level: Level,
+    #[validate]
     centre_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2700,26 +3184,37 @@ impl Default for ThreeTapPanDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct FourTapPanDelayParameters {
     delay_1_mode: DelayMode,
+    #[validate]
     delay_1_ms: LinearMilliseconds<2600>,
     delay_1_note: NoteLength,
     delay_2_mode: DelayMode,
+    #[validate]
     delay_2_ms: LinearMilliseconds<2600>,
     delay_2_note: NoteLength,
     delay_3_mode: DelayMode,
+    #[validate]
     delay_3_ms: LinearMilliseconds<2600>,
     delay_3_note: NoteLength,
     delay_4_mode: DelayMode,
+    #[validate]
     delay_4_ms: LinearMilliseconds<2600>,
     delay_4_note: NoteLength,
     delay_1_feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     delay_1_level: Level,
+    #[validate]
     delay_2_level: Level,
+    #[validate]
     delay_3_level: Level,
+    #[validate]
     delay_4_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2761,15 +3256,19 @@ impl Default for FourTapPanDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct MultiTapDelayParameters {
     delay_1_mode: DelayMode,
+    #[validate]
     delay_1_ms: LinearMilliseconds<2600>,
     delay_1_note: NoteLength,
     delay_2_mode: DelayMode,
+    #[validate]
     delay_2_ms: LinearMilliseconds<2600>,
     delay_2_note: NoteLength,
     delay_3_mode: DelayMode,
+    #[validate]
     delay_3_ms: LinearMilliseconds<2600>,
     delay_3_note: NoteLength,
     delay_4_mode: DelayMode,
+    #[validate]
     delay_4_ms: LinearMilliseconds<2600>,
     delay_4_note: NoteLength,
     delay_1_feedback: Feedback,
@@ -2778,13 +3277,20 @@ pub struct MultiTapDelayParameters {
     delay_2_pan: Pan,
     delay_3_pan: Pan,
     delay_4_pan: Pan,
+    #[validate]
     delay_1_level: Level,
+    #[validate]
     delay_2_level: Level,
+    #[validate]
     delay_3_level: Level,
+    #[validate]
     delay_4_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2829,32 +3335,43 @@ impl Default for MultiTapDelayParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ReverseDelayParameters {
+    #[validate]
     threshold: Level,
     rev_delay_mode: DelayMode,
+    #[validate]
     rev_delay_ms: LinearMilliseconds<1300>,
     rev_delay_note: NoteLength,
     rev_delay_feedback: Feedback,
     rev_delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     rev_delay_pan: Pan,
+    #[validate]
     rev_delay_level: Level,
     delay_1_mode: DelayMode,
+    #[validate]
     delay_1_ms: LinearMilliseconds<1300>,
     delay_1_note: NoteLength,
     delay_2_mode: DelayMode,
+    #[validate]
     delay_2_ms: LinearMilliseconds<1300>,
     delay_2_note: NoteLength,
     delay_3_mode: DelayMode,
+    #[validate]
     delay_3_ms: LinearMilliseconds<1300>,
     delay_3_note: NoteLength,
     delay_3_feedback: Feedback,
     delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_1_pan: Pan,
     delay_2_pan: Pan,
+    #[validate]
     delay_1_level: Level,
+    #[validate]
     delay_2_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2901,19 +3418,27 @@ impl Default for ReverseDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ShuffleDelayParameters {
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
+    #[validate]
     shuffle_rate: UInt<0, 100>,
+    #[validate]
     acceleration: UInt<0, 15>,
     feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
     pan_a: Pan,
     pan_b: Pan,
+    #[validate]
     level_a: Level,
+    #[validate]
     level_b: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -2948,23 +3473,32 @@ impl Default for ShuffleDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct Delay3DParameters {
     delay_left_mode: DelayMode,
+    #[validate]
     delay_left_ms: LinearMilliseconds<2600>,
     delay_left_note: NoteLength,
     delay_right_mode: DelayMode,
+    #[validate]
     delay_right_ms: LinearMilliseconds<2600>,
     delay_right_note: NoteLength,
     delay_centre_mode: DelayMode,
+    #[validate]
     delay_centre_ms: LinearMilliseconds<2600>,
     delay_centre_note: NoteLength,
     centre_feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     left_level: Level,
+    #[validate]
     right_level: Level,
+    #[validate]
     centre_level: Level,
     output_mode: OutputMode,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3003,14 +3537,19 @@ impl Default for Delay3DParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct TimeCtrlDelayParameters {
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<1300>,
     delay_note: NoteLength,
+    #[validate]
     acceleration: UInt<0, 15>,
     feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3040,15 +3579,20 @@ impl Default for TimeCtrlDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LongTimeCtrlDelayParameters {
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
+    #[validate]
     acceleration: UInt<0, 15>,
     feedback: Feedback,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
     pan: Pan,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3079,18 +3623,28 @@ impl Default for LongTimeCtrlDelayParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct TapeEchoParameters {
     mode: TapeHeads,
+    #[validate]
     repeat_rate: Level,
+    #[validate]
     intensity: Level,
+    #[validate]
     bass: Gain,
+    #[validate]
     treble: Gain,
     head_short_pan: Pan,
     head_middle_pan: Pan,
     head_long_pan: Pan,
+    #[validate]
     tape_distortion: UInt<0, 5>,
+    #[validate]
     wow_flutter_rate: Level,
+    #[validate]
     wow_flutter_depth: Level,
+    #[validate]
     echo_level: Level,
+    #[validate]
     direct_level: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3123,21 +3677,28 @@ impl Default for TapeEchoParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LofiNoiseParameters {
+    #[validate]
     lofi_type: LofiType,
     post_filter_type: FilterType,
     post_filter_cutoff: LogFrequency<200, 8000>,
     noise_type: NoiseType,
     noise_lpf: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     noise_level: Level,
     disc_noise_type: DiscTypeWithRandom,
     disc_noise_lpf: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     disc_noise_level: Level,
     hum_noise_type: HumFrequency,
     hum_noise_lpf: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     hum_noise_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3172,13 +3733,18 @@ impl Default for LofiNoiseParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LofiCompressParameters {
+    #[validate]
     pre_filter_type: Int<1, 6>,
+    #[validate]
     lofi_type: LofiType,
     post_filter_type: FilterType,
     post_filter_cutoff: LogFrequency<200, 8000>,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3205,14 +3771,20 @@ impl Default for LofiCompressParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct LofiRadioParameters {
+    #[validate]
     lofi_type: LofiType,
     post_filter_type: FilterType,
     post_filter_cutoff: LogFrequency<200, 8000>,
+    #[validate]
     radio_detune: Level,
+    #[validate]
     radio_noise_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3240,9 +3812,12 @@ impl Default for LofiRadioParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct TelephoneParameters {
+    #[validate]
     voice_quality: UInt<0, 15>,
+    #[validate]
     treble: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3265,18 +3840,29 @@ impl Default for TelephoneParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct PhonographParameters {
+    #[validate]
     signal_distortion: Level,
+    #[validate]
     frequencey_range: Level,
     disc_type: DiscType,
+    #[validate]
     scartch_noise_level: Level,
+    #[validate]
     dust_noise_level: Level,
+    #[validate]
     hiss_noise_level: Level,
+    #[validate]
     total_noise_level: Level,
+    #[validate]
     wow: Level,
+    #[validate]
     flutter: Level,
+    #[validate]
     random: Level,
+    #[validate]
     total_wf: Level,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3308,15 +3894,20 @@ impl Default for PhonographParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct PitchShifterParameters {
+    #[validate]
     coarse_semitones: Semitones,
     fine_percent: EvenPercent<100>,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<1300>,
     delay_note: NoteLength,
     feedback: Feedback,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3345,25 +3936,34 @@ impl Default for PitchShifterParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct TwoVoicePitchShifterParameters {
+    #[validate]
     pitch_1_coarse_semitones: Semitones,
     pitch_1_fine_percent: EvenPercent<100>,
     pitch_1_delay_mode: DelayMode,
+    #[validate]
     pitch_1_delay_ms: LinearMilliseconds<1300>,
     pitch_1_delay_note: NoteLength,
     pitch_1_feedback: Feedback,
     pitch_1_pan: Pan,
+    #[validate]
     pitch_1_level: Level,
+    #[validate]
     pitch_2_coarse_semitones: Semitones,
     pitch_2_fine_percent: EvenPercent<100>,
     pitch_2_delay_mode: DelayMode,
+    #[validate]
     pitch_2_delay_ms: LinearMilliseconds<1300>,
     pitch_2_delay_note: NoteLength,
     pitch_2_feedback: Feedback,
     pitch_2_pan: Pan,
+    #[validate]
     pitch_2_level: Level,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3402,35 +4002,57 @@ impl Default for TwoVoicePitchShifterParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct StepPitchShifterParameters {
+    #[validate]
     step1: Semitones,
+    #[validate]
     step2: Semitones,
+    #[validate]
     step3: Semitones,
+    #[validate]
     step4: Semitones,
+    #[validate]
     step5: Semitones,
+    #[validate]
     step6: Semitones,
+    #[validate]
     step7: Semitones,
+    #[validate]
     step8: Semitones,
+    #[validate]
     step9: Semitones,
+    #[validate]
     step10: Semitones,
+    #[validate]
     step11: Semitones,
+    #[validate]
     step12: Semitones,
+    #[validate]
     step13: Semitones,
+    #[validate]
     step14: Semitones,
+    #[validate]
     step15: Semitones,
+    #[validate]
     step16: Semitones,
     rate_mode: RateMode,
     rate_hz: LinearFrequency,
     rate_note: NoteLength,
+    #[validate]
     attack: Level,
+    #[validate]
     gate_time: Level,
     fine_percent: EvenPercent<100>,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<1300>,
     delay_note: NoteLength,
     feedback: Feedback,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3481,11 +4103,15 @@ impl Default for StepPitchShifterParameters {
 pub struct ReverbParameters {
     character: ReverbOnlyCharacter,
     pre_delay: LogMilliseconds,
+    #[validate]
     time: Level,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3515,9 +4141,12 @@ pub struct GatedReverbParameters {
     gate_type: GateType,
     pre_delay: LogMilliseconds,
     gate_time: GateTime,
+    #[validate]
     low_gain: Gain,
+    #[validate]
     high_gain: Gain,
     balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3543,14 +4172,17 @@ impl Default for GatedReverbParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DriveChorusParameters<const DEFAULT_DRIVE: u16, const DEFAULT_LEVEL: u16> {
+    #[validate]
     drive: Level,
     pan: Pan,
     chorus_pre_delay: LogMilliseconds,
     chorus_rate_mode: RateMode,
     chorus_rate_hz: LinearFrequency,
     chorus_rate_note: NoteLength,
+    #[validate]
     chorus_depth: Level,
     chorus_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3578,15 +4210,18 @@ impl<const DD: u16, const DL: u16> Default for DriveChorusParameters<DD, DL> {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DriveFlangerParameters<const DEFAULT_DRIVE: u16> {
+    #[validate]
     drive: Level,
     pan: Pan,
     flanger_pre_delay: LogMilliseconds,
     flanger_rate_mode: RateMode,
     flanger_rate_hz: LinearFrequency,
     flanger_rate_note: NoteLength,
+    #[validate]
     flanger_depth: Level,
     flanger_feedback: Feedback,
     flanger_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3615,14 +4250,17 @@ impl<const DD: u16> Default for DriveFlangerParameters<DD> {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct DriveDelayParameters<const DEFAULT_DRIVE: u16> {
+    #[validate]
     drive: Level,
     pan: Pan,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
     delay_feedback: Feedback,
     delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3650,14 +4288,18 @@ impl<const DD: u16> Default for DriveDelayParameters<DD> {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct EnhancerChorusParameters {
+    #[validate]
     enhancer_sensitivity: Level,
+    #[validate]
     enhancer_mix: Level,
     chorus_pre_delay: LogMilliseconds,
     chorus_rate_mode: RateMode,
     chorus_rate_hz: LinearFrequency,
     chorus_rate_note: NoteLength,
+    #[validate]
     chorus_depth: Level,
     chorus_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3685,15 +4327,19 @@ impl Default for EnhancerChorusParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct EnhancerFlangerParameters {
+    #[validate]
     enhancer_sensitivity: Level,
+    #[validate]
     enhancer_mix: Level,
     flanger_pre_delay: LogMilliseconds,
     flanger_rate_mode: RateMode,
     flanger_rate_hz: LinearFrequency,
     flanger_rate_note: NoteLength,
+    #[validate]
     flanger_depth: Level,
     flanger_feedback: Feedback,
     flanger_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3722,14 +4368,18 @@ impl Default for EnhancerFlangerParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct EnhancerDelayParameters {
+    #[validate]
     enhancer_sensitivity: Level,
+    #[validate]
     enhancer_mix: Level,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
     delay_feedback: Feedback,
     delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3761,14 +4411,17 @@ pub struct ChorusDelayParameters {
     chorus_rate_mode: RateMode,
     chorus_rate_hz: LinearFrequency,
     chorus_rate_note: NoteLength,
+    #[validate]
     chorus_depth: Level,
     chorus_balance: Balance,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
     delay_feedback: Feedback,
     delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3804,15 +4457,18 @@ pub struct FlangerDelayParameters {
     flanger_rate_mode: RateMode,
     flanger_rate_hz: LinearFrequency,
     flanger_rate_note: NoteLength,
+    #[validate]
     flanger_depth: Level,
     flanger_feedback: Feedback,
     flanger_balance: Balance,
     delay_mode: DelayMode,
+    #[validate]
     delay_ms: LinearMilliseconds<2600>,
     delay_note: NoteLength,
     delay_feedback: Feedback,
     delay_hf_damp: LogFrequencyOrByPass<200, 8000>,
     delay_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3849,15 +4505,18 @@ pub struct ChorusFlangerParameters {
     chorus_rate_mode: RateMode,
     chorus_rate_hz: LinearFrequency,
     chorus_rate_note: NoteLength,
+    #[validate]
     chorus_depth: Level,
     chorus_balance: Balance,
     flanger_pre_delay: LogMilliseconds,
     flanger_rate_mode: RateMode,
     flanger_rate_hz: LinearFrequency,
     flanger_rate_note: NoteLength,
+    #[validate]
     flanger_depth: Level,
     flanger_feedback: Feedback,
     flanger_balance: Balance,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -3890,22 +4549,31 @@ impl Default for ChorusFlangerParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct SympatheticResonanceParameters {
+    #[validate]
     depth: Level,
+    #[validate]
     damper: Level,
     pre_lpf: LogFrequencyOrByPassOffByOne<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
     pre_hpf: ByPassOrLogFrequency<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
     peaking_freq: LogFrequency<200, 8000>,
+    #[validate]
     peaking_gain: Gain,
     peaking_q: QFactor,
     hf_damp: LogFrequencyOrByPassOffByOne<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
     lf_damp: ByPassOrLogFrequency<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
+    #[validate]
     lid_open: Int<1, 7>,
+    #[validate]
     level: Level,
+    #[validate]
     p_sft_amount: Level,
+    #[validate]
     p_sft_level: Level,
     p_sft_lpf: LogFrequencyOrByPassOffByOne<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
     p_sft_hpf: ByPassOrLogFrequency<16, 16000>, // technically this mislabels 15000 as 16000, but it really doesn't matter
+    #[validate]
     p_sft_to_rev: Level,
+    #[validate]
     damper_offset: UInt<0, 64>,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -1,6 +1,14 @@
+use std::fmt::Display;
+use std::str::FromStr;
 use super::numeric::Parameter;
 use crate::json::serialize_default_terminated_array;
+use crate::json::schema::{described_schema, unit_schema, single_property_schema_of};
 use schemars::JsonSchema;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use serde::Serialize;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 use validator::Validate;
 
 pub mod discrete;
@@ -9,8 +17,170 @@ pub mod chorus;
 pub mod reverb;
 pub mod mfx;
 
+/// The effects of each live set
+#[derive(Debug, Copy, Clone, PartialEq, EnumIter)]
+pub enum Effect {
+    Mfx,
+    Reverb,
+    Chorus
+}
+
+impl Display for Effect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mfx => write!(f, "MFX"),
+            Self::Reverb => write!(f, "Reverb"),
+            Self::Chorus => write!(f, "Chorus")
+        }
+    }
+}
+
+impl FromStr for Effect {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::iter().find(|e| e.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Effect should be one of 'mfx', 'reverb' or 'chorus', but found '{}'", s))
+    }
+}
+
 trait Parameters<const N: usize> : Validate + From<[Parameter; N]> + Default {
     fn parameters(&self) -> [Parameter; N];
+
+    fn describe(&self) -> Vec<ParameterInfo>;
+
+    fn fields() -> Vec<ParameterField>;
+}
+
+// the schema of one type of an effect, where the definition of its parameters is given a title, description and unit for
+// each field (which the derived schema doesn't have, as they are generated by the Parameters derive)
+fn parameters_schema_of<T: Parameters<N> + JsonSchema + Serialize, const N: usize>(effect: Effect, type_name: &str, gen: &mut SchemaGenerator) -> Schema {
+    let schema = effect_type_schema_of::<T>(effect, type_name, gen);
+    // the definition may have been renamed (eg. "DelayParameters2") where parameters of different effects share a name
+    let definition_name = match gen.subschema_for::<T>() {
+        Schema::Object(object) => object.reference.and_then(|r| r.strip_prefix(&gen.settings().definitions_path).map(|n| n.to_string())),
+        _ => None
+    };
+    if let Some(Schema::Object(definition)) = definition_name.and_then(|n| gen.definitions_mut().get_mut(&n)) {
+        for field in T::fields() {
+            let Some(property) = definition.object().properties.get_mut(field.name) else {
+                continue;
+            };
+            if matches!(property, Schema::Object(o) if o.metadata.as_ref().is_some_and(|m| m.description.is_some())) {
+                continue; // already described by another type which shares these parameters
+            }
+            let description = match field.unit {
+                None => format!("Not used by the {} {}, but kept so that the RDS file is unchanged", type_name, effect),
+                Some(_) => format!("{} of the {} {}", field.title, type_name, effect)
+            };
+            *property = described_schema(property.clone(), &field.title, &description);
+            if let Some(unit) = field.unit.filter(|u| !u.is_empty()) {
+                *property = unit_schema(property.clone(), unit);
+            }
+        }
+    }
+    schema
+}
+
+// the schema of one type of an effect, eg. {"Phaser": {...}}
+fn effect_type_schema_of<T: JsonSchema + Default + Serialize>(effect: Effect, type_name: &str, gen: &mut SchemaGenerator) -> Schema {
+    let mut schema = single_property_schema_of::<T>(type_name, gen);
+    if let Schema::Object(object) = &mut schema {
+        if let Some(property) = object.object().properties.get_mut(type_name) {
+            *property = described_schema(property.clone(), type_name, &format!("The parameters of the {} {}", type_name, effect));
+        }
+    }
+    schema
+}
+
+/// A type which an effect parameter is converted into, which can display its value in human readable units
+pub trait DisplayParameter {
+    fn unit() -> &'static str;
+
+    fn range() -> String;
+
+    fn display(&self) -> String;
+}
+
+/// The name, unit, range and formatted value of a single effect parameter, eg. "Rate: 2.40 Hz"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterInfo {
+    pub name: String,
+    pub unit: &'static str,
+    pub range: String,
+    pub value: String
+}
+
+impl ParameterInfo {
+    pub fn of<T: DisplayParameter>(field: &str, value: &T) -> Self {
+        Self {
+            name: Self::field_title(field, T::unit()),
+            unit: T::unit(),
+            range: T::range(),
+            value: value.display()
+        }
+    }
+
+    // fields whose names are misspelt, which are kept so that existing JSON files can still be read
+    const MISSPELT_WORDS: [(&'static str, &'static str); 2] = [
+        ("frequencey", "frequency"),
+        ("scartch", "scratch")
+    ];
+
+    // eg. "pre_delay" => "Pre Delay", "rate_hz" => "Rate" (because the unit is shown with the value), "band1_250hz" => "Band1 250Hz"
+    fn field_title(field: &str, unit: &str) -> String {
+        let mut words: Vec<&str> = field.split('_')
+            .map(|w| Self::MISSPELT_WORDS.iter().find(|(misspelt, _)| *misspelt == w).map_or(w, |(_, correct)| correct))
+            .collect();
+        if words.len() > 1 {
+            let last = words[words.len() - 1];
+            if last.eq_ignore_ascii_case(unit) || (last == "percent" && unit == "%") {
+                words.pop();
+            }
+        }
+        words.iter().map(|w| {
+            if let Some(number) = w.strip_suffix("hz").filter(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())) {
+                return format!("{}Hz", number);
+            }
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new()
+            }
+        }).collect::<Vec<String>>().join(" ")
+    }
+}
+
+impl Display for ParameterInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+/// The title and unit of a field of effect parameters, as given in the JSON schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterField {
+    pub name: &'static str,
+    pub title: String,
+    pub unit: Option<&'static str> // None for unused parameters
+}
+
+impl ParameterField {
+    pub fn of<T: DisplayParameter>(name: &'static str) -> Self {
+        Self {
+            name,
+            title: ParameterInfo::field_title(name, T::unit()),
+            unit: Some(T::unit())
+        }
+    }
+
+    pub fn unused(name: &'static str) -> Self {
+        Self {
+            name,
+            title: "Unused".into(),
+            unit: None
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate)]
@@ -34,6 +204,14 @@ impl<const N: usize> Parameters<N> for UnusedParameters<N> {
     fn parameters(&self) -> [Parameter; N] {
         self.unused
     }
+
+    fn describe(&self) -> Vec<ParameterInfo> {
+        Vec::new()
+    }
+
+    fn fields() -> Vec<ParameterField> {
+        vec![ParameterField::unused("unused")]
+    }
 }
 
 impl<const N: usize> Default for UnusedParameters<N> {
@@ -6,6 +6,7 @@ use validator::Validate;
 use crate::{json::{type_name_pretty, validation::out_of_range_err, schema::{u16_schema, i16_schema}}, roland::types::enums::Pan};
 
 use super::super::numeric::Parameter;
+use super::DisplayParameter;
 
 /// Parameter(0-2) === FilterType(Off, LowPassFilter, HighPassFilter)
 #[derive(Serialize, Deserialize, Debug, JsonSchema, EnumIter, EnumParameter, PartialEq, Copy, Clone)]
@@ -367,6 +368,20 @@ impl From<Parameter> for Switch {
     }
 }
 
+impl DisplayParameter for Switch {
+    fn unit() -> &'static str {
+        ""
+    }
+
+    fn range() -> String {
+        "Off, On".into()
+    }
+
+    fn display(&self) -> String {
+        if self.0 { "On" } else { "Off" }.into()
+    }
+}
+
 /// Parameter(MIN-MAX) === UInt(MIN-MAX)
 #[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct UInt<const MIN: u16, const MAX: u16>(pub u16);
@@ -425,6 +440,20 @@ impl<const MIN: u16, const MAX: u16> Validate for UInt<MIN, MAX> {
     }
 }
 
+impl<const MIN: u16, const MAX: u16> DisplayParameter for UInt<MIN, MAX> {
+    fn unit() -> &'static str {
+        ""
+    }
+
+    fn range() -> String {
+        format!("{} to {}", MIN, MAX)
+    }
+
+    fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
 pub type Level = UInt<0, 127>;
 pub type LinearMilliseconds<const MAX: u16> = UInt<1, MAX>;
 pub type PreLpf = UInt<0, 7>;
@@ -481,6 +510,20 @@ impl<const MIN: isize, const MAX: isize> Validate for Int<MIN, MAX> {
     }
 }
 
+impl<const MIN: isize, const MAX: isize> DisplayParameter for Int<MIN, MAX> {
+    fn unit() -> &'static str {
+        ""
+    }
+
+    fn range() -> String {
+        format!("{} to {}", MIN, MAX)
+    }
+
+    fn display(&self) -> String {
+        self.0.to_string()
+    }
+}
+
 pub type Gain = Int<-15, 15>;
 pub type DampGain = Int<-36, 0>;
 pub type BoostGain = Int<-60, 4>;
@@ -508,4 +551,22 @@ impl From<Parameter> for Pan {
         }
         (value.0 as u8).into()
     }
+}
+
+impl DisplayParameter for Pan {
+    fn unit() -> &'static str {
+        ""
+    }
+
+    fn range() -> String {
+        format!("{} to {}", Pan::from(0).display(), Pan::from(127).display())
+    }
+
+    fn display(&self) -> String {
+        match self {
+            Pan::Left(l) => format!("L{}", l),
+            Pan::Centre => "Centre".into(),
+            Pan::Right(r) => format!("{}R", r)
+        }
+    }
 }
\ No newline at end of file
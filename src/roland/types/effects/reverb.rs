@@ -1,12 +1,13 @@
+use std::str::FromStr;
 use schemars::JsonSchema;
 use validator::Validate;
-use crate::json::schema::{one_of_schema, single_property_schema_of};
+use crate::json::schema::one_of_schema;
 use crate::json::{serialize_default_terminated_array, type_name_pretty};
 
 use crate::roland::types::numeric::Parameter;
 use super::discrete::{LogMilliseconds, DiscreteValues, LogFrequency, LogFrequencyOrByPass};
 use super::parameters::{ReverbCharacter, Gm2ReverbCharacter, Level, PreLpf, Size, UInt, Int, DampGain};
-use super::{UnusedParameters, Parameters};
+use super::{UnusedParameters, Parameters, ParameterInfo, ParameterField, Effect, parameters_schema_of};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub enum ReverbType { // 0-6
@@ -33,6 +34,20 @@ impl ReverbType {
         }
     }
 
+    // the default parameters of a type, as loaded by the keyboard when the type is changed
+    pub fn default_for(number: u8) -> Self {
+        match number {
+            0 => Self::Off(Default::default()),
+            1 => Self::Reverb(Default::default()),
+            2 => Self::Room(Default::default()),
+            3 => Self::Hall(Default::default()),
+            4 => Self::Plate(Default::default()),
+            5 => Self::Gm2Reverb(Default::default()),
+            6 => Self::Cathedral(Default::default()),
+            _ => panic!("Invalid reverb type: {}", number)
+        }
+    }
+
     pub fn number(&self) -> u8 {
         match self {
             Self::Off(_) => 0,
@@ -69,7 +84,18 @@ impl ReverbType {
         }
     }
 
-    #[allow(dead_code)] // used by tests, potentially useful if using this as a library
+    pub fn describe(&self) -> Vec<ParameterInfo> {
+        match self {
+            Self::Off(u) => u.describe(),
+            Self::Reverb(re) => re.describe(),
+            Self::Room(ro) => ro.describe(),
+            Self::Hall(h) => h.describe(),
+            Self::Plate(p) => p.describe(),
+            Self::Gm2Reverb(g) => g.describe(),
+            Self::Cathedral(c) => c.describe()
+        }
+    }
+
     pub fn default(&self) -> Self {
         match self {
             Self::Off(_) => Self::Off(Default::default()),
@@ -96,6 +122,17 @@ impl Default for ReverbType {
     }
 }
 
+impl FromStr for ReverbType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let types: Vec<Self> = (0..=6).map(Self::default_for).collect();
+        let names: Vec<String> = types.iter().map(|t| t.name().to_string()).collect();
+        types.into_iter().find(|t| t.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Reverb type should be one of {}, but found '{}'", names.join(", "), s))
+    }
+}
+
 impl Validate for ReverbType {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -117,23 +154,30 @@ impl JsonSchema for ReverbType {
 
     fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
-            single_property_schema_of::<UnusedParameters<20>>("Off", gen),
-            single_property_schema_of::<ReverbParameters>("Reverb", gen),
-            single_property_schema_of::<CharacterParameters<50, 64>>("Room", gen),
-            single_property_schema_of::<CharacterParameters<76, 70>>("Hall", gen),
-            single_property_schema_of::<CharacterParameters<66, 64>>("Plate", gen),
-            single_property_schema_of::<Gm2ReverbParameters>("Gm2Reverb", gen),
-            single_property_schema_of::<CathedralParameters>("Cathedral", gen)
+            schema_of::<UnusedParameters<20>>("Off", gen),
+            schema_of::<ReverbParameters>("Reverb", gen),
+            schema_of::<CharacterParameters<50, 64>>("Room", gen),
+            schema_of::<CharacterParameters<76, 70>>("Hall", gen),
+            schema_of::<CharacterParameters<66, 64>>("Plate", gen),
+            schema_of::<Gm2ReverbParameters>("Gm2Reverb", gen),
+            schema_of::<CathedralParameters>("Cathedral", gen)
         ])
     }
 }
 
+fn schema_of<T: Parameters<20> + JsonSchema + serde::Serialize>(type_name: &str, gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+    parameters_schema_of::<T, 20>(Effect::Reverb, type_name, gen)
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct ReverbParameters {
     character: ReverbCharacter,
+    #[validate]
     time: Level,
     hf_damp: LogFrequencyOrByPass<200, 8000>,
+    #[validate]
     delay_feedback: Level,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -158,15 +202,22 @@ impl Default for ReverbParameters {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct CharacterParameters<const DEFAULT_MS: usize, const DEFAULT_TIME: u16> {
     pre_delay: LogMilliseconds,
+    #[validate]
     time: Level,
+    #[validate]
     size: Size,
     high_cut: LogFrequencyOrByPass<160, 12500>, // technically this mislabels 320 as 315 and 640 as 630, but it really doesn't matter
+    #[validate]
     density: Level,
+    #[validate]
     diffusion: Level,
     lf_damp_freq: LogFrequency<50, 4000>, // technically this mislabels 64 as 63 and 320 as 315, but it really doesn't matter
+    #[validate]
     lf_damp_gain: DampGain,
     hf_damp_freq: LogFrequency<4000, 12500>, // technically this mislabels 6400 as 6300, but it really doesn't matter
+    #[validate]
     hf_damp_gain: DampGain,
+    #[validate]
     level: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -197,9 +248,13 @@ impl<const DMS: usize, const DT: u16> Default for CharacterParameters<DMS, DT> {
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct Gm2ReverbParameters {
     character: Gm2ReverbCharacter,
+    #[validate]
     pre_lpf: PreLpf,
+    #[validate]
     level: Level,
+    #[validate]
     time: Level,
+    #[validate]
     delay_feedback: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
@@ -223,8 +278,11 @@ impl Default for Gm2ReverbParameters {
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Validate, Parameters)]
 pub struct CathedralParameters {
+    #[validate]
     pre_lpf: PreLpf,
+    #[validate]
     level: Level,
+    #[validate]
     time: Level,
     #[serde(deserialize_with = "serialize_default_terminated_array::deserialize")]
     #[serde(serialize_with = "serialize_default_terminated_array::serialize")]
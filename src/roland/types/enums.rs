@@ -1,9 +1,11 @@
+use std::str::FromStr;
+
 use schemars::JsonSchema;
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, Display};
 use validator::Validate;
 
-use crate::json::{validation::{validate_control_change, out_of_range_err, unused_by_rd300nx_err}, type_name_pretty, schema::{one_of_schema, enum_schema, u8_schema, single_property_schema, enum_except_one_schema}};
+use crate::json::{serialize_map_keys_in_order::MapKey, validation::{validate_control_change, out_of_range_err, unused_by_rd300nx_err}, type_name_pretty, schema::{one_of_schema, enum_schema, u8_schema, single_property_schema, enum_except_one_schema, described_schema}};
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, EnumIter, JsonSchema)]
 pub enum OutputPort { // 0-5
@@ -218,9 +220,9 @@ impl JsonSchema for Pan {
 
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
-            single_property_schema("Left", u8_schema(1, Self::MAX_L)),
+            single_property_schema("Left", described_schema(u8_schema(1, Self::MAX_L), "Left", "How far the sound is panned to the left of centre")),
             enum_schema(vec!["Centre".into()]),
-            single_property_schema("Right", u8_schema(1, Self::MAX_R))
+            single_property_schema("Right", described_schema(u8_schema(1, Self::MAX_R), "Right", "How far the sound is panned to the right of centre"))
         ])
     }
 }
@@ -319,6 +321,12 @@ pub enum Layer { // 0-3
     UnusedLower2
 }
 
+impl MapKey for Layer {
+    fn describe(&self) -> String {
+        format!("the {} layer", self)
+    }
+}
+
 impl From<u8> for Layer {
     fn from(value: u8) -> Self {
         Self::iter().nth(value as usize).unwrap()
@@ -337,6 +345,15 @@ impl Default for Layer {
     }
 }
 
+impl FromStr for Layer {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::iter().filter(|l| *l != Self::UnusedLower2).find(|l| l.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Layer should be one of 'upper1', 'upper2' or 'lower1', but found '{}'", s))
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, JsonSchema)]
 pub enum KeyOffPosition {
     Standard,
@@ -450,7 +467,7 @@ impl JsonSchema for KeyTouchVelocity {
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
             enum_schema(vec!["Real".into()]),
-            single_property_schema("Fixed", u8_schema(Self::MIN_FIXED, Self::MAX_FIXED))
+            single_property_schema("Fixed", described_schema(u8_schema(Self::MIN_FIXED, Self::MAX_FIXED), "Fixed", "The velocity of every note, however hard the keys are played"))
         ])
     }
 }
@@ -567,7 +584,7 @@ impl JsonSchema for SoundFocusType {
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
             enum_except_one_schema::<SoundFocusType>("Other"),
-            single_property_schema("Other", u8_schema(Self::MIN_OTHER, Self::MAX_OTHER))
+            single_property_schema("Other", described_schema(u8_schema(Self::MIN_OTHER, Self::MAX_OTHER), "Other", "Number of a sound focus type which has no name"))
         ])
     }
 }
@@ -643,6 +660,12 @@ impl Into<u8> for MidiChannel {
     }
 }
 
+impl MapKey for MidiChannel {
+    fn describe(&self) -> String {
+        format!("MIDI channel {}", Into::<u8>::into(*self) + 1)
+    }
+}
+
 impl Default for MidiChannel {
     fn default() -> Self {
         Self::from(0)
@@ -700,7 +723,7 @@ impl JsonSchema for VoiceReserve {
 
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
-            single_property_schema("Voices", u8_schema(0, Self::MAX_VOICES)),
+            single_property_schema("Voices", described_schema(u8_schema(0, Self::MAX_VOICES), "Voices", "Number of voices reserved for the MIDI channel")),
             enum_schema(vec!["Full".into()]),
         ])
     }
@@ -901,7 +924,7 @@ impl JsonSchema for PedalFunction {
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
             enum_except_one_schema::<PedalFunction>("ControlChange"),
-            single_property_schema("ControlChange", u8_schema(0, 127))
+            single_property_schema("ControlChange", described_schema(u8_schema(0, 127), "Control Change", "MIDI control change number which the pedal sends"))
         ])
     }
 }
@@ -992,7 +1015,7 @@ impl JsonSchema for SliderFunction {
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema(vec![
             enum_except_one_schema::<SliderFunction>("ControlChange"),
-            single_property_schema("ControlChange", u8_schema(0, 127))
+            single_property_schema("ControlChange", described_schema(u8_schema(0, 127), "Control Change", "MIDI control change number which the slider sends"))
         ])
     }
 }
@@ -1026,3 +1049,13 @@ impl Default for Temperament {
         Self::from(0)
     }
 }
+
+impl FromStr for Temperament {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let simplified: String = s.chars().filter(|c| c.is_ascii_alphanumeric()).collect();
+        Self::iter().find(|t| format!("{:?}", t).eq_ignore_ascii_case(&simplified))
+            .ok_or_else(|| format!("Temperament should be one of {}, but found '{}'", Self::iter().map(|t| format!("{:?}", t)).collect::<Vec<_>>().join(", "), s))
+    }
+}
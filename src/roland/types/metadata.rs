@@ -2,9 +2,9 @@ use std::borrow::Cow;
 
 use schemars::JsonSchema;
 use validator::{Validate, ValidationError, ValidationErrors};
-use crate::{roland::rd300nx::RD300NX, json::{schema::{array_schema, u8_schema, object_schema, single_property_schema_of, one_of_schema_with_default}, validation::{out_of_range_err, merge_all_fixed}, type_name_pretty}};
+use crate::{roland::rd300nx::RD300NX, json::{schema::{array_schema, u8_schema, object_schema, described_property_schema_of, one_of_schema, one_of_schema_with_default, single_property_schema, described_schema}, validation::{out_of_range_err, merge_all_fixed}, type_name_pretty}};
 
-#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug)]
 pub enum ToneRemain {
     Always(bool),
     BySet(ToneRemainSets)
@@ -19,6 +19,19 @@ impl ToneRemain {
     }
 }
 
+impl JsonSchema for ToneRemain {
+    fn schema_name() -> String {
+        type_name_pretty::<Self>().into()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        one_of_schema(vec![
+            single_property_schema("Always", described_schema(gen.subschema_for::<bool>(), "Always", "Whether held notes keep sounding when changing between any live sets")),
+            single_property_schema("BySet", described_schema(gen.subschema_for::<ToneRemainSets>(), "By Set", "Which changes between the live sets of each bank keep held notes sounding"))
+        ])
+    }
+}
+
 impl Validate for ToneRemain {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -51,9 +64,9 @@ impl JsonSchema for ToneRemainSets {
 
     fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         object_schema(vec![
-            ("user_sets", gen.subschema_for::<BySet<{RD300NX::USER_SETS}>>()),
-            ("piano", gen.subschema_for::<BySet<{RD300NX::PIANO_SETS}>>()),
-            ("e_piano", gen.subschema_for::<BySet<{RD300NX::E_PIANO_SETS}>>())
+            ("user_sets", described_schema(gen.subschema_for::<BySet<{RD300NX::USER_SETS}>>(), "User Sets", "Changes between user live sets which keep held notes sounding")),
+            ("piano", described_schema(gen.subschema_for::<BySet<{RD300NX::PIANO_SETS}>>(), "Piano", "Changes between piano live sets which keep held notes sounding")),
+            ("e_piano", described_schema(gen.subschema_for::<BySet<{RD300NX::E_PIANO_SETS}>>(), "E. Piano", "Changes between e. piano live sets which keep held notes sounding"))
         ], Some(serde_json::to_value(Self::default()).unwrap()))
     }
 }
@@ -96,9 +109,9 @@ impl<const N: usize> JsonSchema for BySet<N> {
 
     fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         one_of_schema_with_default(vec![
-            single_property_schema_of::<bool>("Always", gen),
-            single_property_schema_of::<ToneRemainIndicies<N>>("ByIndex", gen),
-            single_property_schema_of::<ToneRemainRanges<N>>("ByRange", gen)
+            described_property_schema_of::<bool>("Always", "Always", "Whether every change to the next live set keeps held notes sounding", gen),
+            described_property_schema_of::<ToneRemainIndicies<N>>("ByIndex", "By Index", "Indices (from 0) of the live sets whose held notes keep sounding when changing to the next live set", gen),
+            described_property_schema_of::<ToneRemainRanges<N>>("ByRange", "By Range", "Ranges of live sets within which held notes keep sounding when changing to the next live set", gen)
         ], Some(serde_json::to_value(Self::default()).unwrap()))
     }
 }
@@ -153,6 +166,10 @@ impl<const N: usize> Validate for ToneRemainIndicies<N> {
 pub struct ToneRemainRanges<const N: usize>(Vec<PatchRange<N>>);
 
 impl<const N: usize> ToneRemainRanges<N> {
+    pub fn new(ranges: Vec<PatchRange<N>>) -> Self {
+        Self(ranges)
+    }
+
     pub fn any(&self) -> bool {
         self.0.iter().any(|pr| pr.any())
     }
@@ -181,6 +198,12 @@ impl<const N: usize> PatchRange<N> {
         }
     }
 
+    pub fn new(from: usize, to: usize) -> Self {
+        let range = Self { from, to };
+        range.check_valid();
+        range
+    }
+
     pub fn any(&self) -> bool {
         self.check_valid();
         self.to > self.from
@@ -209,8 +232,8 @@ impl<const N: usize> JsonSchema for PatchRange<N> {
 
     fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
         object_schema(vec![
-            ("from", u8_schema(1, N as u8)),
-            ("to", u8_schema(1, N as u8))
+            ("from", described_schema(u8_schema(1, N as u8), "From", "Number (from 1) of the first live set in the range")),
+            ("to", described_schema(u8_schema(1, N as u8), "To", "Number (from 1) of the last live set in the range, which is changed to but not from"))
         ], Some(serde_json::to_value(Self::default()).unwrap()))
     }
 }
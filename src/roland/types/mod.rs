@@ -8,6 +8,8 @@ pub mod metadata;
 
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, JsonSchema)]
 pub struct StateMap<T> {
+    #[schemars(title = "On", description = "The value when the switch is on")]
     pub on: T,
+    #[schemars(title = "Off", description = "The value when the switch is off")]
     pub off: T
 }
\ No newline at end of file
@@ -1,8 +1,12 @@
 
+use std::str::FromStr;
+
 use schemars::JsonSchema;
 use strum::IntoEnumIterator;
 use strum_macros::{EnumIter, Display};
 
+use crate::json::serialize_map_keys_in_order::MapKey;
+
 #[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, EnumIter, JsonSchema, PartialOrd)]
 pub enum PianoKey { // 0-87
     A0,
@@ -257,6 +261,12 @@ impl Into<u8> for MidiNote {
     }
 }
 
+impl MapKey for MidiNote {
+    fn describe(&self) -> String {
+        format!("MIDI note {}", Into::<u8>::into(*self))
+    }
+}
+
 impl Default for MidiNote {
     fn default() -> Self {
         Self::from(0)
@@ -295,4 +305,19 @@ impl Default for KeyNote {
     fn default() -> Self {
         Self::from(0)
     }
+}
+
+// either flat names (eg. 'Eb') or sharp names (eg. 'D#') are accepted
+impl FromStr for KeyNote {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, offset) = match s.strip_suffix('#') {
+            Some(natural) => (natural, 1),
+            None => (s, 0)
+        };
+        Self::iter().position(|k| format!("{:?}", k).eq_ignore_ascii_case(name))
+            .map(|i| Self::from(((i + offset) % 12) as u8))
+            .ok_or_else(|| format!("Key should be a note name such as 'C', 'Eb' or 'F#', but found '{}'", s))
+    }
 }
\ No newline at end of file
@@ -64,6 +64,14 @@ pub struct OffsetU8<const OFFSET: u8, const MIN: u8, const MAX: u8>(i8); // MIN(
 
 impl<const O: u8, const L: u8, const H: u8> OffsetU8<O, L, H> {
     pub const ZERO: u8 = O;
+
+    pub fn new(value: i8) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> i8 {
+        self.0
+    }
 }
 
 impl<const O: u8, const L: u8, const H: u8> From<u8> for OffsetU8<O, L, H> {
@@ -209,6 +217,10 @@ pub struct Offset1Dp<const OFFSET: u16, const MIN: u16, const MAX: u16>(f64); //
 
 impl<const O: u16, const L: u16, const H: u16> Offset1Dp<O, L, H> {
     const ZERO: u16 = O;
+
+    pub fn value(&self) -> f64 {
+        self.0
+    }
 }
 
 impl<const O: u16, const L: u16, const H: u16> From<u16> for Offset1Dp<O, L, H> {